@@ -0,0 +1,14 @@
+-- Single-use sign-in nonces bound to a wallet public key
+CREATE TABLE IF NOT EXISTS auth_nonces (
+    nonce TEXT PRIMARY KEY,
+    public_key TEXT NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    consumed_at TIMESTAMP WITH TIME ZONE NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_nonces_public_key
+    ON auth_nonces (public_key);
+
+CREATE INDEX IF NOT EXISTS idx_auth_nonces_expires_at
+    ON auth_nonces (expires_at);
@@ -0,0 +1,18 @@
+-- Sessions issued after a verified wallet signature. Tokens are stored as SHA-256 hashes.
+CREATE TABLE IF NOT EXISTS auth_sessions (
+    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    public_key TEXT NOT NULL,
+    access_token_hash TEXT NOT NULL UNIQUE,
+    refresh_token_hash TEXT NOT NULL UNIQUE,
+    access_expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    refresh_expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    revoked_at TIMESTAMP WITH TIME ZONE NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_sessions_public_key
+    ON auth_sessions (public_key);
+
+CREATE INDEX IF NOT EXISTS idx_auth_sessions_refresh_expires_at
+    ON auth_sessions (refresh_expires_at);
@@ -0,0 +1,23 @@
+-- Wallets allowed to use the admin API, in addition to ADMIN_PUBLIC_KEYS
+CREATE TABLE IF NOT EXISTS admins (
+    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    public_key TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+-- Append-only record of every admin mutation
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    admin_public_key TEXT NOT NULL,
+    action TEXT NOT NULL,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NULL,
+    payload JSONB NOT NULL DEFAULT '{}'::JSONB,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_created_at
+    ON admin_audit_log (created_at DESC);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_entity
+    ON admin_audit_log (entity_type, entity_id);
@@ -0,0 +1,17 @@
+-- Soft-archiving and ordering for admin-managed badges and badge groups
+ALTER TABLE badges
+ADD COLUMN IF NOT EXISTS archived_at TIMESTAMP WITH TIME ZONE NULL;
+
+ALTER TABLE badge_groups
+ADD COLUMN IF NOT EXISTS sort_order INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS archived_at TIMESTAMP WITH TIME ZONE NULL;
+
+UPDATE badge_groups SET sort_order = id * 10;
+
+CREATE INDEX IF NOT EXISTS idx_badge_groups_sort_order
+    ON badge_groups (sort_order);
+
+-- Identity sequences were never advanced by the seed inserts with explicit ids
+SELECT setval(pg_get_serial_sequence('badges', 'id'), (SELECT COALESCE(MAX(id), 1) FROM badges));
+SELECT setval(pg_get_serial_sequence('badge_groups', 'id'), (SELECT COALESCE(MAX(id), 1) FROM badge_groups));
+SELECT setval(pg_get_serial_sequence('badge_conditions', 'id'), (SELECT COALESCE(MAX(id), 1) FROM badge_conditions));
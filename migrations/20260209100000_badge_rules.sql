@@ -0,0 +1,39 @@
+-- Composable badge rules, evaluated in the application, replace badge_conditions
+ALTER TABLE badges
+ADD COLUMN IF NOT EXISTS rule JSONB NULL;
+
+-- Conditions were evaluated independently, so several conditions on one badge behave as "or"
+UPDATE badges b
+SET rule = converted.rule
+FROM (
+    SELECT
+        leaves.badge_id,
+        CASE
+            WHEN COUNT(*) = 1 THEN (ARRAY_AGG(leaves.leaf))[1]
+            ELSE jsonb_build_object('op', 'or', 'rules', jsonb_agg(leaves.leaf ORDER BY leaves.id))
+        END AS rule
+    FROM (
+        SELECT
+            bc.id,
+            bc.badge_id,
+            jsonb_build_object(
+                'op', bc.operation,
+                'signal', pet.event_type,
+                'value', bc.required_count
+            ) AS leaf
+        FROM badge_conditions bc
+        INNER JOIN progression_event_types pet ON bc.progression_event_type_id = pet.id
+    ) leaves
+    GROUP BY leaves.badge_id
+) converted
+WHERE b.id = converted.badge_id AND b.rule IS NULL;
+
+DROP TABLE IF EXISTS badge_conditions;
+
+-- Start of the current streak of a positive progress count, for "held for N days" rules
+ALTER TABLE beta_applicant_progressions
+ADD COLUMN IF NOT EXISTS held_since TIMESTAMP WITH TIME ZONE NULL;
+
+UPDATE beta_applicant_progressions
+SET held_since = created_at
+WHERE progress_count > 0 AND held_since IS NULL;
@@ -0,0 +1,21 @@
+-- Append-only history of progression values; beta_applicant_progressions keeps the latest value
+CREATE TABLE IF NOT EXISTS progression_events (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    beta_applicant_id INTEGER NOT NULL REFERENCES beta_applicants(id) ON DELETE CASCADE,
+    progression_event_type_id INTEGER NOT NULL REFERENCES progression_event_types(id) ON DELETE CASCADE,
+    value INTEGER NOT NULL,
+    source TEXT NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_progression_events_applicant_type_recorded_at
+    ON progression_events (beta_applicant_id, progression_event_type_id, recorded_at);
+
+INSERT INTO progression_events (beta_applicant_id, progression_event_type_id, value, source, recorded_at)
+SELECT bap.beta_applicant_id, bap.progression_event_type_id, bap.progress_count, 'backfill', bap.created_at
+FROM beta_applicant_progressions bap
+WHERE NOT EXISTS (
+    SELECT 1 FROM progression_events pe
+    WHERE pe.beta_applicant_id = bap.beta_applicant_id
+      AND pe.progression_event_type_id = bap.progression_event_type_id
+);
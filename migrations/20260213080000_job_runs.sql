@@ -0,0 +1,13 @@
+-- Status records of scheduled background jobs
+CREATE TABLE IF NOT EXISTS job_runs (
+    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    job_name TEXT NOT NULL,
+    status TEXT NOT NULL,
+    summary JSONB NOT NULL DEFAULT '{}'::jsonb,
+    error TEXT NULL,
+    started_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    finished_at TIMESTAMP WITH TIME ZONE NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_runs_job_name_started_at
+    ON job_runs (job_name, started_at DESC);
@@ -0,0 +1,31 @@
+-- Leaderboard seasons; leaderboard_entries always holds the standings of the open season
+CREATE TABLE IF NOT EXISTS seasons (
+    id INTEGER GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    name TEXT NOT NULL,
+    starts_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    ends_at TIMESTAMP WITH TIME ZONE NULL,
+    finalized_at TIMESTAMP WITH TIME ZONE NULL
+);
+
+-- At most one season is open at a time
+CREATE UNIQUE INDEX IF NOT EXISTS idx_seasons_open
+    ON seasons ((finalized_at IS NULL)) WHERE finalized_at IS NULL;
+
+-- Final standings of finalized seasons
+CREATE TABLE IF NOT EXISTS season_standings (
+    season_id INTEGER NOT NULL REFERENCES seasons(id) ON DELETE CASCADE,
+    beta_applicant_id INTEGER NOT NULL REFERENCES beta_applicants(id) ON DELETE CASCADE,
+    public_key TEXT NOT NULL,
+    total_score INTEGER NOT NULL,
+    rank INTEGER NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    PRIMARY KEY (season_id, beta_applicant_id),
+    UNIQUE (season_id, rank)
+);
+
+CREATE INDEX IF NOT EXISTS idx_season_standings_public_key
+    ON season_standings (season_id, public_key);
+
+INSERT INTO seasons (name, starts_at)
+SELECT 'Season 0', COALESCE((SELECT MIN(created_at) FROM leaderboard_entries), NOW())
+WHERE NOT EXISTS (SELECT 1 FROM seasons);
@@ -0,0 +1,14 @@
+-- Rank and score of every leaderboard entry after each refresh
+CREATE TABLE IF NOT EXISTS leaderboard_snapshots (
+    id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+    beta_applicant_id INTEGER NOT NULL REFERENCES beta_applicants(id) ON DELETE CASCADE,
+    season_id INTEGER NULL REFERENCES seasons(id) ON DELETE CASCADE,
+    rank INTEGER NOT NULL,
+    total_score INTEGER NOT NULL,
+    taken_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_leaderboard_snapshots_applicant_taken_at
+    ON leaderboard_snapshots (beta_applicant_id, taken_at);
+CREATE INDEX IF NOT EXISTS idx_leaderboard_snapshots_season_taken_at
+    ON leaderboard_snapshots (season_id, taken_at);
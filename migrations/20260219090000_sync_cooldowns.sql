@@ -0,0 +1,5 @@
+-- Last self-service sync per applicant, used to enforce a cooldown across replicas
+CREATE TABLE IF NOT EXISTS sync_cooldowns (
+    beta_applicant_id INTEGER PRIMARY KEY REFERENCES beta_applicants(id) ON DELETE CASCADE,
+    last_requested_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,21 @@
+-- Tokens whose wallet balances are synced into progressions. Each token feeds exactly one
+-- progression event type; the built-in CATICS and JUP rows are seeded by the application
+-- from the former CATICS_TOKEN_ADDRESS and JUP_TOKEN_ADDRESS settings.
+CREATE TABLE IF NOT EXISTS tracked_tokens (
+    id SERIAL PRIMARY KEY,
+    mint TEXT NOT NULL,
+    symbol TEXT NOT NULL,
+    decimals INTEGER NOT NULL CHECK (decimals BETWEEN 0 AND 18),
+    kind TEXT NOT NULL CHECK (kind IN ('held', 'staked')),
+    progression_event_type_id INTEGER NOT NULL UNIQUE REFERENCES progression_event_types(id),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (mint, kind)
+);
+
+-- The built-in event types were inserted with explicit ids, so the identity sequence never
+-- advanced. Signals created at runtime start at 1000, leaving room for future built-ins.
+SELECT setval(
+    pg_get_serial_sequence('progression_event_types', 'id'),
+    GREATEST((SELECT MAX(id) FROM progression_event_types), 999)
+);
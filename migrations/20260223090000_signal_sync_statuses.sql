@@ -0,0 +1,11 @@
+-- Outcome of the latest sync of each signal per applicant. Failed syncs keep the stored
+-- progress, so last_success_at tells users how current it is.
+CREATE TABLE IF NOT EXISTS signal_sync_statuses (
+    beta_applicant_id INTEGER NOT NULL REFERENCES beta_applicants(id) ON DELETE CASCADE,
+    progression_event_type_id INTEGER NOT NULL REFERENCES progression_event_types(id) ON DELETE CASCADE,
+    status TEXT NOT NULL CHECK (status IN ('synced', 'unavailable', 'skipped')),
+    error TEXT,
+    last_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_success_at TIMESTAMPTZ,
+    PRIMARY KEY (beta_applicant_id, progression_event_type_id)
+);
@@ -0,0 +1,3 @@
+-- Token balances are stored exactly. INTEGER saturated above ~2.1 billion and dropped fractions.
+ALTER TABLE beta_applicant_progressions ALTER COLUMN progress_count TYPE NUMERIC;
+ALTER TABLE progression_events ALTER COLUMN value TYPE NUMERIC;
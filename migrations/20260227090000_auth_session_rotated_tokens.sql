@@ -0,0 +1,6 @@
+-- Refresh tokens that were rotated away. One presented again has leaked, so its session is revoked.
+CREATE TABLE IF NOT EXISTS auth_session_rotated_tokens (
+    refresh_token_hash TEXT PRIMARY KEY,
+    session_id INTEGER NOT NULL REFERENCES auth_sessions (id) ON DELETE CASCADE,
+    rotated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL
+);
@@ -0,0 +1,7 @@
+-- Cron slot a scheduled run belongs to, so that each slot runs once across all replicas.
+-- On-demand runs leave it NULL and never conflict.
+ALTER TABLE job_runs
+ADD COLUMN IF NOT EXISTS scheduled_for TIMESTAMP WITH TIME ZONE NULL;
+
+ALTER TABLE job_runs
+ADD CONSTRAINT job_runs_job_name_scheduled_for_key UNIQUE (job_name, scheduled_for);
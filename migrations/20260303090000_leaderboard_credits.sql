@@ -0,0 +1,15 @@
+-- Days whose badge totals were added to the scores of a season; a refresh credits each day once
+CREATE TABLE IF NOT EXISTS leaderboard_credits (
+    season_id INTEGER NOT NULL REFERENCES seasons(id) ON DELETE CASCADE,
+    credited_on DATE NOT NULL,
+    credited_at TIMESTAMP WITH TIME ZONE DEFAULT NOW() NOT NULL,
+    PRIMARY KEY (season_id, credited_on)
+);
+
+-- Every earlier refresh left snapshots behind
+INSERT INTO leaderboard_credits (season_id, credited_on, credited_at)
+SELECT season_id, (taken_at AT TIME ZONE 'UTC')::DATE, MIN(taken_at)
+FROM leaderboard_snapshots
+WHERE season_id IS NOT NULL
+GROUP BY season_id, (taken_at AT TIME ZONE 'UTC')::DATE
+ON CONFLICT DO NOTHING;
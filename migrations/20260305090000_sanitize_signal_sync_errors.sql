@@ -0,0 +1,12 @@
+-- Sync errors used to be stored with their full text, including SQL and upstream response
+-- details. Keep only what the HTTP error layer would show for them.
+UPDATE signal_sync_statuses
+SET error = CASE
+    WHEN error LIKE 'Upstream error from %:%'
+        THEN 'Upstream provider ' || split_part(substring(error FROM 21), ':', 1) || ' is unavailable'
+    WHEN error LIKE 'Database error:%' THEN 'Database exception'
+    WHEN error LIKE 'Not found:%' THEN 'Resource not found'
+    WHEN error LIKE 'Bad request: %' THEN substring(error FROM 14)
+    ELSE 'Internal server error'
+END
+WHERE status = 'unavailable' AND error IS NOT NULL;
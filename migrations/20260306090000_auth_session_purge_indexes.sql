@@ -0,0 +1,6 @@
+-- Lets the scheduled auth purge find rotated refresh tokens past the refresh lifetime.
+CREATE INDEX IF NOT EXISTS idx_auth_session_rotated_tokens_rotated_at
+    ON auth_session_rotated_tokens (rotated_at);
+
+CREATE INDEX IF NOT EXISTS idx_auth_session_rotated_tokens_session_id
+    ON auth_session_rotated_tokens (session_id);
@@ -1 +1,4 @@
+pub mod solana_rpc;
 pub mod wallet_holdings;
+pub mod wallet_holdings_cache;
+pub mod wallet_holdings_fallback;
@@ -0,0 +1,346 @@
+use crate::adapters::client::wallet_holdings::staked_unavailable;
+use crate::app_error::AppResult;
+use crate::entities::token_amount::TokenAmount;
+use crate::entities::wallet_holdings::WalletHoldings;
+use crate::infrastructure::solana_rpc::{
+    SolanaRpcWalletHoldingsClient, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+use crate::use_cases::wallet_holdings::WalletHoldingsClient;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JsonRpcRequest {
+    pub jsonrpc: &'static str,
+    pub id: u64,
+    pub method: &'static str,
+    pub params: Value,
+}
+
+impl JsonRpcRequest {
+    pub fn new(method: &'static str, params: Value) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id: 1,
+            method,
+            params,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonRpcResponse<T> {
+    pub result: Option<T>,
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAccountsResult {
+    pub value: Vec<TokenAccount>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAccount {
+    pub pubkey: String,
+    pub account: TokenAccountData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAccountData {
+    pub data: ParsedAccountData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParsedAccountData {
+    pub parsed: ParsedTokenAccount,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ParsedTokenAccount {
+    pub info: TokenAccountInfo,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokenAccountInfo {
+    pub mint: String,
+    #[serde(rename = "tokenAmount")]
+    pub token_amount: UiTokenAmount,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UiTokenAmount {
+    pub amount: String,
+    pub decimals: u8,
+}
+
+#[async_trait]
+impl WalletHoldingsClient for SolanaRpcWalletHoldingsClient {
+    async fn get_wallet_holdings(&self, public_key: &str) -> AppResult<WalletHoldings> {
+        let (token_accounts, token_2022_accounts, staked_jup) = tokio::join!(
+            self.get_token_accounts(public_key, TOKEN_PROGRAM_ID),
+            self.get_token_accounts(public_key, TOKEN_2022_PROGRAM_ID),
+            self.get_staked_jup(public_key),
+        );
+        // Staked JUP comes from the Catalytics API, so its failure only affects staked balances
+        let mut token_accounts = token_accounts?;
+        token_accounts.extend(token_2022_accounts?);
+
+        self.convert_to_wallet_holdings(public_key.to_string(), token_accounts, staked_jup)
+    }
+
+    async fn get_token_balance(
+        &self,
+        public_key: &str,
+        token_address: &str,
+    ) -> AppResult<TokenAmount> {
+        let wallet_holdings = self.get_wallet_holdings(public_key).await?;
+        match wallet_holdings.token_holdings.get(token_address) {
+            Some(balance) => Ok(*balance),
+            None => Ok(TokenAmount::default()),
+        }
+    }
+
+    async fn get_staked_token_balance(
+        &self,
+        public_key: &str,
+        token_address: &str,
+    ) -> AppResult<TokenAmount> {
+        let wallet_holdings = self.get_wallet_holdings(public_key).await?;
+        let staked_token_holdings = wallet_holdings
+            .staked_token_holdings
+            .map_err(staked_unavailable)?;
+        match staked_token_holdings.get(token_address) {
+            Some(balance) => Ok(*balance),
+            None => Ok(TokenAmount::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_error::AppError;
+    use crate::infrastructure::solana_rpc::SolanaRpcConfig;
+    use crate::infrastructure::upstream::{UpstreamClient, UpstreamConfig};
+    use crate::infrastructure::wallet_holdings::{
+        HttpWalletHoldingClient, HttpWalletHoldingsConfig, catalytics_client,
+    };
+    use crate::infrastructure::wallet_holdings_fallback::FallbackWalletHoldingsClient;
+    use axum::routing::{get, post};
+    use axum::{Json, Router};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    const OWNER: &str = "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5";
+    const JUP: &str = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+
+    fn token_account(mint: &str, amount: &str, decimals: u32) -> Value {
+        json!({
+            "pubkey": "account",
+            "account": {
+                "data": {
+                    "program": "spl-token",
+                    "parsed": {
+                        "type": "account",
+                        "info": {
+                            "mint": mint,
+                            "owner": OWNER,
+                            "tokenAmount": {"amount": amount, "decimals": decimals}
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Answers `getTokenAccountsByOwner` per program like an RPC node, or with a JSON-RPC
+    /// error when `fail` is set, and serves staked balances on the Catalytics path.
+    async fn mock_rpc_server(fail: bool) -> String {
+        let rpc = move |Json(request): Json<Value>| async move {
+            if fail {
+                return Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": {"code": -32005, "message": "Node is behind"}
+                }));
+            }
+            assert_eq!(request["method"], "getTokenAccountsByOwner");
+            assert_eq!(request["params"][0], OWNER);
+            let accounts = match request["params"][1]["programId"].as_str() {
+                Some(TOKEN_PROGRAM_ID) => vec![
+                    token_account(JUP, "1500000", 6),
+                    token_account(JUP, "2500001", 6),
+                    token_account("empty", "0", 6),
+                ],
+                Some(TOKEN_2022_PROGRAM_ID) => vec![token_account("t22", "123456789", 9)],
+                other => panic!("unexpected program {:?}", other),
+            };
+            Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": {"context": {"slot": 1}, "value": accounts}
+            }))
+        };
+        let app = Router::new().route("/", post(rpc)).route(
+            "/v2/solana/jup/governance/staked/{public_key}",
+            get(|| async { Json(json!({"stakedJup": 12.5})) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        url
+    }
+
+    fn upstream_config() -> UpstreamConfig {
+        UpstreamConfig {
+            connect_timeout_ms: 500,
+            read_timeout_ms: 1_000,
+            max_retries: 0,
+            retry_base_delay_ms: 1,
+            retry_max_delay_ms: 1,
+            breaker_failure_threshold: 5,
+            breaker_open_seconds: 60,
+        }
+    }
+
+    fn rpc_client(url: &str) -> SolanaRpcWalletHoldingsClient {
+        rpc_client_with_catalytics(url, url)
+    }
+
+    fn shared_catalytics_client() -> Arc<UpstreamClient> {
+        Arc::new(catalytics_client(upstream_config()).unwrap())
+    }
+
+    fn rpc_client_with_catalytics(
+        url: &str,
+        catalytics_url: &str,
+    ) -> SolanaRpcWalletHoldingsClient {
+        rpc_client_sharing(url, catalytics_url, shared_catalytics_client())
+    }
+
+    fn rpc_client_sharing(
+        url: &str,
+        catalytics_url: &str,
+        catalytics_client: Arc<UpstreamClient>,
+    ) -> SolanaRpcWalletHoldingsClient {
+        SolanaRpcWalletHoldingsClient::new(
+            SolanaRpcConfig {
+                rpc_url: format!("{}/", url),
+                catalytics_base_url: catalytics_url.to_string(),
+                jup_mint: JUP.to_string(),
+                upstream: upstream_config(),
+            },
+            catalytics_client,
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sums_balances_per_mint_over_both_token_programs() {
+        let client = rpc_client(&mock_rpc_server(false).await);
+
+        let holdings = client.get_wallet_holdings(OWNER).await.unwrap();
+
+        assert_eq!(holdings.token_holdings.len(), 2);
+        assert_eq!(holdings.token_holdings[JUP], TokenAmount::new(4_000_001, 6));
+        assert_eq!(
+            holdings.token_holdings["t22"],
+            TokenAmount::new(123_456_789, 9)
+        );
+        assert_eq!(
+            holdings.staked_token_holdings.unwrap()[JUP],
+            TokenAmount::new(125, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn held_balances_survive_a_failing_staked_lookup() {
+        let url = mock_rpc_server(false).await;
+        let client = rpc_client_with_catalytics(&url, "http://127.0.0.1:9");
+
+        assert_eq!(
+            client.get_token_balance(OWNER, JUP).await.unwrap(),
+            TokenAmount::new(4_000_001, 6)
+        );
+        let error = client
+            .get_staked_token_balance(OWNER, JUP)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            AppError::Upstream { provider, message }
+                if provider == "catalytics" && message.starts_with("Staked balances are unavailable")
+        ));
+    }
+
+    #[tokio::test]
+    async fn rpc_errors_name_the_provider() {
+        let client = rpc_client(&mock_rpc_server(true).await);
+
+        let error = client.get_token_balance(OWNER, JUP).await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            AppError::Upstream { provider, message }
+                if provider == "solana_rpc" && message.contains("Node is behind")
+        ));
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_rpc_when_jupiter_fails() {
+        let url = mock_rpc_server(false).await;
+        let jupiter = HttpWalletHoldingClient::new(
+            HttpWalletHoldingsConfig {
+                jupiter_base_url: "http://127.0.0.1:9".to_string(),
+                jupiter_api_key: "key".to_string(),
+                catalytics_base_url: url.clone(),
+                jup_mint: JUP.to_string(),
+                upstream: upstream_config(),
+            },
+            shared_catalytics_client(),
+        )
+        .unwrap();
+        let client = FallbackWalletHoldingsClient::new(vec![
+            ("jupiter".to_string(), Arc::new(jupiter)),
+            ("rpc".to_string(), Arc::new(rpc_client(&url))),
+        ]);
+
+        assert_eq!(
+            client.get_token_balance(OWNER, JUP).await.unwrap(),
+            TokenAmount::new(4_000_001, 6)
+        );
+    }
+
+    #[tokio::test]
+    async fn providers_trip_one_catalytics_circuit() {
+        let url = mock_rpc_server(false).await;
+        let shared = Arc::new(
+            catalytics_client(UpstreamConfig {
+                breaker_failure_threshold: 1,
+                ..upstream_config()
+            })
+            .unwrap(),
+        );
+        let failing = rpc_client_sharing(&url, "http://127.0.0.1:9", shared.clone());
+        let healthy = rpc_client_sharing(&url, &url, shared);
+
+        assert!(failing.get_staked_token_balance(OWNER, JUP).await.is_err());
+        let error = healthy
+            .get_staked_token_balance(OWNER, JUP)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            AppError::Upstream { provider, message }
+                if provider == "catalytics" && message.contains("circuit open")
+        ));
+    }
+}
@@ -1,9 +1,11 @@
-use crate::app_error::AppResult;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::token_amount::TokenAmount;
 use crate::entities::wallet_holdings::WalletHoldings;
 use crate::infrastructure::wallet_holdings::HttpWalletHoldingClient;
 use crate::use_cases::wallet_holdings::WalletHoldingsClient;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -37,8 +39,40 @@ pub struct JupiterHoldingsTokenResponse {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct StakedJupResponse {
+    /// Whole JUP as a JSON number or string, kept as sent since an `f64` cannot hold every
+    /// balance exactly.
     #[serde(rename = "stakedJup")]
-    pub staked_jup: f64,
+    pub staked_jup: Box<RawValue>,
+}
+
+impl StakedJupResponse {
+    /// The staked balance at the decimals it was sent with; the sync stores it at the
+    /// decimals the token is registered with.
+    pub fn amount(&self) -> AppResult<TokenAmount> {
+        let raw = self.staked_jup.get();
+        let value = match serde_json::from_str::<String>(raw) {
+            Ok(value) => value,
+            Err(_) => raw.to_string(),
+        };
+        let decimals = value
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len());
+        u8::try_from(decimals)
+            .map_err(|_| format!("Invalid token amount '{}'", value))
+            .and_then(|decimals| TokenAmount::from_ui_str(&value, decimals))
+            .map_err(|message| AppError::Upstream {
+                provider: "catalytics".to_string(),
+                message,
+            })
+    }
+}
+
+/// Error for a staked balance lookup when the holdings were read without staked balances.
+pub fn staked_unavailable(reason: String) -> AppError {
+    AppError::Upstream {
+        provider: "catalytics".to_string(),
+        message: format!("Staked balances are unavailable: {}", reason),
+    }
 }
 
 #[async_trait]
@@ -48,9 +82,7 @@ impl WalletHoldingsClient for HttpWalletHoldingClient {
         let jupiter_response = self.make_jupiter_get_request(&jupiter_endpoint).await?;
 
         let catalytics_endpoint = format!("/v2/solana/jup/governance/staked/{}", public_key);
-        let catalytics_response = self
-            .make_catalytics_get_request(&catalytics_endpoint)
-            .await?;
+        let catalytics_response = self.make_catalytics_get_request(&catalytics_endpoint).await;
         Ok(self
             .convert_to_wallet_holdings(
                 public_key.to_string(),
@@ -60,11 +92,15 @@ impl WalletHoldingsClient for HttpWalletHoldingClient {
             .await?)
     }
 
-    async fn get_token_balance(&self, public_key: &str, token_address: &str) -> AppResult<f64> {
+    async fn get_token_balance(
+        &self,
+        public_key: &str,
+        token_address: &str,
+    ) -> AppResult<TokenAmount> {
         let wallet_holdings = self.get_wallet_holdings(public_key).await?;
         match wallet_holdings.token_holdings.get(token_address) {
             Some(balance) => Ok(*balance),
-            None => Ok(0.0),
+            None => Ok(TokenAmount::default()),
         }
     }
 
@@ -72,11 +108,42 @@ impl WalletHoldingsClient for HttpWalletHoldingClient {
         &self,
         public_key: &str,
         token_address: &str,
-    ) -> AppResult<f64> {
+    ) -> AppResult<TokenAmount> {
         let wallet_holdings = self.get_wallet_holdings(public_key).await?;
-        match wallet_holdings.staked_token_holdings.get(token_address) {
+        let staked_token_holdings = wallet_holdings
+            .staked_token_holdings
+            .map_err(staked_unavailable)?;
+        match staked_token_holdings.get(token_address) {
             Some(balance) => Ok(*balance),
-            None => Ok(0.0),
+            None => Ok(TokenAmount::default()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staked(body: &str) -> AppResult<TokenAmount> {
+        serde_json::from_str::<StakedJupResponse>(body)
+            .unwrap()
+            .amount()
+    }
+
+    #[test]
+    fn staked_jup_is_read_exactly_at_the_decimals_sent() {
+        assert_eq!(
+            staked(r#"{"stakedJup": 123456789012.123456}"#).unwrap(),
+            TokenAmount::new(123_456_789_012_123_456, 6)
+        );
+        assert_eq!(
+            staked(r#"{"stakedJup": "0.10000000000000000001"}"#).unwrap(),
+            TokenAmount::new(10_000_000_000_000_000_001, 20)
+        );
+        assert_eq!(
+            staked(r#"{"stakedJup": 12}"#).unwrap(),
+            TokenAmount::new(12, 0)
+        );
+        assert!(staked(r#"{"stakedJup": 1e3}"#).is_err());
+    }
+}
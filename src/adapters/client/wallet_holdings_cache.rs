@@ -0,0 +1,47 @@
+use crate::adapters::client::wallet_holdings::staked_unavailable;
+use crate::app_error::AppResult;
+use crate::entities::token_amount::TokenAmount;
+use crate::entities::wallet_holdings::{WalletHoldings, WalletHoldingsCacheStats};
+use crate::infrastructure::wallet_holdings_cache::CachedWalletHoldingsClient;
+use crate::use_cases::wallet_holdings::WalletHoldingsClient;
+use async_trait::async_trait;
+
+#[async_trait]
+impl WalletHoldingsClient for CachedWalletHoldingsClient {
+    async fn get_wallet_holdings(&self, public_key: &str) -> AppResult<WalletHoldings> {
+        let holdings = self.holdings(public_key).await?;
+        Ok(holdings.as_ref().clone())
+    }
+
+    async fn get_token_balance(
+        &self,
+        public_key: &str,
+        token_address: &str,
+    ) -> AppResult<TokenAmount> {
+        let holdings = self.holdings(public_key).await?;
+        match holdings.token_holdings.get(token_address) {
+            Some(balance) => Ok(*balance),
+            None => Ok(TokenAmount::default()),
+        }
+    }
+
+    async fn get_staked_token_balance(
+        &self,
+        public_key: &str,
+        token_address: &str,
+    ) -> AppResult<TokenAmount> {
+        let holdings = self.holdings(public_key).await?;
+        let staked_token_holdings = holdings
+            .staked_token_holdings
+            .as_ref()
+            .map_err(|reason| staked_unavailable(reason.clone()))?;
+        match staked_token_holdings.get(token_address) {
+            Some(balance) => Ok(*balance),
+            None => Ok(TokenAmount::default()),
+        }
+    }
+
+    fn cache_stats(&self) -> Option<WalletHoldingsCacheStats> {
+        Some(self.stats())
+    }
+}
@@ -0,0 +1,41 @@
+use crate::adapters::client::wallet_holdings::staked_unavailable;
+use crate::app_error::AppResult;
+use crate::entities::token_amount::TokenAmount;
+use crate::entities::wallet_holdings::WalletHoldings;
+use crate::infrastructure::wallet_holdings_fallback::FallbackWalletHoldingsClient;
+use crate::use_cases::wallet_holdings::WalletHoldingsClient;
+use async_trait::async_trait;
+
+#[async_trait]
+impl WalletHoldingsClient for FallbackWalletHoldingsClient {
+    async fn get_wallet_holdings(&self, public_key: &str) -> AppResult<WalletHoldings> {
+        self.holdings(public_key).await
+    }
+
+    async fn get_token_balance(
+        &self,
+        public_key: &str,
+        token_address: &str,
+    ) -> AppResult<TokenAmount> {
+        let wallet_holdings = self.holdings(public_key).await?;
+        match wallet_holdings.token_holdings.get(token_address) {
+            Some(balance) => Ok(*balance),
+            None => Ok(TokenAmount::default()),
+        }
+    }
+
+    async fn get_staked_token_balance(
+        &self,
+        public_key: &str,
+        token_address: &str,
+    ) -> AppResult<TokenAmount> {
+        let wallet_holdings = self.holdings(public_key).await?;
+        let staked_token_holdings = wallet_holdings
+            .staked_token_holdings
+            .map_err(staked_unavailable)?;
+        match staked_token_holdings.get(token_address) {
+            Some(balance) => Ok(*balance),
+            None => Ok(TokenAmount::default()),
+        }
+    }
+}
@@ -1,36 +1,67 @@
 use crate::app_error::AppError;
 use axum::Json;
-use axum::http::StatusCode;
+use axum::http::{StatusCode, header};
 use axum::response::{IntoResponse, Response};
 use serde::Serialize;
 
 #[derive(Serialize)]
-struct ErrorResponse {
-    status: u16,
-    message: String,
+pub(crate) struct ErrorResponse {
+    pub(crate) status: u16,
+    pub(crate) message: String,
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         tracing::error!("Error: {}", self);
 
-        let (status, message) = match self {
-            AppError::Database(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Database exception".to_string(),
-            ),
-            AppError::Internal(_) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            ),
-            AppError::NotFound(_) => (StatusCode::NOT_FOUND, "Resource not found".to_string()),
+        let retry_after = match self {
+            AppError::RateLimited(seconds) => Some(seconds.max(1)),
+            _ => None,
+        };
+
+        let status = match self {
+            AppError::Database(_) | AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::Upstream { .. } => StatusCode::BAD_GATEWAY,
         };
 
         let error_response = ErrorResponse {
             status: status.as_u16(),
-            message,
+            message: self.public_message(),
         };
 
-        (status, Json(error_response)).into_response()
+        match retry_after {
+            Some(seconds) => (
+                status,
+                [(header::RETRY_AFTER, seconds.to_string())],
+                Json(error_response),
+            )
+                .into_response(),
+            None => (status, Json(error_response)).into_response(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limited_responses_say_when_to_retry() {
+        for (seconds, retry_after) in [(42, "42"), (0, "1")] {
+            let response = AppError::RateLimited(seconds).into_response();
+
+            assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+            assert_eq!(response.headers()[header::RETRY_AFTER], retry_after);
+        }
+        assert!(
+            !AppError::NotFound("badge".to_string())
+                .into_response()
+                .headers()
+                .contains_key(header::RETRY_AFTER)
+        );
     }
 }
@@ -1,9 +1,18 @@
+use crate::infrastructure::auth::AuthConfig;
+use crate::infrastructure::leaderboard::LeaderboardConfig;
+use crate::infrastructure::sync::SyncConfig;
+use crate::use_cases::admin::AdminUseCases;
+use crate::use_cases::auth::AuthUseCases;
 use crate::use_cases::badge::BadgeUseCases;
 use crate::use_cases::badge_group::BadgeGroupUseCases;
 use crate::use_cases::beta_applicant::BetaApplicantUseCases;
 use crate::use_cases::beta_applicant_progression::BetaApplicantProgressionUseCases;
 use crate::use_cases::cat::CatUseCases;
+use crate::use_cases::event::EventBus;
+use crate::use_cases::job::JobUseCases;
 use crate::use_cases::leaderboard::LeaderboardUseCases;
+use crate::use_cases::tracked_token::TrackedTokenUseCases;
+use crate::use_cases::wallet_holdings::WalletHoldingsUseCases;
 use axum::extract::FromRef;
 use std::sync::Arc;
 
@@ -15,6 +24,15 @@ pub struct AppState {
     pub beta_applicant_progression_use_cases: Arc<BetaApplicantProgressionUseCases>,
     pub cat_use_cases: Arc<CatUseCases>,
     pub leaderboard_use_cases: Arc<LeaderboardUseCases>,
+    pub auth_use_cases: Arc<AuthUseCases>,
+    pub auth_config: Arc<AuthConfig>,
+    pub leaderboard_config: Arc<LeaderboardConfig>,
+    pub sync_config: Arc<SyncConfig>,
+    pub admin_use_cases: Arc<AdminUseCases>,
+    pub job_use_cases: Arc<JobUseCases>,
+    pub tracked_token_use_cases: Arc<TrackedTokenUseCases>,
+    pub wallet_holdings_use_cases: Arc<WalletHoldingsUseCases>,
+    pub event_bus: Arc<EventBus>,
 }
 
 impl FromRef<AppState> for Arc<BetaApplicantUseCases> {
@@ -52,3 +70,57 @@ impl FromRef<AppState> for Arc<LeaderboardUseCases> {
         app_state.leaderboard_use_cases.clone()
     }
 }
+
+impl FromRef<AppState> for Arc<AuthUseCases> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.auth_use_cases.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AuthConfig> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.auth_config.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<AdminUseCases> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.admin_use_cases.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<JobUseCases> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.job_use_cases.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<TrackedTokenUseCases> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.tracked_token_use_cases.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<LeaderboardConfig> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.leaderboard_config.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<SyncConfig> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.sync_config.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<EventBus> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.event_bus.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<WalletHoldingsUseCases> {
+    fn from_ref(app_state: &AppState) -> Self {
+        app_state.wallet_holdings_use_cases.clone()
+    }
+}
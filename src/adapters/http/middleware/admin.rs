@@ -0,0 +1,53 @@
+use crate::adapters::http::middleware::auth::{AuthError, AuthenticatedUser};
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::use_cases::admin::AdminUseCases;
+use axum::extract::{FromRef, FromRequestParts};
+use axum::http::request::Parts;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// An [`AuthenticatedUser`] whose public key is on the admin allowlist.
+#[derive(Debug, Clone)]
+pub struct AdminUser {
+    pub public_key: String,
+}
+
+impl AdminUser {
+    /// Audit log entry of an action taken by this admin, to be written together with it.
+    pub fn audit_entry(
+        &self,
+        action: &str,
+        entity_type: &str,
+        entity_id: Option<String>,
+        payload: Value,
+    ) -> NewAdminAuditEntry {
+        NewAdminAuditEntry {
+            admin_public_key: self.public_key.clone(),
+            action: action.to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            payload,
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+    Arc<AdminUseCases>: FromRef<S>,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthenticatedUser::from_request_parts(parts, state).await?;
+
+        let admin_use_cases = Arc::<AdminUseCases>::from_ref(state);
+        if !admin_use_cases.is_admin(&user.public_key).await? {
+            return Err(AuthError::Forbidden);
+        }
+
+        Ok(AdminUser {
+            public_key: user.public_key,
+        })
+    }
+}
@@ -1,15 +1,126 @@
-use anyhow::{Context, Result};
+use crate::adapters::http::app_error_impl::ErrorResponse;
+use crate::adapters::http::middleware::siws::{SiwsError, SiwsMessage, SiwsValidation};
+use crate::app_error::AppError;
+use crate::infrastructure::auth::AuthConfig;
+use crate::use_cases::auth::AuthUseCases;
 use axum::{
-    extract::{FromRequestParts, Request},
+    Json,
+    extract::{FromRequestParts, Request, State},
     http::{HeaderMap, StatusCode, request::Parts},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
 use base64::{Engine as _, engine::general_purpose};
 use bs58;
-use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH, Signature, Verifier, VerifyingKey};
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::warn;
 use urlencoding;
 
+#[derive(Error, Debug)]
+pub enum AuthError {
+    #[error("Missing {0} header")]
+    MissingHeader(&'static str),
+
+    #[error("Authorization header must use the 'Solana' or 'Bearer' scheme")]
+    BadScheme,
+
+    #[error("Authorization data must be in format 'public_key:signature'")]
+    MalformedHeader,
+
+    #[error("Signed message is not valid URL encoding")]
+    BadMessageEncoding,
+
+    #[error("Public key is not valid base58")]
+    BadBase58,
+
+    #[error("Signature is not valid base64")]
+    BadBase64,
+
+    #[error("Public key must be {PUBLIC_KEY_LENGTH} bytes, got {0}")]
+    WrongKeyLength(usize),
+
+    #[error("Signature must be {SIGNATURE_LENGTH} bytes, got {0}")]
+    WrongSignatureLength(usize),
+
+    #[error("Public key is not a valid ed25519 point")]
+    InvalidPublicKey,
+
+    #[error("Signature verification failed")]
+    VerificationFailed,
+
+    #[error("Signed message has passed its expiration time")]
+    ExpiredMessage,
+
+    #[error("Signed message is older than the server accepts")]
+    MessageTooOld,
+
+    #[error("{0}")]
+    InvalidMessage(SiwsError),
+
+    #[error("Nonce is invalid, expired or already used")]
+    InvalidNonce,
+
+    #[error("Access token is invalid, expired or revoked")]
+    InvalidToken,
+
+    #[error("Authentication required")]
+    Unauthenticated,
+
+    #[error("Admin privileges required")]
+    Forbidden,
+
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+impl From<SiwsError> for AuthError {
+    fn from(value: SiwsError) -> Self {
+        match value {
+            SiwsError::Expired => AuthError::ExpiredMessage,
+            SiwsError::MessageTooOld => AuthError::MessageTooOld,
+            other => AuthError::InvalidMessage(other),
+        }
+    }
+}
+
+impl From<AppError> for AuthError {
+    fn from(value: AppError) -> Self {
+        AuthError::Internal(value.to_string())
+    }
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            AuthError::MissingHeader(_)
+            | AuthError::BadScheme
+            | AuthError::MalformedHeader
+            | AuthError::BadMessageEncoding => StatusCode::BAD_REQUEST,
+            AuthError::Forbidden => StatusCode::FORBIDDEN,
+            AuthError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            _ => StatusCode::UNAUTHORIZED,
+        };
+
+        let message = match &self {
+            AuthError::Internal(_) => {
+                tracing::error!("Error: {}", self);
+                "Internal server error".to_string()
+            }
+            _ => self.to_string(),
+        };
+
+        let error_response = ErrorResponse {
+            status: status.as_u16(),
+            message,
+        };
+
+        (status, Json(error_response)).into_response()
+    }
+}
+
 #[derive(Debug, Clone)]
 struct SolanaAuth {
     pub public_key: String,
@@ -25,57 +136,181 @@ struct AuthData {
 #[derive(Debug, Clone)]
 pub struct AuthenticatedUser {
     pub public_key: String,
+    /// When the credentials of the request stop being accepted. Responses that outlive the
+    /// request, such as event streams, end there.
+    pub expires_at: DateTime<Utc>,
 }
 
 impl<S> FromRequestParts<S> for AuthenticatedUser
 where
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
+    type Rejection = AuthError;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
         parts
             .extensions
             .get::<AuthenticatedUser>()
             .cloned()
-            .ok_or(StatusCode::UNAUTHORIZED)
+            .ok_or(AuthError::Unauthenticated)
     }
 }
 
-pub async fn auth_middleware(mut request: Request, next: Next) -> Result<Response, StatusCode> {
-    let headers = request.headers();
+pub async fn auth_middleware(
+    State(auth_config): State<Arc<AuthConfig>>,
+    State(auth_use_cases): State<Arc<AuthUseCases>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AuthError> {
+    let user = match extract_bearer_token(request.headers()) {
+        Some(access_token) => authenticate_access_token(&access_token, &auth_use_cases).await?,
+        None => {
+            authenticate_wallet_signature(request.headers(), &auth_config, &auth_use_cases).await?
+        }
+    };
 
+    request.extensions_mut().insert(user);
+
+    Ok(next.run(request).await)
+}
+
+/// Verifies the `Authorization: Solana <public_key>:<signature>` and `X-Solana-Message`
+/// headers and returns the signer, authenticated for as long as the message is valid. The
+/// message must be a valid Sign-In-With-Solana message, unless `AUTH_ALLOW_LEGACY_MESSAGES`
+/// still accepts signed free text; its nonce is consumed when `AUTH_REQUIRE_NONCE` is set.
+pub async fn authenticate_wallet_signature(
+    headers: &HeaderMap,
+    auth_config: &AuthConfig,
+    auth_use_cases: &AuthUseCases,
+) -> Result<AuthenticatedUser, AuthError> {
+    authenticate_signed_message(
+        headers,
+        auth_config,
+        auth_use_cases,
+        auth_config.require_nonce,
+    )
+    .await
+}
+
+/// Like `authenticate_wallet_signature`, but always consumes the nonce, for sign-ins that
+/// mint long-lived credentials and must not be replayable.
+pub async fn authenticate_wallet_sign_in(
+    headers: &HeaderMap,
+    auth_config: &AuthConfig,
+    auth_use_cases: &AuthUseCases,
+) -> Result<String, AuthError> {
+    authenticate_signed_message(headers, auth_config, auth_use_cases, true)
+        .await
+        .map(|user| user.public_key)
+}
+
+async fn authenticate_signed_message(
+    headers: &HeaderMap,
+    auth_config: &AuthConfig,
+    auth_use_cases: &AuthUseCases,
+    require_nonce: bool,
+) -> Result<AuthenticatedUser, AuthError> {
     let auth_header = extract_header(headers, "Authorization")?;
     let message = extract_header(headers, "X-Solana-Message")?;
 
-    let auth_data =
-        parse_auth_headers(&auth_header, &message).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let auth_data = parse_auth_headers(&auth_header, &message)?;
+    let now = Utc::now();
+    let siws_message = match verify_signed_message(&auth_data, auth_config, now) {
+        // The signature is valid, the message just predates Sign-In-With-Solana
+        Err(AuthError::InvalidMessage(SiwsError::MalformedHeader))
+            if auth_config.allow_legacy_messages && !require_nonce =>
+        {
+            warn!(
+                "Accepted a signed message that is not a Sign-In-With-Solana message from {}",
+                auth_data.solana.public_key
+            );
+            return Ok(AuthenticatedUser {
+                public_key: auth_data.solana.public_key,
+                expires_at: now + Duration::seconds(auth_config.max_message_age_seconds),
+            });
+        }
+        result => result?,
+    };
 
-    is_authorized(&auth_data).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    if require_nonce {
+        let nonce = siws_message
+            .nonce
+            .as_deref()
+            .ok_or(SiwsError::MissingField("Nonce"))?;
 
-    request.extensions_mut().insert(AuthenticatedUser {
-        public_key: auth_data.solana.public_key.clone(),
-    });
+        // Consumed only after the signature checks out, so forged requests cannot burn nonces
+        let consumed = auth_use_cases
+            .consume_nonce(&auth_data.solana.public_key, nonce)
+            .await?;
+        if !consumed {
+            return Err(AuthError::InvalidNonce);
+        }
+    }
 
-    Ok(next.run(request).await)
+    let expires_at = siws_message
+        .valid_until(Duration::seconds(auth_config.max_message_age_seconds))
+        .ok_or(SiwsError::MissingField("Issued At"))?;
+    Ok(AuthenticatedUser {
+        public_key: auth_data.solana.public_key,
+        expires_at,
+    })
+}
+
+/// Checks the signature and the Sign-In-With-Solana fields of the signed message.
+fn verify_signed_message(
+    auth_data: &AuthData,
+    auth_config: &AuthConfig,
+    now: DateTime<Utc>,
+) -> Result<SiwsMessage, AuthError> {
+    is_authorized(auth_data)?;
+
+    let siws_message = SiwsMessage::parse(&auth_data.decoded_message)?;
+    siws_message.validate(&SiwsValidation {
+        public_key: &auth_data.solana.public_key,
+        allowed_domains: &auth_config.allowed_domains,
+        max_age: Duration::seconds(auth_config.max_message_age_seconds),
+        now,
+    })?;
+
+    Ok(siws_message)
+}
+
+async fn authenticate_access_token(
+    access_token: &str,
+    auth_use_cases: &AuthUseCases,
+) -> Result<AuthenticatedUser, AuthError> {
+    let session = auth_use_cases
+        .authenticate_access_token(access_token)
+        .await?
+        .ok_or(AuthError::InvalidToken)?;
+    Ok(AuthenticatedUser {
+        public_key: session.public_key,
+        expires_at: session.access_expires_at,
+    })
+}
+
+pub fn extract_bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.to_string())
 }
 
-fn extract_header(headers: &HeaderMap, header_name: &str) -> Result<String, StatusCode> {
+fn extract_header(headers: &HeaderMap, header_name: &'static str) -> Result<String, AuthError> {
     headers
         .get(header_name)
         .and_then(|value| value.to_str().ok())
         .map(|s| s.to_string())
-        .ok_or(StatusCode::BAD_REQUEST)
+        .ok_or(AuthError::MissingHeader(header_name))
 }
 
-fn parse_auth_headers(auth_header: &str, message: &str) -> Result<AuthData> {
+fn parse_auth_headers(auth_header: &str, message: &str) -> Result<AuthData, AuthError> {
     let solana = parse_solana_auth(auth_header)?;
 
-    let mut decoded_message = urlencoding::decode(message)
-        .context("Failed to URL decode message")?
-        .to_string();
-
-    decoded_message = decoded_message.replace("\\n", "\n");
+    let decoded_message = urlencoding::decode(message)
+        .map_err(|_| AuthError::BadMessageEncoding)?
+        .replace("\\n", "\n");
 
     Ok(AuthData {
         solana,
@@ -83,24 +318,25 @@ fn parse_auth_headers(auth_header: &str, message: &str) -> Result<AuthData> {
     })
 }
 
-fn parse_solana_auth(auth_header: &str) -> Result<SolanaAuth> {
+fn parse_solana_auth(auth_header: &str) -> Result<SolanaAuth, AuthError> {
     let auth_data = auth_header
         .strip_prefix("Solana ")
-        .context("Authorization header must start with 'Solana '")?;
+        .ok_or(AuthError::BadScheme)?;
 
-    let parts: Vec<&str> = auth_data.split(':').collect();
-    anyhow::ensure!(
-        parts.len() == 2,
-        "Authorization data must be in format 'public_key:signature'"
-    );
+    let (public_key, signature) = auth_data
+        .split_once(':')
+        .filter(|(public_key, signature)| {
+            !public_key.is_empty() && !signature.is_empty() && !signature.contains(':')
+        })
+        .ok_or(AuthError::MalformedHeader)?;
 
     Ok(SolanaAuth {
-        public_key: parts[0].to_string(),
-        signature: parts[1].to_string(),
+        public_key: public_key.to_string(),
+        signature: signature.to_string(),
     })
 }
 
-fn is_authorized(auth_data: &AuthData) -> Result<()> {
+fn is_authorized(auth_data: &AuthData) -> Result<(), AuthError> {
     verify_solana_signature(
         &auth_data.solana.public_key,
         &auth_data.solana.signature,
@@ -112,23 +348,363 @@ fn verify_solana_signature(
     public_key_b58: &str,
     signature_base64: &str,
     message: &str,
-) -> Result<()> {
+) -> Result<(), AuthError> {
     let public_key_bytes = bs58::decode(public_key_b58)
         .into_vec()
-        .context("Failed to decode public key from base58")?;
+        .map_err(|_| AuthError::BadBase58)?;
+    let public_key_bytes: [u8; PUBLIC_KEY_LENGTH] = public_key_bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| AuthError::WrongKeyLength(bytes.len()))?;
 
     let signature_bytes = general_purpose::STANDARD
         .decode(signature_base64)
-        .context("Failed to decode signature from base64")?;
+        .map_err(|_| AuthError::BadBase64)?;
+    let signature_bytes: [u8; SIGNATURE_LENGTH] = signature_bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| AuthError::WrongSignatureLength(bytes.len()))?;
 
-    let verifying_key = VerifyingKey::from_bytes(&public_key_bytes.try_into().unwrap())
-        .context("Invalid public key format")?;
+    let verifying_key =
+        VerifyingKey::from_bytes(&public_key_bytes).map_err(|_| AuthError::InvalidPublicKey)?;
 
-    let signature = Signature::from_bytes(&signature_bytes.try_into().unwrap());
+    let signature = Signature::from_bytes(&signature_bytes);
 
     verifying_key
         .verify(message.as_bytes(), &signature)
-        .context("Signature verification failed")?;
+        .map_err(|_| AuthError::VerificationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::persistence::in_memory::InMemoryPersistence;
+    use crate::app_error::AppResult;
+    use crate::entities::auth_session::{AuthSession, AuthSessionPurge};
+    use crate::use_cases::auth::{AuthLifetimes, AuthSessionPersistence};
+    use ed25519_dalek::{Signer, SigningKey};
+    use proptest::prelude::*;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    fn signer_public_key() -> String {
+        bs58::encode(signing_key().verifying_key().as_bytes()).into_string()
+    }
 
-    Ok(())
+    fn signed_header(message: &str) -> String {
+        let signature =
+            general_purpose::STANDARD.encode(signing_key().sign(message.as_bytes()).to_bytes());
+        format!("Solana {}:{}", signer_public_key(), signature)
+    }
+
+    fn auth_config() -> AuthConfig {
+        AuthConfig {
+            require_nonce: false,
+            allow_legacy_messages: false,
+            allowed_domains: vec!["app.catalytics.pro".to_string()],
+            nonce_ttl_seconds: 300,
+            max_message_age_seconds: 300,
+            nonce_store: crate::infrastructure::auth::NonceStore::Memory,
+            access_token_ttl_seconds: 900,
+            refresh_token_ttl_seconds: 3600,
+            admin_public_keys: Vec::new(),
+            internal_api_token: None,
+        }
+    }
+
+    fn siws_message(address: &str, issued_at: DateTime<Utc>) -> String {
+        format!(
+            "app.catalytics.pro wants you to sign in with your Solana account:\n{}\n\nURI: https://app.catalytics.pro\nIssued At: {}",
+            address,
+            issued_at.to_rfc3339()
+        )
+    }
+
+    fn verify_message(message: &str) -> Result<SiwsMessage, AuthError> {
+        let auth_data = parse_auth_headers(&signed_header(message), message)?;
+        verify_signed_message(&auth_data, &auth_config(), Utc::now())
+    }
+
+    fn verify_headers(auth_header: &str, message: &str) -> Result<(), AuthError> {
+        let auth_data = parse_auth_headers(auth_header, message)?;
+        is_authorized(&auth_data)
+    }
+
+    #[test]
+    fn accepts_valid_signature() {
+        let message = "Sign in to Catalytics";
+        assert!(verify_headers(&signed_header(message), message).is_ok());
+    }
+
+    #[test]
+    fn rejects_signature_for_other_message() {
+        let result = verify_headers(&signed_header("Sign in to Catalytics"), "Something else");
+        assert!(matches!(result, Err(AuthError::VerificationFailed)));
+    }
+
+    #[test]
+    fn rejects_unknown_scheme() {
+        let result = verify_headers("Basic dXNlcjpwYXNz", "message");
+        assert!(matches!(result, Err(AuthError::BadScheme)));
+    }
+
+    #[test]
+    fn rejects_missing_separator() {
+        let result = verify_headers("Solana abcdef", "message");
+        assert!(matches!(result, Err(AuthError::MalformedHeader)));
+    }
+
+    #[test]
+    fn rejects_invalid_base58() {
+        let result = verify_headers("Solana 0OIl:AAAA", "message");
+        assert!(matches!(result, Err(AuthError::BadBase58)));
+    }
+
+    #[test]
+    fn rejects_short_public_key_without_panicking() {
+        let public_key = bs58::encode([1u8; 31]).into_string();
+        let result = verify_headers(&format!("Solana {}:AAAA", public_key), "message");
+        assert!(matches!(result, Err(AuthError::WrongKeyLength(31))));
+    }
+
+    #[test]
+    fn rejects_short_signature_without_panicking() {
+        let public_key = bs58::encode([1u8; 32]).into_string();
+        let signature = general_purpose::STANDARD.encode([1u8; 63]);
+        let result = verify_headers(&format!("Solana {}:{}", public_key, signature), "message");
+        assert!(matches!(result, Err(AuthError::WrongSignatureLength(63))));
+    }
+
+    #[test]
+    fn reports_missing_header() {
+        let result = extract_header(&HeaderMap::new(), "X-Solana-Message");
+        assert!(matches!(
+            result,
+            Err(AuthError::MissingHeader("X-Solana-Message"))
+        ));
+    }
+
+    #[test]
+    fn accepts_signed_siws_message_without_nonce() {
+        let message = siws_message(&signer_public_key(), Utc::now());
+
+        let parsed = verify_message(&message).unwrap();
+
+        assert_eq!(parsed.address, signer_public_key());
+        assert_eq!(parsed.nonce, None);
+    }
+
+    #[test]
+    fn rejects_signed_text_that_is_not_a_siws_message() {
+        let result = verify_message("Sign in to Catalytics");
+        assert!(matches!(
+            result,
+            Err(AuthError::InvalidMessage(SiwsError::MalformedHeader))
+        ));
+    }
+
+    #[test]
+    fn rejects_siws_message_for_another_address() {
+        let other = bs58::encode([1u8; 32]).into_string();
+        let result = verify_message(&siws_message(&other, Utc::now()));
+        assert!(matches!(
+            result,
+            Err(AuthError::InvalidMessage(SiwsError::AddressMismatch))
+        ));
+    }
+
+    #[test]
+    fn rejects_stale_siws_message() {
+        let message = siws_message(&signer_public_key(), Utc::now() - Duration::hours(1));
+        assert!(matches!(
+            verify_message(&message),
+            Err(AuthError::MessageTooOld)
+        ));
+    }
+
+    #[derive(Debug)]
+    struct NoSessions;
+
+    #[async_trait::async_trait]
+    impl AuthSessionPersistence for NoSessions {
+        async fn create_session(
+            &self,
+            _: &str,
+            _: &str,
+            _: &str,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> AppResult<AuthSession> {
+            unreachable!()
+        }
+
+        async fn read_active_session_by_access_token(
+            &self,
+            _: &str,
+        ) -> AppResult<Option<AuthSession>> {
+            unreachable!()
+        }
+
+        async fn rotate_session(
+            &self,
+            _: &str,
+            _: &str,
+            _: &str,
+            _: DateTime<Utc>,
+            _: DateTime<Utc>,
+        ) -> AppResult<Option<AuthSession>> {
+            unreachable!()
+        }
+
+        async fn revoke_session_by_rotated_refresh_token(&self, _: &str) -> AppResult<bool> {
+            unreachable!()
+        }
+
+        async fn purge_sessions(&self, _: DateTime<Utc>) -> AppResult<AuthSessionPurge> {
+            unreachable!()
+        }
+
+        async fn revoke_session_by_access_token(&self, _: &str) -> AppResult<bool> {
+            unreachable!()
+        }
+    }
+
+    fn signed_headers(message: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("Authorization", signed_header(message).parse().unwrap());
+        headers.insert(
+            "X-Solana-Message",
+            urlencoding::encode(message).parse().unwrap(),
+        );
+        headers
+    }
+
+    #[tokio::test]
+    async fn sign_in_consumes_a_nonce_even_when_requests_need_none() {
+        let auth_use_cases = AuthUseCases::new(
+            Arc::new(InMemoryPersistence::new()),
+            Arc::new(NoSessions),
+            AuthLifetimes {
+                nonce: Duration::minutes(5),
+                access_token: Duration::minutes(15),
+                refresh_token: Duration::days(30),
+            },
+        );
+        let config = auth_config();
+        let without_nonce = signed_headers(&siws_message(&signer_public_key(), Utc::now()));
+
+        assert!(
+            authenticate_wallet_signature(&without_nonce, &config, &auth_use_cases)
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            authenticate_wallet_sign_in(&without_nonce, &config, &auth_use_cases).await,
+            Err(AuthError::InvalidMessage(SiwsError::MissingField("Nonce")))
+        ));
+
+        let nonce = auth_use_cases
+            .issue_nonce(&signer_public_key())
+            .await
+            .unwrap();
+        let with_nonce = signed_headers(&format!(
+            "{}\nNonce: {}",
+            siws_message(&signer_public_key(), Utc::now()),
+            nonce.nonce
+        ));
+
+        assert_eq!(
+            authenticate_wallet_sign_in(&with_nonce, &config, &auth_use_cases)
+                .await
+                .unwrap(),
+            signer_public_key()
+        );
+        assert!(matches!(
+            authenticate_wallet_sign_in(&with_nonce, &config, &auth_use_cases).await,
+            Err(AuthError::InvalidNonce)
+        ));
+    }
+
+    #[test]
+    fn maps_expired_siws_message() {
+        assert!(matches!(
+            AuthError::from(SiwsError::Expired),
+            AuthError::ExpiredMessage
+        ));
+        assert!(matches!(
+            AuthError::from(SiwsError::MessageTooOld),
+            AuthError::MessageTooOld
+        ));
+        assert_ne!(
+            AuthError::ExpiredMessage.to_string(),
+            AuthError::MessageTooOld.to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn legacy_messages_are_accepted_only_while_allowed_and_never_for_sign_in() {
+        let auth_use_cases = AuthUseCases::new(
+            Arc::new(InMemoryPersistence::new()),
+            Arc::new(NoSessions),
+            AuthLifetimes {
+                nonce: Duration::minutes(5),
+                access_token: Duration::minutes(15),
+                refresh_token: Duration::days(30),
+            },
+        );
+        let legacy = signed_headers("Sign in to Catalytics");
+        let mut config = auth_config();
+
+        assert!(matches!(
+            authenticate_wallet_signature(&legacy, &config, &auth_use_cases).await,
+            Err(AuthError::InvalidMessage(SiwsError::MalformedHeader))
+        ));
+
+        config.allow_legacy_messages = true;
+        let user = authenticate_wallet_signature(&legacy, &config, &auth_use_cases)
+            .await
+            .unwrap();
+        assert_eq!(user.public_key, signer_public_key());
+        assert!(matches!(
+            authenticate_wallet_sign_in(&legacy, &config, &auth_use_cases).await,
+            Err(AuthError::InvalidMessage(SiwsError::MalformedHeader))
+        ));
+
+        let stale = signed_headers(&siws_message(
+            &signer_public_key(),
+            Utc::now() - Duration::hours(1),
+        ));
+        assert!(matches!(
+            authenticate_wallet_signature(&stale, &config, &auth_use_cases).await,
+            Err(AuthError::MessageTooOld)
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn never_panics_on_arbitrary_headers(auth_header in any::<String>(), message in any::<String>()) {
+            let _ = verify_headers(&auth_header, &message);
+        }
+
+        #[test]
+        fn never_panics_on_arbitrary_solana_credentials(
+            public_key in "[1-9A-HJ-NP-Za-km-z]{0,64}",
+            signature in "[A-Za-z0-9+/=]{0,128}",
+            message in any::<String>(),
+        ) {
+            let _ = verify_headers(&format!("Solana {}:{}", public_key, signature), &message);
+        }
+
+        #[test]
+        fn reports_key_length_for_any_wrong_sized_key(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+            prop_assume!(bytes.len() != PUBLIC_KEY_LENGTH);
+            let public_key = bs58::encode(&bytes).into_string();
+            let result = verify_headers(&format!("Solana {}:AAAA", public_key), "message");
+            prop_assert!(
+                matches!(result, Err(AuthError::WrongKeyLength(_)) | Err(AuthError::MalformedHeader)),
+                "unexpected result: {:?}",
+                result
+            );
+        }
+    }
 }
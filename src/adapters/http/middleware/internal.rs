@@ -0,0 +1,36 @@
+use crate::adapters::http::middleware::auth::{AuthError, extract_bearer_token};
+use crate::infrastructure::auth::AuthConfig;
+use axum::extract::{FromRef, FromRequestParts};
+use axum::http::request::Parts;
+use std::sync::Arc;
+
+/// A service that presented `Authorization: Bearer <INTERNAL_API_TOKEN>`, such as a batch job.
+#[derive(Debug, Clone)]
+pub struct InternalCaller;
+
+impl<S> FromRequestParts<S> for InternalCaller
+where
+    S: Send + Sync,
+    Arc<AuthConfig>: FromRef<S>,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let auth_config = Arc::<AuthConfig>::from_ref(state);
+        let expected = auth_config
+            .internal_api_token
+            .as_deref()
+            .ok_or(AuthError::Forbidden)?;
+        let token = extract_bearer_token(&parts.headers)
+            .ok_or(AuthError::MissingHeader("Authorization"))?;
+
+        if !constant_time_eq(token.as_bytes(), expected.as_bytes()) {
+            return Err(AuthError::InvalidToken);
+        }
+        Ok(InternalCaller)
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
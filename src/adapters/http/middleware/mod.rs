@@ -1,3 +1,6 @@
+pub mod admin;
 pub mod auth;
+pub mod internal;
+pub mod siws;
 
 pub use auth::auth_middleware;
@@ -0,0 +1,359 @@
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Solana account:";
+
+/// Tolerated clock drift between the wallet and the server when checking timestamps.
+const CLOCK_SKEW_SECONDS: i64 = 30;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SiwsError {
+    #[error("Message is not a Sign-In-With-Solana message")]
+    MalformedHeader,
+
+    #[error("Message does not contain an address")]
+    MissingAddress,
+
+    #[error("Message field '{0}' is malformed")]
+    MalformedField(&'static str),
+
+    #[error("Message field '{0}' is required")]
+    MissingField(&'static str),
+
+    #[error("Message address does not match the signing public key")]
+    AddressMismatch,
+
+    #[error("Message domain is not allowed")]
+    DomainNotAllowed,
+
+    #[error("Message was issued in the future")]
+    IssuedInFuture,
+
+    #[error("Message is too old")]
+    MessageTooOld,
+
+    #[error("Message has expired")]
+    Expired,
+
+    #[error("Message is not valid yet")]
+    NotYetValid,
+}
+
+/// Structured Sign-In-With-Solana message, following the layout wallets produce for
+/// `signIn` requests:
+///
+/// ```text
+/// {domain} wants you to sign in with your Solana account:
+/// {address}
+///
+/// {statement}
+///
+/// URI: {uri}
+/// Version: {version}
+/// Chain ID: {chain_id}
+/// Nonce: {nonce}
+/// Issued At: {issued_at}
+/// Expiration Time: {expiration_time}
+/// Not Before: {not_before}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiwsMessage {
+    pub domain: String,
+    pub address: String,
+    pub statement: Option<String>,
+    pub uri: Option<String>,
+    pub version: Option<String>,
+    pub chain_id: Option<String>,
+    pub nonce: Option<String>,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub not_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SiwsValidation<'a> {
+    pub public_key: &'a str,
+    pub allowed_domains: &'a [String],
+    pub max_age: Duration,
+    pub now: DateTime<Utc>,
+}
+
+impl SiwsMessage {
+    pub fn parse(message: &str) -> Result<Self, SiwsError> {
+        let mut lines = message.lines();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(HEADER_SUFFIX))
+            .filter(|domain| !domain.is_empty())
+            .ok_or(SiwsError::MalformedHeader)?;
+
+        let address = lines
+            .next()
+            .map(str::trim)
+            .filter(|address| !address.is_empty())
+            .ok_or(SiwsError::MissingAddress)?;
+
+        let mut parsed = Self {
+            domain: domain.to_string(),
+            address: address.to_string(),
+            statement: None,
+            uri: None,
+            version: None,
+            chain_id: None,
+            nonce: None,
+            issued_at: None,
+            expiration_time: None,
+            not_before: None,
+        };
+
+        let mut statement_lines = Vec::new();
+        let mut in_fields = false;
+
+        for line in lines {
+            if let Some((key, value)) = line.split_once(": ") {
+                let value = value.to_string();
+                match key {
+                    "URI" => parsed.uri = Some(value),
+                    "Version" => parsed.version = Some(value),
+                    "Chain ID" => parsed.chain_id = Some(value),
+                    "Nonce" => parsed.nonce = Some(value),
+                    "Issued At" => parsed.issued_at = Some(parse_timestamp(&value, "Issued At")?),
+                    "Expiration Time" => {
+                        parsed.expiration_time = Some(parse_timestamp(&value, "Expiration Time")?)
+                    }
+                    "Not Before" => {
+                        parsed.not_before = Some(parse_timestamp(&value, "Not Before")?)
+                    }
+                    "Request ID" => {}
+                    _ if !in_fields => {
+                        statement_lines.push(line);
+                        continue;
+                    }
+                    _ => {}
+                }
+                in_fields = true;
+            } else if !in_fields && !line.trim().is_empty() {
+                statement_lines.push(line);
+            }
+        }
+
+        if !statement_lines.is_empty() {
+            parsed.statement = Some(statement_lines.join("\n"));
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn validate(&self, validation: &SiwsValidation) -> Result<(), SiwsError> {
+        if self.address != validation.public_key {
+            return Err(SiwsError::AddressMismatch);
+        }
+
+        if !validation.allowed_domains.contains(&self.domain) {
+            return Err(SiwsError::DomainNotAllowed);
+        }
+
+        let skew = Duration::seconds(CLOCK_SKEW_SECONDS);
+
+        let issued_at = self.issued_at.ok_or(SiwsError::MissingField("Issued At"))?;
+        if issued_at > validation.now + skew {
+            return Err(SiwsError::IssuedInFuture);
+        }
+        if validation.now - issued_at > validation.max_age {
+            return Err(SiwsError::MessageTooOld);
+        }
+
+        if let Some(expiration_time) = self.expiration_time
+            && expiration_time <= validation.now - skew
+        {
+            return Err(SiwsError::Expired);
+        }
+
+        if let Some(not_before) = self.not_before
+            && not_before > validation.now + skew
+        {
+            return Err(SiwsError::NotYetValid);
+        }
+
+        Ok(())
+    }
+
+    /// Until when `validate` keeps accepting the message, leaving out the clock skew.
+    pub fn valid_until(&self, max_age: Duration) -> Option<DateTime<Utc>> {
+        let too_old_at = self.issued_at? + max_age;
+        Some(self.expiration_time.map_or(too_old_at, |expiration_time| {
+            expiration_time.min(too_old_at)
+        }))
+    }
+}
+
+fn parse_timestamp(value: &str, field: &'static str) -> Result<DateTime<Utc>, SiwsError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|_| SiwsError::MalformedField(field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5";
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-02-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn message(fields: &str) -> String {
+        format!(
+            "app.catalytics.pro wants you to sign in with your Solana account:\n{}\n\nSign in to Catalytics\nto see your badges\n\n{}",
+            ADDRESS, fields
+        )
+    }
+
+    fn validate(message: &SiwsMessage) -> Result<(), SiwsError> {
+        message.validate(&SiwsValidation {
+            public_key: ADDRESS,
+            allowed_domains: &["app.catalytics.pro".to_string()],
+            max_age: Duration::minutes(5),
+            now: now(),
+        })
+    }
+
+    fn issued_at(offset: Duration) -> SiwsMessage {
+        SiwsMessage {
+            issued_at: Some(now() + offset),
+            ..SiwsMessage::parse(&message("")).unwrap()
+        }
+    }
+
+    #[test]
+    fn parses_every_field() {
+        let parsed = SiwsMessage::parse(&message(
+            "URI: https://app.catalytics.pro\nVersion: 1\nChain ID: mainnet\nNonce: abc123\n\
+             Issued At: 2026-02-01T11:59:00Z\nExpiration Time: 2026-02-01T12:10:00Z\n\
+             Not Before: 2026-02-01T11:58:00+00:00\nRequest ID: 42",
+        ))
+        .unwrap();
+
+        assert_eq!(parsed.domain, "app.catalytics.pro");
+        assert_eq!(parsed.address, ADDRESS);
+        assert_eq!(
+            parsed.statement.as_deref(),
+            Some("Sign in to Catalytics\nto see your badges")
+        );
+        assert_eq!(parsed.uri.as_deref(), Some("https://app.catalytics.pro"));
+        assert_eq!(parsed.version.as_deref(), Some("1"));
+        assert_eq!(parsed.chain_id.as_deref(), Some("mainnet"));
+        assert_eq!(parsed.nonce.as_deref(), Some("abc123"));
+        assert_eq!(parsed.issued_at, Some(now() - Duration::minutes(1)));
+        assert_eq!(parsed.expiration_time, Some(now() + Duration::minutes(10)));
+        assert_eq!(parsed.not_before, Some(now() - Duration::minutes(2)));
+        assert_eq!(validate(&parsed), Ok(()));
+    }
+
+    #[test]
+    fn statement_may_contain_colons() {
+        let parsed = SiwsMessage::parse(&format!(
+            "app.catalytics.pro wants you to sign in with your Solana account:\n{}\n\nNote: read this\n\nNonce: n",
+            ADDRESS
+        ))
+        .unwrap();
+
+        assert_eq!(parsed.statement.as_deref(), Some("Note: read this"));
+        assert_eq!(parsed.nonce.as_deref(), Some("n"));
+    }
+
+    #[test]
+    fn rejects_malformed_messages() {
+        assert_eq!(
+            SiwsMessage::parse("Sign in to Catalytics"),
+            Err(SiwsError::MalformedHeader)
+        );
+        assert_eq!(
+            SiwsMessage::parse(" wants you to sign in with your Solana account:\naddress"),
+            Err(SiwsError::MalformedHeader)
+        );
+        assert_eq!(
+            SiwsMessage::parse("app.catalytics.pro wants you to sign in with your Solana account:"),
+            Err(SiwsError::MissingAddress)
+        );
+        assert_eq!(
+            SiwsMessage::parse(&message("Issued At: yesterday")),
+            Err(SiwsError::MalformedField("Issued At"))
+        );
+        assert_eq!(
+            SiwsMessage::parse(&message("Expiration Time: 12:00")),
+            Err(SiwsError::MalformedField("Expiration Time"))
+        );
+        assert_eq!(
+            SiwsMessage::parse(&message("Not Before: soon")),
+            Err(SiwsError::MalformedField("Not Before"))
+        );
+    }
+
+    #[test]
+    fn rejects_other_addresses_and_domains() {
+        let mut parsed = issued_at(Duration::zero());
+        parsed.address = "other".to_string();
+        assert_eq!(validate(&parsed), Err(SiwsError::AddressMismatch));
+
+        let mut parsed = issued_at(Duration::zero());
+        parsed.domain = "evil.example".to_string();
+        assert_eq!(validate(&parsed), Err(SiwsError::DomainNotAllowed));
+    }
+
+    #[test]
+    fn requires_issued_at_within_the_allowed_age() {
+        let mut parsed = issued_at(Duration::zero());
+        parsed.issued_at = None;
+        assert_eq!(validate(&parsed), Err(SiwsError::MissingField("Issued At")));
+
+        assert_eq!(validate(&issued_at(Duration::seconds(20))), Ok(()));
+        assert_eq!(
+            validate(&issued_at(Duration::minutes(1))),
+            Err(SiwsError::IssuedInFuture)
+        );
+        assert_eq!(
+            validate(&issued_at(-Duration::minutes(6))),
+            Err(SiwsError::MessageTooOld)
+        );
+    }
+
+    #[test]
+    fn honours_expiration_and_not_before() {
+        let mut parsed = issued_at(-Duration::minutes(1));
+        parsed.expiration_time = Some(now() - Duration::minutes(1));
+        assert_eq!(validate(&parsed), Err(SiwsError::Expired));
+
+        parsed.expiration_time = Some(now() - Duration::seconds(10));
+        assert_eq!(validate(&parsed), Ok(()));
+
+        parsed.not_before = Some(now() + Duration::minutes(1));
+        assert_eq!(validate(&parsed), Err(SiwsError::NotYetValid));
+    }
+
+    #[test]
+    fn is_valid_until_it_gets_too_old_or_expires() {
+        let max_age = Duration::minutes(5);
+        let signed = issued_at(Duration::minutes(-1));
+        assert_eq!(
+            signed.valid_until(max_age),
+            Some(now() + Duration::minutes(4))
+        );
+
+        let expiring = SiwsMessage {
+            expiration_time: Some(now() + Duration::minutes(2)),
+            ..signed
+        };
+        assert_eq!(
+            expiring.valid_until(max_age),
+            Some(now() + Duration::minutes(2))
+        );
+
+        let undated = SiwsMessage::parse(&message("")).unwrap();
+        assert_eq!(undated.valid_until(max_age), None);
+    }
+}
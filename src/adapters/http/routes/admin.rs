@@ -0,0 +1,369 @@
+use crate::adapters::http::app_state::AppState;
+use crate::adapters::http::middleware::admin::AdminUser;
+use crate::adapters::http::middleware::auth_middleware;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::admin::Admin;
+use crate::entities::admin_audit_entry::AdminAuditEntry;
+use crate::entities::job_run::{JobRun, JobRunStatus};
+use crate::entities::season::{NewSeason, Season, SeasonRollover, SeasonUpdate};
+use crate::entities::wallet_holdings::WalletHoldingsCacheStats;
+use crate::use_cases::admin::AdminUseCases;
+use crate::use_cases::job::JobUseCases;
+use crate::use_cases::leaderboard::LeaderboardUseCases;
+use crate::use_cases::leaderboard_refresh::LeaderboardRefreshJob;
+use crate::use_cases::wallet_holdings::WalletHoldingsUseCases;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{delete, get, patch, post};
+use axum::{Json, Router, middleware};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tracing::instrument;
+
+pub fn private_router(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/admins", get(read_admins).post(create_admin))
+        .route("/admins/{public_key}", delete(delete_admin))
+        .route("/audit-log", get(read_audit_log))
+        .route("/jobs/runs", get(read_job_runs))
+        .route("/leaderboard/refresh", post(refresh_leaderboard))
+        .route("/seasons/current", patch(update_current_season))
+        .route("/seasons/current/finalize", post(finalize_current_season))
+        .route("/wallet-holdings/cache", get(read_wallet_holdings_cache))
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminResponse {
+    public_key: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<Admin> for AdminResponse {
+    fn from(admin: Admin) -> Self {
+        Self {
+            public_key: admin.public_key,
+            created_at: admin.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditEntryResponse {
+    id: i32,
+    admin_public_key: String,
+    action: String,
+    entity_type: String,
+    entity_id: Option<String>,
+    payload: Value,
+    created_at: DateTime<Utc>,
+}
+
+impl From<AdminAuditEntry> for AuditEntryResponse {
+    fn from(entry: AdminAuditEntry) -> Self {
+        Self {
+            id: entry.id,
+            admin_public_key: entry.admin_public_key,
+            action: entry.action,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id,
+            payload: entry.payload,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JobRunResponse {
+    id: i32,
+    job_name: String,
+    status: String,
+    summary: Value,
+    error: Option<String>,
+    scheduled_for: Option<DateTime<Utc>>,
+    started_at: DateTime<Utc>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<JobRun> for JobRunResponse {
+    fn from(run: JobRun) -> Self {
+        Self {
+            id: run.id,
+            job_name: run.job_name,
+            status: run.status,
+            summary: run.summary,
+            error: run.error,
+            scheduled_for: run.scheduled_for,
+            started_at: run.started_at,
+            finished_at: run.finished_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WalletHoldingsCacheResponse {
+    hits: u64,
+    misses: u64,
+    coalesced: u64,
+    entries: usize,
+}
+
+impl From<WalletHoldingsCacheStats> for WalletHoldingsCacheResponse {
+    fn from(stats: WalletHoldingsCacheStats) -> Self {
+        Self {
+            hits: stats.hits,
+            misses: stats.misses,
+            coalesced: stats.coalesced,
+            entries: stats.entries,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SeasonResponse {
+    id: i32,
+    name: String,
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+    finalized_at: Option<DateTime<Utc>>,
+}
+
+impl From<Season> for SeasonResponse {
+    fn from(season: Season) -> Self {
+        Self {
+            id: season.id,
+            name: season.name,
+            starts_at: season.starts_at,
+            ends_at: season.ends_at,
+            finalized_at: season.finalized_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SeasonRolloverResponse {
+    finalized: SeasonResponse,
+    next: SeasonResponse,
+    archived_entries: u64,
+}
+
+impl From<SeasonRollover> for SeasonRolloverResponse {
+    fn from(rollover: SeasonRollover) -> Self {
+        Self {
+            finalized: rollover.finalized.into(),
+            next: rollover.next.into(),
+            archived_entries: rollover.archived_entries,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateSeasonRequest {
+    name: Option<String>,
+    ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FinalizeSeasonRequest {
+    next_name: String,
+    next_ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateAdminRequest {
+    public_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditLogQueryParams {
+    page: Option<u32>,
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JobRunsQueryParams {
+    limit: Option<u32>,
+}
+
+#[instrument(skip(admin_use_cases))]
+async fn read_admins(
+    _admin: AdminUser,
+    State(admin_use_cases): State<Arc<AdminUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let admins = admin_use_cases.read_admins().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(
+            admins
+                .into_iter()
+                .map(AdminResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[instrument(skip(admin_use_cases))]
+async fn create_admin(
+    admin: AdminUser,
+    State(admin_use_cases): State<Arc<AdminUseCases>>,
+    Json(payload): Json<CreateAdminRequest>,
+) -> AppResult<impl IntoResponse> {
+    let created = admin_use_cases
+        .create_admin(&admin.public_key, &payload.public_key)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(AdminResponse::from(created))))
+}
+
+#[instrument(skip(admin_use_cases))]
+async fn delete_admin(
+    admin: AdminUser,
+    Path(public_key): Path<String>,
+    State(admin_use_cases): State<Arc<AdminUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    admin_use_cases
+        .delete_admin(&admin.public_key, &public_key)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(admin_use_cases))]
+async fn read_audit_log(
+    _admin: AdminUser,
+    Query(params): Query<AuditLogQueryParams>,
+    State(admin_use_cases): State<Arc<AdminUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let page = params.page.filter(|page| *page > 0).unwrap_or(1);
+    let limit = params
+        .limit
+        .filter(|limit| *limit > 0 && *limit <= 100)
+        .unwrap_or(50);
+
+    let entries = admin_use_cases.read_audit_log(page, limit).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(
+            entries
+                .into_iter()
+                .map(AuditEntryResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[instrument(skip(job_use_cases))]
+async fn read_job_runs(
+    _admin: AdminUser,
+    Query(params): Query<JobRunsQueryParams>,
+    State(job_use_cases): State<Arc<JobUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let limit = params
+        .limit
+        .filter(|limit| *limit > 0 && *limit <= 100)
+        .unwrap_or(20);
+
+    let runs = job_use_cases.read_runs(limit).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(
+            runs.into_iter()
+                .map(JobRunResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[instrument(skip(wallet_holdings_use_cases))]
+async fn read_wallet_holdings_cache(
+    _admin: AdminUser,
+    State(wallet_holdings_use_cases): State<Arc<WalletHoldingsUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let stats = wallet_holdings_use_cases
+        .read_cache_stats()
+        .ok_or_else(|| AppError::NotFound("Wallet holdings are not cached".to_string()))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(WalletHoldingsCacheResponse::from(stats)),
+    ))
+}
+
+#[instrument(skip(leaderboard_use_cases, job_use_cases))]
+async fn refresh_leaderboard(
+    admin: AdminUser,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+    State(job_use_cases): State<Arc<JobUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("refresh", "leaderboard", None, json!({}));
+    let job = LeaderboardRefreshJob::new(leaderboard_use_cases, Some(audit));
+    let run = job_use_cases
+        .run_exclusive(&job, None)
+        .await?
+        .ok_or_else(|| {
+            AppError::Conflict("The leaderboard is already being refreshed or synced".to_string())
+        })?;
+
+    if run.status == JobRunStatus::Failed.as_str() {
+        return Err(AppError::Internal(run.error.unwrap_or_default()));
+    }
+
+    Ok((StatusCode::OK, Json(JobRunResponse::from(run))))
+}
+
+#[instrument(skip(leaderboard_use_cases))]
+async fn update_current_season(
+    admin: AdminUser,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+    Json(payload): Json<UpdateSeasonRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("update", "season", None, json!(payload));
+    let season = leaderboard_use_cases
+        .update_open_season(
+            SeasonUpdate {
+                name: payload.name.clone(),
+                ends_at: payload.ends_at,
+            },
+            &audit,
+        )
+        .await?;
+
+    Ok((StatusCode::OK, Json(SeasonResponse::from(season))))
+}
+
+#[instrument(skip(leaderboard_use_cases))]
+async fn finalize_current_season(
+    admin: AdminUser,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+    Json(payload): Json<FinalizeSeasonRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("finalize", "season", None, json!(payload));
+    let rollover = leaderboard_use_cases
+        .finalize_season(
+            NewSeason {
+                name: payload.next_name.clone(),
+                ends_at: payload.next_ends_at,
+            },
+            Some(&audit),
+        )
+        .await?;
+
+    Ok((StatusCode::OK, Json(SeasonRolloverResponse::from(rollover))))
+}
@@ -0,0 +1,440 @@
+use crate::adapters::http::app_state::AppState;
+use crate::adapters::http::middleware::admin::AdminUser;
+use crate::adapters::http::middleware::auth_middleware;
+use crate::app_error::AppResult;
+use crate::entities::badge::{BadgeDefinition, BadgeUpdate, NewBadge};
+use crate::entities::badge_group::{BadgeGroup, BadgeGroupUpdate, NewBadgeGroup};
+use crate::entities::badge_rule::BadgeRule;
+use crate::use_cases::badge::BadgeUseCases;
+use crate::use_cases::badge_evaluation::BadgeEvaluationJob;
+use crate::use_cases::badge_group::BadgeGroupUseCases;
+use crate::use_cases::job::JobUseCases;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, patch, post, put};
+use axum::{Json, Router, middleware};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::instrument;
+
+pub fn private_router(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/badge-groups",
+            get(read_badge_groups).post(create_badge_group),
+        )
+        .route("/badge-groups/order", put(reorder_badge_groups))
+        .route(
+            "/badge-groups/{id}",
+            patch(update_badge_group).delete(delete_badge_group),
+        )
+        .route("/badge-groups/{id}/archive", post(archive_badge_group))
+        .route("/badge-groups/{id}/unarchive", post(unarchive_badge_group))
+        .route("/badge-groups/{id}/badge-order", put(reorder_badges))
+        .route("/badges", get(read_badges).post(create_badge))
+        .route("/badges/{id}", patch(update_badge).delete(delete_badge))
+        .route("/badges/{id}/archive", post(archive_badge))
+        .route("/badges/{id}/unarchive", post(unarchive_badge))
+        .route(
+            "/badges/{id}/rule",
+            put(set_badge_rule).delete(delete_badge_rule),
+        )
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminBadgeGroupResponse {
+    id: i32,
+    title: String,
+    description: String,
+    sort_order: i32,
+    archived_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<BadgeGroup> for AdminBadgeGroupResponse {
+    fn from(badge_group: BadgeGroup) -> Self {
+        Self {
+            id: badge_group.id,
+            title: badge_group.title,
+            description: badge_group.description,
+            sort_order: badge_group.sort_order,
+            archived_at: badge_group.archived_at,
+            created_at: badge_group.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AdminBadgeResponse {
+    id: i32,
+    title: String,
+    description: String,
+    score: i32,
+    badge_group_id: i32,
+    sort_order: i32,
+    rule: Option<BadgeRule>,
+    archived_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<BadgeDefinition> for AdminBadgeResponse {
+    fn from(badge: BadgeDefinition) -> Self {
+        Self {
+            id: badge.id,
+            title: badge.title,
+            description: badge.description,
+            score: badge.score,
+            badge_group_id: badge.badge_group_id,
+            sort_order: badge.sort_order,
+            rule: badge.rule,
+            archived_at: badge.archived_at,
+            created_at: badge.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateBadgeGroupRequest {
+    title: String,
+    description: String,
+    #[serde(default)]
+    sort_order: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateBadgeGroupRequest {
+    title: Option<String>,
+    description: Option<String>,
+    sort_order: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateBadgeRequest {
+    title: String,
+    description: String,
+    score: i32,
+    badge_group_id: i32,
+    #[serde(default)]
+    sort_order: i32,
+    rule: Option<BadgeRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateBadgeRequest {
+    title: Option<String>,
+    description: Option<String>,
+    score: Option<i32>,
+    badge_group_id: Option<i32>,
+    sort_order: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderRequest {
+    ids: Vec<i32>,
+}
+
+#[instrument(skip(badge_group_use_cases))]
+async fn read_badge_groups(
+    _admin: AdminUser,
+    State(badge_group_use_cases): State<Arc<BadgeGroupUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let badge_groups = badge_group_use_cases.read_all_including_archived().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(
+            badge_groups
+                .into_iter()
+                .map(AdminBadgeGroupResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[instrument(skip(badge_group_use_cases))]
+async fn create_badge_group(
+    admin: AdminUser,
+    State(badge_group_use_cases): State<Arc<BadgeGroupUseCases>>,
+    Json(payload): Json<CreateBadgeGroupRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("create", "badge_group", None, json!(payload));
+    let badge_group = badge_group_use_cases
+        .create(
+            NewBadgeGroup {
+                title: payload.title.clone(),
+                description: payload.description.clone(),
+                sort_order: payload.sort_order,
+            },
+            &audit,
+        )
+        .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(AdminBadgeGroupResponse::from(badge_group)),
+    ))
+}
+
+#[instrument(skip(badge_group_use_cases))]
+async fn update_badge_group(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_group_use_cases): State<Arc<BadgeGroupUseCases>>,
+    Json(payload): Json<UpdateBadgeGroupRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry(
+        "update",
+        "badge_group",
+        Some(id.to_string()),
+        json!(payload),
+    );
+    let badge_group = badge_group_use_cases
+        .update(
+            id,
+            BadgeGroupUpdate {
+                title: payload.title.clone(),
+                description: payload.description.clone(),
+                sort_order: payload.sort_order,
+            },
+            &audit,
+        )
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(AdminBadgeGroupResponse::from(badge_group)),
+    ))
+}
+
+#[instrument(skip(badge_group_use_cases))]
+async fn archive_badge_group(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_group_use_cases): State<Arc<BadgeGroupUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("archive", "badge_group", Some(id.to_string()), json!({}));
+    let badge_group = badge_group_use_cases.archive(id, true, &audit).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(AdminBadgeGroupResponse::from(badge_group)),
+    ))
+}
+
+#[instrument(skip(badge_group_use_cases))]
+async fn unarchive_badge_group(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_group_use_cases): State<Arc<BadgeGroupUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("unarchive", "badge_group", Some(id.to_string()), json!({}));
+    let badge_group = badge_group_use_cases.archive(id, false, &audit).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(AdminBadgeGroupResponse::from(badge_group)),
+    ))
+}
+
+#[instrument(skip(badge_group_use_cases))]
+async fn delete_badge_group(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_group_use_cases): State<Arc<BadgeGroupUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("delete", "badge_group", Some(id.to_string()), json!({}));
+    badge_group_use_cases.delete(id, &audit).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(badge_group_use_cases))]
+async fn reorder_badge_groups(
+    admin: AdminUser,
+    State(badge_group_use_cases): State<Arc<BadgeGroupUseCases>>,
+    Json(payload): Json<OrderRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("reorder", "badge_group", None, json!(payload));
+    badge_group_use_cases
+        .reorder(payload.ids.clone(), &audit)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(badge_use_cases))]
+async fn reorder_badges(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+    Json(payload): Json<OrderRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry(
+        "reorder",
+        "badge",
+        Some(format!("group:{}", id)),
+        json!(payload),
+    );
+    badge_use_cases
+        .reorder(id, payload.ids.clone(), &audit)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(badge_use_cases))]
+async fn read_badges(
+    _admin: AdminUser,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let badges = badge_use_cases.read_definitions().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(
+            badges
+                .into_iter()
+                .map(AdminBadgeResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[instrument(skip(badge_use_cases, job_use_cases))]
+async fn create_badge(
+    admin: AdminUser,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+    State(job_use_cases): State<Arc<JobUseCases>>,
+    Json(payload): Json<CreateBadgeRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("create", "badge", None, json!(payload));
+    let badge = badge_use_cases
+        .create_definition(
+            NewBadge {
+                title: payload.title.clone(),
+                description: payload.description.clone(),
+                score: payload.score,
+                badge_group_id: payload.badge_group_id,
+                sort_order: payload.sort_order,
+                rule: payload.rule.clone(),
+            },
+            &audit,
+        )
+        .await?;
+
+    // Awarding a new rule is accepted here and carried out by a job
+    let status = if badge.rule.is_some() {
+        job_use_cases.spawn(Arc::new(BadgeEvaluationJob::new(badge_use_cases.clone())));
+        StatusCode::ACCEPTED
+    } else {
+        StatusCode::CREATED
+    };
+
+    Ok((status, Json(AdminBadgeResponse::from(badge))))
+}
+
+#[instrument(skip(badge_use_cases))]
+async fn update_badge(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+    Json(payload): Json<UpdateBadgeRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("update", "badge", Some(id.to_string()), json!(payload));
+    let badge = badge_use_cases
+        .update_definition(
+            id,
+            BadgeUpdate {
+                title: payload.title.clone(),
+                description: payload.description.clone(),
+                score: payload.score,
+                badge_group_id: payload.badge_group_id,
+                sort_order: payload.sort_order,
+            },
+            &audit,
+        )
+        .await?;
+
+    Ok((StatusCode::OK, Json(AdminBadgeResponse::from(badge))))
+}
+
+#[instrument(skip(badge_use_cases))]
+async fn archive_badge(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("archive", "badge", Some(id.to_string()), json!({}));
+    let badge = badge_use_cases.archive(id, true, &audit).await?;
+
+    Ok((StatusCode::OK, Json(AdminBadgeResponse::from(badge))))
+}
+
+#[instrument(skip(badge_use_cases))]
+async fn unarchive_badge(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("unarchive", "badge", Some(id.to_string()), json!({}));
+    let badge = badge_use_cases.archive(id, false, &audit).await?;
+
+    Ok((StatusCode::OK, Json(AdminBadgeResponse::from(badge))))
+}
+
+#[instrument(skip(badge_use_cases))]
+async fn delete_badge(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("delete", "badge", Some(id.to_string()), json!({}));
+    badge_use_cases.delete(id, &audit).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(badge_use_cases, job_use_cases))]
+async fn set_badge_rule(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+    State(job_use_cases): State<Arc<JobUseCases>>,
+    Json(rule): Json<BadgeRule>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry(
+        "update_rule",
+        "badge",
+        Some(id.to_string()),
+        json!({ "rule": rule }),
+    );
+    let badge = badge_use_cases
+        .set_rule(id, Some(rule.clone()), &audit)
+        .await?;
+    job_use_cases.spawn(Arc::new(BadgeEvaluationJob::new(badge_use_cases.clone())));
+
+    Ok((StatusCode::ACCEPTED, Json(AdminBadgeResponse::from(badge))))
+}
+
+#[instrument(skip(badge_use_cases))]
+async fn delete_badge_rule(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry("delete_rule", "badge", Some(id.to_string()), json!({}));
+    badge_use_cases.set_rule(id, None, &audit).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,143 @@
+use crate::adapters::http::app_state::AppState;
+use crate::adapters::http::middleware::admin::AdminUser;
+use crate::adapters::http::middleware::auth_middleware;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::tracked_token::{
+    NewTrackedToken, TokenKind, TrackedToken, TrackedTokenUpdate,
+};
+use crate::use_cases::tracked_token::TrackedTokenUseCases;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, patch};
+use axum::{Json, Router, middleware};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::instrument;
+
+pub fn private_router(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/tokens",
+            get(read_tracked_tokens).post(create_tracked_token),
+        )
+        .route("/tokens/{id}", patch(update_tracked_token))
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TrackedTokenResponse {
+    id: i32,
+    mint: String,
+    symbol: String,
+    decimals: i32,
+    kind: String,
+    /// Name to use as `signal` in badge rules and leaderboard kinds.
+    signal: String,
+    enabled: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl From<TrackedToken> for TrackedTokenResponse {
+    fn from(token: TrackedToken) -> Self {
+        Self {
+            id: token.id,
+            mint: token.mint,
+            symbol: token.symbol,
+            decimals: token.decimals,
+            kind: token.kind.to_string(),
+            signal: token.signal.name().into_owned(),
+            enabled: token.enabled,
+            created_at: token.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateTrackedTokenRequest {
+    mint: String,
+    symbol: String,
+    decimals: i32,
+    kind: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateTrackedTokenRequest {
+    symbol: Option<String>,
+    decimals: Option<i32>,
+    enabled: Option<bool>,
+}
+
+#[instrument(skip(tracked_token_use_cases))]
+async fn read_tracked_tokens(
+    _admin: AdminUser,
+    State(tracked_token_use_cases): State<Arc<TrackedTokenUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let tokens = tracked_token_use_cases.read_all().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(
+            tokens
+                .into_iter()
+                .map(TrackedTokenResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[instrument(skip(tracked_token_use_cases))]
+async fn create_tracked_token(
+    admin: AdminUser,
+    State(tracked_token_use_cases): State<Arc<TrackedTokenUseCases>>,
+    Json(payload): Json<CreateTrackedTokenRequest>,
+) -> AppResult<impl IntoResponse> {
+    let kind: TokenKind = payload.kind.parse().map_err(AppError::BadRequest)?;
+    let audit = admin.audit_entry("create", "tracked_token", None, json!(payload));
+    let token = tracked_token_use_cases
+        .create(
+            NewTrackedToken {
+                mint: payload.mint.clone(),
+                symbol: payload.symbol.clone(),
+                decimals: payload.decimals,
+                kind,
+            },
+            &audit,
+        )
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(TrackedTokenResponse::from(token))))
+}
+
+#[instrument(skip(tracked_token_use_cases))]
+async fn update_tracked_token(
+    admin: AdminUser,
+    Path(id): Path<i32>,
+    State(tracked_token_use_cases): State<Arc<TrackedTokenUseCases>>,
+    Json(payload): Json<UpdateTrackedTokenRequest>,
+) -> AppResult<impl IntoResponse> {
+    let audit = admin.audit_entry(
+        "update",
+        "tracked_token",
+        Some(id.to_string()),
+        json!(payload),
+    );
+    let token = tracked_token_use_cases
+        .update(
+            id,
+            TrackedTokenUpdate {
+                symbol: payload.symbol.clone(),
+                decimals: payload.decimals,
+                enabled: payload.enabled,
+            },
+            &audit,
+        )
+        .await?;
+
+    Ok((StatusCode::OK, Json(TrackedTokenResponse::from(token))))
+}
@@ -0,0 +1,134 @@
+use crate::adapters::http::app_state::AppState;
+use crate::adapters::http::middleware::auth::{
+    AuthError, authenticate_wallet_sign_in, extract_bearer_token,
+};
+use crate::app_error::AppResult;
+use crate::entities::auth_nonce::AuthNonce;
+use crate::entities::auth_session::IssuedSession;
+use crate::infrastructure::auth::AuthConfig;
+use crate::use_cases::auth::{AuthUseCases, validate_address};
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::instrument;
+
+pub fn public_router() -> Router<AppState> {
+    Router::new()
+        .route("/nonce", get(issue_nonce))
+        .route("/session", post(create_session).delete(revoke_session))
+        .route("/session/refresh", post(refresh_session))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NonceQueryParams {
+    public_key: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NonceResponse {
+    nonce: String,
+    public_key: String,
+    issued_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<AuthNonce> for NonceResponse {
+    fn from(nonce: AuthNonce) -> Self {
+        Self {
+            nonce: nonce.nonce,
+            public_key: nonce.public_key,
+            issued_at: nonce.created_at,
+            expires_at: nonce.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionResponse {
+    public_key: String,
+    token_type: &'static str,
+    access_token: String,
+    access_expires_at: DateTime<Utc>,
+    refresh_token: String,
+    refresh_expires_at: DateTime<Utc>,
+}
+
+impl From<IssuedSession> for SessionResponse {
+    fn from(session: IssuedSession) -> Self {
+        Self {
+            public_key: session.public_key,
+            token_type: "Bearer",
+            access_token: session.access_token,
+            access_expires_at: session.access_expires_at,
+            refresh_token: session.refresh_token,
+            refresh_expires_at: session.refresh_expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RefreshSessionRequest {
+    refresh_token: String,
+}
+
+#[instrument(skip(auth_use_cases))]
+async fn issue_nonce(
+    Query(params): Query<NonceQueryParams>,
+    State(auth_use_cases): State<Arc<AuthUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    validate_address("publicKey", &params.public_key)?;
+
+    let nonce = auth_use_cases.issue_nonce(&params.public_key).await?;
+
+    Ok((StatusCode::OK, Json(NonceResponse::from(nonce))))
+}
+
+#[instrument(skip(headers, auth_config, auth_use_cases))]
+async fn create_session(
+    headers: HeaderMap,
+    State(auth_config): State<Arc<AuthConfig>>,
+    State(auth_use_cases): State<Arc<AuthUseCases>>,
+) -> Result<impl IntoResponse, AuthError> {
+    let public_key = authenticate_wallet_sign_in(&headers, &auth_config, &auth_use_cases).await?;
+
+    let session = auth_use_cases.create_session(&public_key).await?;
+
+    Ok((StatusCode::CREATED, Json(SessionResponse::from(session))))
+}
+
+#[instrument(skip(auth_use_cases, payload))]
+async fn refresh_session(
+    State(auth_use_cases): State<Arc<AuthUseCases>>,
+    Json(payload): Json<RefreshSessionRequest>,
+) -> Result<impl IntoResponse, AuthError> {
+    let session = auth_use_cases
+        .refresh_session(&payload.refresh_token)
+        .await?
+        .ok_or(AuthError::InvalidToken)?;
+
+    Ok((StatusCode::OK, Json(SessionResponse::from(session))))
+}
+
+#[instrument(skip(headers, auth_use_cases))]
+async fn revoke_session(
+    headers: HeaderMap,
+    State(auth_use_cases): State<Arc<AuthUseCases>>,
+) -> Result<impl IntoResponse, AuthError> {
+    let access_token =
+        extract_bearer_token(&headers).ok_or(AuthError::MissingHeader("Authorization"))?;
+
+    if !auth_use_cases.revoke_session(&access_token).await? {
+        return Err(AuthError::InvalidToken);
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
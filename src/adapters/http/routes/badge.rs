@@ -1,35 +1,57 @@
 use crate::adapters::http::app_state::AppState;
+use crate::adapters::http::middleware::admin::AdminUser;
 use crate::adapters::http::middleware::auth::AuthenticatedUser;
 use crate::adapters::http::middleware::auth_middleware;
+use crate::adapters::http::middleware::internal::InternalCaller;
 use crate::app_error::AppResult;
 use crate::entities::badge::BadgeDto;
 use crate::entities::badge_group::BadgeGroup;
 use crate::entities::badge_requirement::BadgeRequirementDto;
+use crate::entities::badge_rule::BadgeRule;
+use crate::entities::sync_report::{SignalSyncResult, SyncReport};
+use crate::entities::token_amount::{decimal_to_json, serialize_decimal};
 use crate::entities::user_progression::UserProgressionDto;
+use crate::infrastructure::sync::SyncConfig;
+use crate::use_cases::admin::AdminUseCases;
 use crate::use_cases::badge::BadgeUseCases;
 use crate::use_cases::badge_group::BadgeGroupUseCases;
 use crate::use_cases::beta_applicant_progression::BetaApplicantProgressionUseCases;
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
-use axum::routing::get;
+use axum::http::{StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
 use axum::{Json, Router, middleware};
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::instrument;
+use tracing::{instrument, warn};
 
-pub fn private_router() -> Router<AppState> {
+pub fn private_router(app_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/", get(read_badges))
-        .layer(middleware::from_fn(auth_middleware))
+        .route("/sync", post(sync_own_badges))
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
 }
 
 pub fn public_router() -> Router<AppState> {
     Router::new().route("/sync", get(sync_user_badges))
 }
 
+/// Batch sync for admins, nested under `/admin`.
+pub fn admin_router(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/badges/sync", post(admin_sync_badges))
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
+}
+
+/// Batch sync for services holding the internal API token, nested under `/internal`.
+pub fn internal_router() -> Router<AppState> {
+    Router::new().route("/badges/sync", post(internal_sync_badges))
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct BadgeResponse {
@@ -42,7 +64,9 @@ struct BadgeResponse {
     badge_group_id: i32,
     progression_event_type: String,
     operation: String,
-    required_count: i32,
+    #[serde(serialize_with = "serialize_decimal")]
+    required_count: Decimal,
+    rule: Option<BadgeRule>,
 }
 
 fn assemble_badge_responses(
@@ -57,7 +81,8 @@ fn assemble_badge_responses(
     badge_dtos
         .into_iter()
         .map(|badge| {
-            let requirement = requirements_map.get(&badge.id);
+            let rule = requirements_map.get(&badge.id).map(|req| req.rule.clone());
+            let comparison = rule.as_ref().and_then(BadgeRule::primary_comparison);
             BadgeResponse {
                 id: badge.id,
                 title: badge.title,
@@ -66,11 +91,14 @@ fn assemble_badge_responses(
                 is_unlocked: badge.is_unlocked,
                 unlocked_at: badge.unlocked_at,
                 badge_group_id: badge.badge_group_id,
-                progression_event_type: requirement
-                    .map(|r| r.progression_event_type.clone())
+                progression_event_type: comparison
+                    .map(|(signal, _, _)| signal.name().to_string())
                     .unwrap_or_default(),
-                operation: requirement.map(|r| r.operation.clone()).unwrap_or_default(),
-                required_count: requirement.map(|r| r.required_count).unwrap_or(0),
+                operation: comparison
+                    .map(|(_, operation, _)| operation.to_string())
+                    .unwrap_or_default(),
+                required_count: comparison.map(|(_, _, value)| value).unwrap_or_default(),
+                rule,
             }
         })
         .collect()
@@ -98,7 +126,10 @@ impl From<BadgeGroup> for BadgeGroupResponse {
 #[serde(rename_all = "camelCase")]
 struct UserProgressionResponse {
     progression_event_type: String,
-    current_progress: i32,
+    #[serde(serialize_with = "serialize_decimal")]
+    current_progress: Decimal,
+    sync_status: Option<String>,
+    last_synced_at: Option<DateTime<Utc>>,
 }
 
 impl From<UserProgressionDto> for UserProgressionResponse {
@@ -106,6 +137,10 @@ impl From<UserProgressionDto> for UserProgressionResponse {
         Self {
             progression_event_type: progression.progression_event_type,
             current_progress: progression.current_progress,
+            sync_status: progression
+                .sync_status
+                .map(|status| status.as_str().to_string()),
+            last_synced_at: progression.last_synced_at,
         }
     }
 }
@@ -158,14 +193,194 @@ struct SyncUserBadgesQueryParams {
     public_key: String,
 }
 
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SignalSyncResponse {
+    progression_event_type: String,
+    status: String,
+    value: Option<Value>,
+    error: Option<String>,
+}
+
+impl From<SignalSyncResult> for SignalSyncResponse {
+    fn from(result: SignalSyncResult) -> Self {
+        Self {
+            progression_event_type: result.signal.name().to_string(),
+            status: result.outcome.status().to_string(),
+            value: result.outcome.value().map(decimal_to_json),
+            error: result.outcome.reason().map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SyncReportResponse {
+    signals: Vec<SignalSyncResponse>,
+    unlocked_badge_ids: Vec<i32>,
+}
+
+impl From<SyncReport> for SyncReportResponse {
+    fn from(report: SyncReport) -> Self {
+        Self {
+            signals: report
+                .signals
+                .into_iter()
+                .map(SignalSyncResponse::from)
+                .collect(),
+            unlocked_badge_ids: report.unlocked_badge_ids,
+        }
+    }
+}
+
 #[instrument(skip(progression_use_cases, badge_use_cases))]
+async fn sync_own_badges(
+    auth: AuthenticatedUser,
+    State(progression_use_cases): State<Arc<BetaApplicantProgressionUseCases>>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let report = progression_use_cases
+        .sync_with_cooldown(&auth.public_key, badge_use_cases)
+        .await?;
+    Ok((StatusCode::OK, Json(SyncReportResponse::from(report))))
+}
+
+/// Deprecated in favour of the authenticated `POST /api/badges/sync`. Still public, but only
+/// syncs registered wallets and shares the per-wallet cooldown.
+#[instrument(skip(progression_use_cases, badge_use_cases, sync_config))]
 async fn sync_user_badges(
     Query(params): Query<SyncUserBadgesQueryParams>,
     State(progression_use_cases): State<Arc<BetaApplicantProgressionUseCases>>,
     State(badge_use_cases): State<Arc<BadgeUseCases>>,
+    State(sync_config): State<Arc<SyncConfig>>,
+) -> AppResult<Response> {
+    if !sync_config.legacy_sync_enabled {
+        return Ok((
+            StatusCode::GONE,
+            [(header::LINK, SYNC_SUCCESSOR_LINK)],
+            "Use POST /api/badges/sync",
+        )
+            .into_response());
+    }
+
+    warn!(
+        "Deprecated GET /badges/sync called for {}",
+        params.public_key
+    );
+    let report = progression_use_cases
+        .sync_with_cooldown(&params.public_key, badge_use_cases)
+        .await?;
+    Ok((
+        StatusCode::OK,
+        [
+            (header::HeaderName::from_static("deprecation"), "true"),
+            (header::LINK, SYNC_SUCCESSOR_LINK),
+        ],
+        Json(SyncReportResponse::from(report)),
+    )
+        .into_response())
+}
+
+const SYNC_SUCCESSOR_LINK: &str = "</api/badges/sync>; rel=\"successor-version\"";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchSyncRequest {
+    public_keys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WalletSyncResponse {
+    public_key: String,
+    report: Option<SyncReportResponse>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchSyncResponse {
+    synced: usize,
+    failed: usize,
+    results: Vec<WalletSyncResponse>,
+}
+
+async fn batch_sync(
+    public_keys: &[String],
+    progression_use_cases: &BetaApplicantProgressionUseCases,
+    badge_use_cases: Arc<BadgeUseCases>,
+) -> AppResult<BatchSyncResponse> {
+    let results: Vec<WalletSyncResponse> = progression_use_cases
+        .sync_many(public_keys, badge_use_cases)
+        .await?
+        .into_iter()
+        .map(|(public_key, result)| match result {
+            Ok(report) => WalletSyncResponse {
+                public_key,
+                report: Some(SyncReportResponse::from(report)),
+                error: None,
+            },
+            Err(e) => WalletSyncResponse {
+                public_key,
+                report: None,
+                error: Some(e.to_string()),
+            },
+        })
+        .collect();
+
+    let failed = results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count();
+    Ok(BatchSyncResponse {
+        synced: results.len() - failed,
+        failed,
+        results,
+    })
+}
+
+#[instrument(skip(admin_use_cases, progression_use_cases, badge_use_cases))]
+async fn admin_sync_badges(
+    admin: AdminUser,
+    State(admin_use_cases): State<Arc<AdminUseCases>>,
+    State(progression_use_cases): State<Arc<BetaApplicantProgressionUseCases>>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+    Json(payload): Json<BatchSyncRequest>,
 ) -> AppResult<impl IntoResponse> {
-    progression_use_cases
-        .sync_all_progressions(&params.public_key, badge_use_cases)
+    // Each wallet syncs in its own transactions, so the request is logged before it runs
+    // rather than after, where a failure could leave syncs without an entry.
+    admin_use_cases
+        .record(
+            &admin.public_key,
+            "sync",
+            "beta_applicant",
+            None,
+            json!({ "publicKeys": payload.public_keys }),
+        )
         .await?;
-    Ok(StatusCode::OK)
+
+    let response = batch_sync(
+        &payload.public_keys,
+        &progression_use_cases,
+        badge_use_cases,
+    )
+    .await?;
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[instrument(skip(progression_use_cases, badge_use_cases))]
+async fn internal_sync_badges(
+    _internal: InternalCaller,
+    State(progression_use_cases): State<Arc<BetaApplicantProgressionUseCases>>,
+    State(badge_use_cases): State<Arc<BadgeUseCases>>,
+    Json(payload): Json<BatchSyncRequest>,
+) -> AppResult<impl IntoResponse> {
+    let response = batch_sync(
+        &payload.public_keys,
+        &progression_use_cases,
+        badge_use_cases,
+    )
+    .await?;
+    Ok((StatusCode::OK, Json(response)))
 }
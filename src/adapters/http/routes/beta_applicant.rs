@@ -3,6 +3,7 @@ use crate::adapters::http::middleware::auth::AuthenticatedUser;
 use crate::adapters::http::middleware::auth_middleware;
 use crate::app_error::AppResult;
 use crate::entities::beta_applicant::BetaApplicant;
+use crate::entities::leaderboard_entry::UserStanding;
 use crate::use_cases::badge::BadgeUseCases;
 use crate::use_cases::beta_applicant::BetaApplicantUseCases;
 use crate::use_cases::beta_applicant_progression::BetaApplicantProgressionUseCases;
@@ -17,12 +18,12 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::instrument;
 
-pub fn private_router() -> Router<AppState> {
+pub fn private_router(app_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/", post(create_beta_applicant))
         .route("/", get(read_beta_applicant))
         .route("/", patch(update_beta_applicant))
-        .layer(middleware::from_fn(auth_middleware))
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
 }
 
 pub fn public_router() -> Router<AppState> {
@@ -38,7 +39,23 @@ struct BetaApplicantResponse {
     referral_code: String,
     referred_by: Option<String>,
     referral_count: i64,
+    /// Deprecated: same as `officialRank`.
     current_rank: u32,
+    /// Rank as of the last daily refresh; 0 until the user is on the leaderboard.
+    official_rank: u32,
+    official_score: i32,
+    /// Rank and score once the current badge total is added at the next refresh.
+    projected_rank: u32,
+    projected_score: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateBetaApplicantResponse {
+    #[serde(flatten)]
+    applicant: BetaApplicantResponse,
+    /// Badges earned by the signup and the first sync.
+    unlocked_badge_ids: Vec<i32>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -48,7 +65,14 @@ struct BetaApplicantsCountResponse {
 }
 
 impl BetaApplicantResponse {
-    fn from_applicant_with_rank(applicant: BetaApplicant, rank: u32) -> Self {
+    fn from_applicant_with_standing(
+        applicant: BetaApplicant,
+        standing: Option<UserStanding>,
+    ) -> Self {
+        let official_rank = standing
+            .as_ref()
+            .and_then(|standing| standing.official_rank)
+            .unwrap_or(0);
         Self {
             public_key: applicant.public_key,
             email: applicant.email,
@@ -56,7 +80,11 @@ impl BetaApplicantResponse {
             referral_code: applicant.referral_code,
             referred_by: applicant.referred_by,
             referral_count: applicant.referral_count,
-            current_rank: rank,
+            current_rank: official_rank,
+            official_rank,
+            official_score: standing.as_ref().map_or(0, |s| s.official_score),
+            projected_rank: standing.as_ref().map_or(0, |s| s.projected_rank),
+            projected_score: standing.as_ref().map_or(0, |s| s.projected_score),
         }
     }
 }
@@ -76,7 +104,7 @@ async fn create_beta_applicant(
     State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
     Json(payload): Json<CreateBetaApplicantRequest>,
 ) -> AppResult<impl IntoResponse> {
-    let applicant = beta_applicant_use_cases
+    let (applicant, report) = beta_applicant_use_cases
         .create(
             &auth.public_key,
             payload.referral_code.as_deref(),
@@ -86,15 +114,16 @@ async fn create_beta_applicant(
         )
         .await?;
 
-    let rank = leaderboard_use_cases
-        .get_user_rank(&auth.public_key)
+    let standing = leaderboard_use_cases
+        .get_user_standing(&auth.public_key)
         .await?;
 
     Ok((
         StatusCode::CREATED,
-        Json(BetaApplicantResponse::from_applicant_with_rank(
-            applicant, rank,
-        )),
+        Json(CreateBetaApplicantResponse {
+            applicant: BetaApplicantResponse::from_applicant_with_standing(applicant, standing),
+            unlocked_badge_ids: report.unlocked_badge_ids,
+        }),
     ))
 }
 
@@ -105,14 +134,14 @@ async fn read_beta_applicant(
     State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
 ) -> AppResult<impl IntoResponse> {
     let applicant = beta_applicant_use_cases.read(&auth.public_key).await?;
-    let rank = leaderboard_use_cases
-        .get_user_rank(&auth.public_key)
+    let standing = leaderboard_use_cases
+        .get_user_standing(&auth.public_key)
         .await?;
 
     Ok((
         StatusCode::OK,
-        Json(BetaApplicantResponse::from_applicant_with_rank(
-            applicant, rank,
+        Json(BetaApplicantResponse::from_applicant_with_standing(
+            applicant, standing,
         )),
     ))
 }
@@ -133,14 +162,14 @@ async fn update_beta_applicant(
     let applicant = beta_applicant_use_cases
         .update(&auth.public_key, payload.email.as_deref())
         .await?;
-    let rank = leaderboard_use_cases
-        .get_user_rank(&auth.public_key)
+    let standing = leaderboard_use_cases
+        .get_user_standing(&auth.public_key)
         .await?;
 
     Ok((
         StatusCode::OK,
-        Json(BetaApplicantResponse::from_applicant_with_rank(
-            applicant, rank,
+        Json(BetaApplicantResponse::from_applicant_with_standing(
+            applicant, standing,
         )),
     ))
 }
@@ -0,0 +1,161 @@
+use crate::adapters::http::app_state::AppState;
+use crate::adapters::http::middleware::auth::AuthenticatedUser;
+use crate::adapters::http::middleware::auth_middleware;
+use crate::entities::user_event::{UserEvent, UserEventKind};
+use crate::use_cases::event::EventBus;
+use axum::extract::ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code};
+use axum::extract::{Query, Request, State};
+use axum::http::{HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Router, middleware};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::{Stream, StreamExt};
+use tracing::{instrument, warn};
+
+pub fn private_router(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/stream", get(stream_events))
+        .route("/ws", get(websocket_events))
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
+        .layer(middleware::from_fn(access_token_from_query))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccessTokenQueryParams {
+    access_token: Option<String>,
+}
+
+/// Browsers cannot set headers on `EventSource` and WebSocket requests, so these routes also
+/// accept the access token as `?accessToken=`.
+async fn access_token_from_query(mut request: Request, next: Next) -> Response {
+    if !request.headers().contains_key(header::AUTHORIZATION)
+        && let Ok(Query(AccessTokenQueryParams {
+            access_token: Some(access_token),
+        })) = Query::try_from_uri(request.uri())
+        && let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", access_token))
+    {
+        request.headers_mut().insert(header::AUTHORIZATION, value);
+    }
+    next.run(request).await
+}
+
+/// Events of a single user, ending once `expires_at` passes so that a stream never outlives
+/// the credentials it was opened with. Events a slow client missed are skipped rather than
+/// buffered.
+fn user_events(
+    receiver: broadcast::Receiver<UserEvent>,
+    public_key: String,
+    expires_at: DateTime<Utc>,
+) -> impl Stream<Item = UserEventKind> {
+    let expiry = tokio_stream::once(None).then(move |end| async move {
+        let remaining = (expires_at - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(remaining).await;
+        end
+    });
+
+    BroadcastStream::new(receiver)
+        .filter_map(move |event| match event {
+            Ok(event) if event.public_key == public_key => Some(Some(event.kind)),
+            Ok(_) => None,
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                warn!("Event stream of {} skipped {} events", public_key, skipped);
+                None
+            }
+        })
+        .merge(expiry)
+        .map_while(|event| event)
+}
+
+#[instrument(skip(event_bus))]
+async fn stream_events(
+    auth: AuthenticatedUser,
+    State(event_bus): State<Arc<EventBus>>,
+) -> impl IntoResponse {
+    let events = user_events(event_bus.subscribe(), auth.public_key, auth.expires_at)
+        .map(|event| Event::default().event(event.name()).json_data(&event));
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+#[instrument(skip(websocket, event_bus))]
+async fn websocket_events(
+    auth: AuthenticatedUser,
+    websocket: WebSocketUpgrade,
+    State(event_bus): State<Arc<EventBus>>,
+) -> impl IntoResponse {
+    let receiver = event_bus.subscribe();
+    let events = user_events(receiver, auth.public_key, auth.expires_at);
+    websocket.on_upgrade(move |socket| forward_to_websocket(socket, events))
+}
+
+async fn forward_to_websocket(mut socket: WebSocket, events: impl Stream<Item = UserEventKind>) {
+    let mut events = Box::pin(events);
+
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else {
+                    // The credentials expired; clients reconnect with fresh ones
+                    let _ = socket.send(Message::Close(Some(CloseFrame {
+                        code: close_code::POLICY,
+                        reason: "Session expired".into(),
+                    }))).await;
+                    return;
+                };
+                let Ok(json) = serde_json::to_string(&event) else {
+                    continue;
+                };
+                if socket.send(Message::Text(json.into())).await.is_err() {
+                    return;
+                }
+            }
+            // Incoming messages are ignored; the loop only ends when the client goes away
+            message = socket.recv() => match message {
+                Some(Ok(_)) => {}
+                _ => return,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    const PUBLIC_KEY: &str = "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5";
+
+    #[tokio::test]
+    async fn streams_only_the_users_events_until_the_credentials_expire() {
+        let event_bus = EventBus::new(16);
+        let events = user_events(
+            event_bus.subscribe(),
+            PUBLIC_KEY.to_string(),
+            Utc::now() + Duration::milliseconds(200),
+        );
+        event_bus.send(UserEvent::new(
+            "someone else",
+            UserEventKind::BadgeUnlocked { badge_id: 1 },
+        ));
+        event_bus.send(UserEvent::new(
+            PUBLIC_KEY,
+            UserEventKind::BadgeUnlocked { badge_id: 2 },
+        ));
+
+        let received: Vec<UserEventKind> =
+            tokio::time::timeout(std::time::Duration::from_secs(5), events.collect())
+                .await
+                .unwrap();
+
+        assert_eq!(received, vec![UserEventKind::BadgeUnlocked { badge_id: 2 }]);
+    }
+}
@@ -1,23 +1,44 @@
 use crate::adapters::http::app_state::AppState;
 use crate::adapters::http::middleware::auth::AuthenticatedUser;
 use crate::adapters::http::middleware::auth_middleware;
-use crate::app_error::AppResult;
-use crate::entities::leaderboard_entry::LeaderboardEntryDto;
-use crate::use_cases::leaderboard::LeaderboardUseCases;
-use axum::extract::{Query, State};
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::leaderboard_entry::{
+    LeaderboardCursor, LeaderboardEntryDto, LeaderboardSlice,
+};
+use crate::entities::leaderboard_kind::LeaderboardKind;
+use crate::entities::leaderboard_snapshot::{LeaderboardSnapshot, MoverDirection, RankMovement};
+use crate::entities::season::Season;
+use crate::entities::token_amount::decimal_to_json;
+use crate::infrastructure::leaderboard::LeaderboardConfig;
+use crate::use_cases::leaderboard::{LeaderboardUseCases, PublicLeaderboard};
+use axum::extract::{Path, Query, State};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use axum::{Json, Router, middleware};
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use tracing::instrument;
 
-pub fn private_router() -> Router<AppState> {
+pub fn public_router() -> Router<AppState> {
+    Router::new().route("/public", get(get_public_leaderboard))
+}
+
+pub fn private_router(app_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/", get(get_user_leaderboard))
         .route("/list", get(get_leaderboard_list))
-        .layer(middleware::from_fn(auth_middleware))
+        .route("/history", get(get_rank_history))
+        .route("/movers/climbers", get(get_top_climbers))
+        .route("/movers/fallers", get(get_top_fallers))
+        .route("/seasons", get(get_seasons))
+        .route("/seasons/{id}/list", get(get_season_leaderboard_list))
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
 }
 
 #[derive(Debug, Deserialize)]
@@ -25,6 +46,42 @@ pub fn private_router() -> Router<AppState> {
 struct LeaderboardQueryParams {
     page: Option<u32>,
     limit: Option<u32>,
+    /// `total` (default), `badge_group:<id>` or `signal:<name>`.
+    kind: Option<String>,
+    /// Opaque keyset cursor from a previous response; replaces `page`.
+    cursor: Option<String>,
+    /// `me` returns the caller's entry with `radius` neighbours on each side.
+    around: Option<String>,
+    radius: Option<u32>,
+}
+
+impl LeaderboardQueryParams {
+    fn kind(&self) -> AppResult<LeaderboardKind> {
+        match &self.kind {
+            Some(kind) => kind.parse().map_err(AppError::BadRequest),
+            None => Ok(LeaderboardKind::Total),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PublicLeaderboardQueryParams {
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HistoryQueryParams {
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MoversQueryParams {
+    days: Option<u32>,
+    limit: Option<u32>,
 }
 
 #[derive(Debug, Serialize)]
@@ -33,6 +90,9 @@ struct LeaderboardEntryResponse {
     public_key: String,
     rank: u32,
     total_score: i32,
+    /// Exact score of signal boards.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<Value>,
     previous_rank: Option<u32>,
 }
 
@@ -42,6 +102,7 @@ impl From<LeaderboardEntryDto> for LeaderboardEntryResponse {
             public_key: mask_public_key(&entry.public_key),
             rank: entry.rank,
             total_score: entry.total_score,
+            score: entry.score.map(decimal_to_json),
             previous_rank: entry.previous_rank,
         }
     }
@@ -66,6 +127,50 @@ fn calculate_user_page(user_rank: u32, limit: u32) -> u32 {
     ((user_rank - 1) / limit) + 1
 }
 
+/// Defaults to the page containing the user when no page is requested.
+fn resolve_page_and_limit(params: &LeaderboardQueryParams, user_rank: u32) -> (u32, u32) {
+    let default_limit = 10;
+    let limit = params.limit.unwrap_or(default_limit);
+
+    let limit = if limit == 0 || limit > 100 { 10 } else { limit };
+
+    let page = match params.page {
+        Some(p) => {
+            if p == 0 {
+                1
+            } else {
+                p
+            }
+        }
+        None => calculate_user_page(user_rank, limit),
+    };
+
+    (page, limit)
+}
+
+fn encode_cursor(cursor: LeaderboardCursor) -> String {
+    let raw = match cursor {
+        LeaderboardCursor::After(rank) => format!("after:{}", rank),
+        LeaderboardCursor::Before(rank) => format!("before:{}", rank),
+    };
+    general_purpose::URL_SAFE_NO_PAD.encode(raw)
+}
+
+fn decode_cursor(cursor: &str) -> AppResult<LeaderboardCursor> {
+    let invalid = || AppError::BadRequest("Invalid cursor".to_string());
+    let raw = general_purpose::URL_SAFE_NO_PAD
+        .decode(cursor)
+        .map_err(|_| invalid())?;
+    let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+
+    match raw.split_once(':') {
+        Some(("after", rank)) => rank.parse().map(LeaderboardCursor::After),
+        Some(("before", rank)) => rank.parse().map(LeaderboardCursor::Before),
+        _ => return Err(invalid()),
+    }
+    .map_err(|_| invalid())
+}
+
 fn is_user_on_page(user_rank: u32, page: u32, limit: u32) -> bool {
     if user_rank == 0 {
         return false;
@@ -89,6 +194,10 @@ struct PaginationResponse {
     page: u32,
     limit: u32,
     total: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -96,13 +205,118 @@ struct PaginationResponse {
 struct UserContextResponse {
     rank: u32,
     total_score: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<Value>,
     is_on_current_page: bool,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PublicLeaderboardResponse {
+    leaderboard: Vec<LeaderboardEntryResponse>,
+    total: u32,
+    generated_at: DateTime<Utc>,
+}
+
+impl From<PublicLeaderboard> for PublicLeaderboardResponse {
+    fn from(leaderboard: PublicLeaderboard) -> Self {
+        Self {
+            leaderboard: leaderboard
+                .entries
+                .into_iter()
+                .map(LeaderboardEntryResponse::from)
+                .collect(),
+            total: leaderboard.total,
+            generated_at: leaderboard.generated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RankHistoryPointResponse {
+    rank: u32,
+    total_score: i32,
+    taken_at: DateTime<Utc>,
+}
+
+impl From<LeaderboardSnapshot> for RankHistoryPointResponse {
+    fn from(snapshot: LeaderboardSnapshot) -> Self {
+        Self {
+            rank: snapshot.rank,
+            total_score: snapshot.total_score,
+            taken_at: snapshot.taken_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RankHistoryResponse {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    history: Vec<RankHistoryPointResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RankMovementResponse {
+    public_key: String,
+    rank: u32,
+    total_score: i32,
+    baseline_rank: u32,
+    change: i32,
+}
+
+impl From<RankMovement> for RankMovementResponse {
+    fn from(movement: RankMovement) -> Self {
+        Self {
+            public_key: mask_public_key(&movement.public_key),
+            rank: movement.rank,
+            total_score: movement.total_score,
+            baseline_rank: movement.baseline_rank,
+            change: movement.change,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SeasonResponse {
+    id: i32,
+    name: String,
+    starts_at: DateTime<Utc>,
+    ends_at: Option<DateTime<Utc>>,
+    finalized_at: Option<DateTime<Utc>>,
+    is_current: bool,
+}
+
+impl From<Season> for SeasonResponse {
+    fn from(season: Season) -> Self {
+        Self {
+            is_current: season.is_open(),
+            id: season.id,
+            name: season.name,
+            starts_at: season.starts_at,
+            ends_at: season.ends_at,
+            finalized_at: season.finalized_at,
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct UserLeaderboardResponse {
+    /// Rank as of the last daily refresh; 0 until the user is on the leaderboard.
+    official_rank: u32,
+    official_score: i32,
+    previous_rank: Option<u32>,
+    /// Rank and score once the current badge total is added at the next refresh.
+    projected_rank: u32,
+    projected_score: i32,
+    /// Deprecated: same as `projectedRank`.
     rank: u32,
+    /// Deprecated: same as `projectedScore`.
     total_score: i32,
 }
 
@@ -112,61 +326,264 @@ async fn get_leaderboard_list(
     Query(params): Query<LeaderboardQueryParams>,
     State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
 ) -> AppResult<impl IntoResponse> {
+    let kind = params.kind()?;
     let user_entry = leaderboard_use_cases
-        .get_user_leaderboard_entry(&auth.public_key)
+        .get_user_entry_of_kind(kind, &auth.public_key)
         .await?;
 
     let (user_rank, user_score) = match &user_entry {
         Some(entry) => (entry.rank, entry.total_score),
         None => (0, 0),
     };
+    let user_exact_score = user_entry.as_ref().and_then(|entry| entry.score);
 
-    let default_limit = 10;
-    let limit = params.limit.unwrap_or(default_limit);
+    if kind != LeaderboardKind::Total && (params.cursor.is_some() || params.around.is_some()) {
+        return Err(AppError::BadRequest(
+            "cursor and around are only supported for the total leaderboard".to_string(),
+        ));
+    }
 
-    let limit = if limit == 0 || limit > 100 { 10 } else { limit };
+    let (page, limit) = resolve_page_and_limit(&params, user_rank);
 
-    let page = match params.page {
-        Some(p) => {
-            if p == 0 {
-                1
-            } else {
-                p
+    let (slice, limit) = match (&params.around, &params.cursor) {
+        (Some(around), _) => {
+            if around != "me" {
+                return Err(AppError::BadRequest(
+                    "around only supports 'me'".to_string(),
+                ));
             }
+            let radius = params
+                .radius
+                .filter(|radius| *radius > 0 && *radius <= 50)
+                .unwrap_or(5);
+            let slice = leaderboard_use_cases
+                .get_leaderboard_around(&auth.public_key, radius)
+                .await?;
+            (slice, radius * 2 + 1)
+        }
+        (None, Some(cursor)) => {
+            let slice = leaderboard_use_cases
+                .get_leaderboard_by_cursor(Some(decode_cursor(cursor)?), limit)
+                .await?;
+            (slice, limit)
+        }
+        (None, None) => {
+            let (entries, total) = leaderboard_use_cases
+                .get_leaderboard_of_kind(kind, page, limit)
+                .await?;
+            let slice = LeaderboardSlice {
+                entries,
+                total,
+                has_previous: page > 1,
+                has_next: page * limit < total,
+            };
+            (slice, limit)
         }
-        None => calculate_user_page(user_rank, limit),
     };
 
-    let (entries, total) = leaderboard_use_cases.get_leaderboard(page, limit).await?;
+    let first_rank = slice.entries.first().map(|entry| entry.rank);
+    let last_rank = slice.entries.last().map(|entry| entry.rank);
+    let keyset = params.cursor.is_some() || params.around.is_some();
 
-    let leaderboard_response = entries
+    let (page, is_user_on_page) = match (keyset, first_rank, last_rank) {
+        (true, Some(first), Some(last)) => (
+            calculate_user_page(first, limit),
+            user_rank >= first && user_rank <= last,
+        ),
+        (true, _, _) => (page, false),
+        (false, _, _) => (page, is_user_on_page(user_rank, page, limit)),
+    };
+
+    let (next_cursor, previous_cursor) = if kind == LeaderboardKind::Total {
+        (
+            last_rank
+                .filter(|_| slice.has_next)
+                .map(|rank| encode_cursor(LeaderboardCursor::After(rank))),
+            first_rank
+                .filter(|_| slice.has_previous)
+                .map(|rank| encode_cursor(LeaderboardCursor::Before(rank))),
+        )
+    } else {
+        (None, None)
+    };
+
+    let leaderboard_response = slice
+        .entries
         .into_iter()
         .map(LeaderboardEntryResponse::from)
         .collect::<Vec<_>>();
 
-    let is_user_on_page = is_user_on_page(user_rank, page, limit);
-
     Ok((
         StatusCode::OK,
         Json(LeaderboardResponse {
             leaderboard: leaderboard_response,
-            pagination: PaginationResponse { page, limit, total },
+            pagination: PaginationResponse {
+                page,
+                limit,
+                total: slice.total,
+                next_cursor,
+                previous_cursor,
+            },
             user_context: UserContextResponse {
                 rank: user_rank,
                 total_score: user_score,
+                score: user_exact_score.map(decimal_to_json),
                 is_on_current_page: is_user_on_page,
             },
         }),
     ))
 }
 
+#[instrument(skip(headers, leaderboard_config, leaderboard_use_cases))]
+async fn get_public_leaderboard(
+    headers: HeaderMap,
+    Query(params): Query<PublicLeaderboardQueryParams>,
+    State(leaderboard_config): State<Arc<LeaderboardConfig>>,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+) -> AppResult<Response> {
+    let limit = params
+        .limit
+        .filter(|limit| *limit > 0 && *limit <= 100)
+        .unwrap_or(10);
+
+    let leaderboard = leaderboard_use_cases.get_public_leaderboard(limit).await?;
+    let body = serde_json::to_vec(&PublicLeaderboardResponse::from(leaderboard))
+        .map_err(|e| AppError::Internal(e.to_string()))?;
+
+    let digest = Sha256::digest(&body);
+    let etag = format!(
+        "\"{}\"",
+        digest[..16]
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+    );
+    let cache_control = format!(
+        "public, max-age={}",
+        leaderboard_config.public_cache_ttl_seconds.max(0)
+    );
+
+    let not_modified = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag));
+    if not_modified {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(ETAG, etag), (CACHE_CONTROL, cache_control)],
+        )
+            .into_response());
+    }
+
+    Ok((
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, "application/json".to_string()),
+            (ETAG, etag),
+            (CACHE_CONTROL, cache_control),
+        ],
+        body,
+    )
+        .into_response())
+}
+
 #[instrument(skip(leaderboard_use_cases))]
-async fn get_user_leaderboard(
+async fn get_rank_history(
     auth: AuthenticatedUser,
+    Query(params): Query<HistoryQueryParams>,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let to = params.to.unwrap_or_else(Utc::now);
+    let from = params.from.unwrap_or(to - Duration::days(30));
+
+    let history = leaderboard_use_cases
+        .get_rank_history(&auth.public_key, from, to)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(RankHistoryResponse {
+            from,
+            to,
+            history: history
+                .into_iter()
+                .map(RankHistoryPointResponse::from)
+                .collect(),
+        }),
+    ))
+}
+
+#[instrument(skip(leaderboard_use_cases))]
+async fn get_top_climbers(
+    _auth: AuthenticatedUser,
+    Query(params): Query<MoversQueryParams>,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    get_top_movers(MoverDirection::Climbers, params, leaderboard_use_cases).await
+}
+
+#[instrument(skip(leaderboard_use_cases))]
+async fn get_top_fallers(
+    _auth: AuthenticatedUser,
+    Query(params): Query<MoversQueryParams>,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    get_top_movers(MoverDirection::Fallers, params, leaderboard_use_cases).await
+}
+
+async fn get_top_movers(
+    direction: MoverDirection,
+    params: MoversQueryParams,
+    leaderboard_use_cases: Arc<LeaderboardUseCases>,
+) -> AppResult<impl IntoResponse> {
+    let days = params.days.unwrap_or(7);
+    let limit = params
+        .limit
+        .filter(|limit| *limit > 0 && *limit <= 100)
+        .unwrap_or(10);
+
+    let movers = leaderboard_use_cases
+        .get_top_movers(direction, days, limit)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(
+            movers
+                .into_iter()
+                .map(RankMovementResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[instrument(skip(leaderboard_use_cases))]
+async fn get_seasons(
+    _auth: AuthenticatedUser,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let seasons = leaderboard_use_cases.read_seasons().await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(
+            seasons
+                .into_iter()
+                .map(SeasonResponse::from)
+                .collect::<Vec<_>>(),
+        ),
+    ))
+}
+
+#[instrument(skip(leaderboard_use_cases))]
+async fn get_season_leaderboard_list(
+    auth: AuthenticatedUser,
+    Path(season_id): Path<i32>,
+    Query(params): Query<LeaderboardQueryParams>,
     State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
 ) -> AppResult<impl IntoResponse> {
     let user_entry = leaderboard_use_cases
-        .get_user_realtime_leaderboard_entry(&auth.public_key)
+        .get_user_season_entry(season_id, &auth.public_key)
         .await?;
 
     let (user_rank, user_score) = match &user_entry {
@@ -174,11 +591,67 @@ async fn get_user_leaderboard(
         None => (0, 0),
     };
 
+    let (page, limit) = resolve_page_and_limit(&params, user_rank);
+
+    let (entries, total) = leaderboard_use_cases
+        .get_season_leaderboard(season_id, page, limit)
+        .await?;
+
+    let leaderboard_response = entries
+        .into_iter()
+        .map(LeaderboardEntryResponse::from)
+        .collect::<Vec<_>>();
+
     Ok((
         StatusCode::OK,
-        Json(UserLeaderboardResponse {
-            rank: user_rank,
-            total_score: user_score,
+        Json(LeaderboardResponse {
+            leaderboard: leaderboard_response,
+            pagination: PaginationResponse {
+                page,
+                limit,
+                total,
+                next_cursor: None,
+                previous_cursor: None,
+            },
+            user_context: UserContextResponse {
+                rank: user_rank,
+                total_score: user_score,
+                score: None,
+                is_on_current_page: is_user_on_page(user_rank, page, limit),
+            },
         }),
     ))
 }
+
+#[instrument(skip(leaderboard_use_cases))]
+async fn get_user_leaderboard(
+    auth: AuthenticatedUser,
+    State(leaderboard_use_cases): State<Arc<LeaderboardUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let standing = leaderboard_use_cases
+        .get_user_standing(&auth.public_key)
+        .await?;
+
+    let response = match standing {
+        Some(standing) => UserLeaderboardResponse {
+            official_rank: standing.official_rank.unwrap_or(0),
+            official_score: standing.official_score,
+            previous_rank: standing.previous_rank,
+            projected_rank: standing.projected_rank,
+            projected_score: standing.projected_score,
+            rank: standing.projected_rank,
+            total_score: standing.projected_score,
+        },
+        None => UserLeaderboardResponse {
+            official_rank: 0,
+            official_score: 0,
+            previous_rank: None,
+            projected_rank: 0,
+            projected_score: 0,
+            rank: 0,
+            total_score: 0,
+        },
+    };
+
+    Ok((StatusCode::OK, Json(response)))
+}
@@ -1,19 +1,40 @@
+mod admin;
+mod admin_badge;
+mod admin_token;
+mod auth;
 mod badge;
 mod beta_applicant;
 mod cat;
+mod event;
 mod health;
 mod leaderboard;
+mod progression;
 
 use crate::adapters::http::app_state::AppState;
 use axum::Router;
 
-pub fn router() -> Router<AppState> {
+pub fn router(app_state: AppState) -> Router<AppState> {
     Router::new()
-        .nest("/beta-applicants", beta_applicant::private_router())
+        .nest("/admin", admin::private_router(app_state.clone()))
+        .nest("/admin", admin_badge::private_router(app_state.clone()))
+        .nest("/admin", admin_token::private_router(app_state.clone()))
+        .nest("/admin", badge::admin_router(app_state.clone()))
+        .nest("/auth", auth::public_router())
+        .nest(
+            "/beta-applicants",
+            beta_applicant::private_router(app_state.clone()),
+        )
         .nest("/beta-applicants", beta_applicant::public_router())
-        .nest("/badges", badge::private_router())
+        .nest("/badges", badge::private_router(app_state.clone()))
         .nest("/badges", badge::public_router())
         .nest("/cats", cat::public_router())
-        .nest("/leaderboard", leaderboard::private_router())
+        .nest("/events", event::private_router(app_state.clone()))
+        .nest("/internal", badge::internal_router())
+        .nest(
+            "/leaderboard",
+            leaderboard::private_router(app_state.clone()),
+        )
+        .nest("/leaderboard", leaderboard::public_router())
+        .nest("/progressions", progression::private_router(app_state))
         .nest("/k8s", health::router())
 }
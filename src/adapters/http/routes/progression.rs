@@ -0,0 +1,105 @@
+use crate::adapters::http::app_state::AppState;
+use crate::adapters::http::middleware::auth::AuthenticatedUser;
+use crate::adapters::http::middleware::auth_middleware;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::progression_event::{ProgressionEvent, ProgressionHistoryQuery};
+use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::token_amount::serialize_decimal;
+use crate::use_cases::beta_applicant_progression::BetaApplicantProgressionUseCases;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router, middleware};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::instrument;
+
+pub fn private_router(app_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/history", get(read_progression_history))
+        .layer(middleware::from_fn_with_state(app_state, auth_middleware))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgressionHistoryQueryParams {
+    event_type: String,
+    /// Inclusive.
+    from: Option<DateTime<Utc>>,
+    /// Exclusive; pass the oldest `recordedAt` of a page to read the one before it.
+    to: Option<DateTime<Utc>>,
+    limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgressionEventResponse {
+    #[serde(serialize_with = "serialize_decimal")]
+    value: Decimal,
+    source: String,
+    recorded_at: DateTime<Utc>,
+}
+
+impl From<ProgressionEvent> for ProgressionEventResponse {
+    fn from(event: ProgressionEvent) -> Self {
+        Self {
+            value: event.value,
+            source: event.source,
+            recorded_at: event.recorded_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ProgressionHistoryResponse {
+    event_type: String,
+    events: Vec<ProgressionEventResponse>,
+    has_more: bool,
+}
+
+#[instrument(skip(progression_use_cases))]
+async fn read_progression_history(
+    auth: AuthenticatedUser,
+    Query(params): Query<ProgressionHistoryQueryParams>,
+    State(progression_use_cases): State<Arc<BetaApplicantProgressionUseCases>>,
+) -> AppResult<impl IntoResponse> {
+    let event_type = ProgressionEventType::from_name(&params.event_type).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Unknown progression event type '{}'",
+            params.event_type
+        ))
+    })?;
+    let limit = params
+        .limit
+        .filter(|limit| *limit > 0 && *limit <= 500)
+        .unwrap_or(100);
+
+    let history = progression_use_cases
+        .read_progression_history(
+            &auth.public_key,
+            event_type,
+            ProgressionHistoryQuery {
+                from: params.from,
+                to: params.to,
+                limit,
+            },
+        )
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ProgressionHistoryResponse {
+            event_type: event_type.name().to_string(),
+            events: history
+                .events
+                .into_iter()
+                .map(ProgressionEventResponse::from)
+                .collect(),
+            has_more: history.has_more,
+        }),
+    ))
+}
@@ -0,0 +1,280 @@
+use crate::adapters::persistence::PostgresPersistence;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::admin::Admin;
+use crate::entities::admin_audit_entry::{AdminAuditEntry, NewAdminAuditEntry};
+use crate::use_cases::admin::AdminPersistence;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgConnection;
+
+#[derive(sqlx::FromRow, Debug)]
+struct AdminDb {
+    pub id: i32,
+    pub public_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct AdminAuditEntryDb {
+    pub id: i32,
+    pub admin_public_key: String,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub payload: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AdminDb> for Admin {
+    fn from(admin: AdminDb) -> Self {
+        Self {
+            id: admin.id,
+            public_key: admin.public_key,
+            created_at: admin.created_at,
+        }
+    }
+}
+
+impl From<AdminAuditEntryDb> for AdminAuditEntry {
+    fn from(entry: AdminAuditEntryDb) -> Self {
+        Self {
+            id: entry.id,
+            admin_public_key: entry.admin_public_key,
+            action: entry.action,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id,
+            payload: entry.payload,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[async_trait]
+impl AdminPersistence for PostgresPersistence {
+    async fn is_admin(&self, public_key: &str) -> AppResult<bool> {
+        let exists = sqlx::query_scalar!(
+            "SELECT EXISTS (SELECT 1 FROM admins WHERE public_key = $1)",
+            public_key
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(exists.unwrap_or(false))
+    }
+
+    async fn read_admins(&self) -> AppResult<Vec<Admin>> {
+        let admins = sqlx::query_as!(
+            AdminDb,
+            "SELECT id, public_key, created_at FROM admins ORDER BY id"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(admins.into_iter().map(Admin::from).collect())
+    }
+
+    async fn create_admin(&self, public_key: &str, audit: &NewAdminAuditEntry) -> AppResult<Admin> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let admin = sqlx::query_as!(
+            AdminDb,
+            "INSERT INTO admins (public_key) VALUES ($1)
+             ON CONFLICT (public_key) DO UPDATE SET public_key = EXCLUDED.public_key
+             RETURNING id, public_key, created_at",
+            public_key
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(admin.into())
+    }
+
+    async fn delete_admin(&self, public_key: &str, audit: &NewAdminAuditEntry) -> AppResult<bool> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let result = sqlx::query!("DELETE FROM admins WHERE public_key = $1", public_key)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(true)
+    }
+
+    async fn record_audit_entry(&self, entry: &NewAdminAuditEntry) -> AppResult<()> {
+        let mut connection = self.pool.acquire().await.map_err(AppError::from)?;
+        insert_audit_entry(&mut connection, entry).await
+    }
+
+    async fn read_audit_entries(&self, limit: u32, offset: u32) -> AppResult<Vec<AdminAuditEntry>> {
+        let entries = sqlx::query_as!(
+            AdminAuditEntryDb,
+            "SELECT id, admin_public_key, action, entity_type, entity_id, payload, created_at
+             FROM admin_audit_log
+             ORDER BY created_at DESC, id DESC
+             LIMIT $1 OFFSET $2",
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(entries.into_iter().map(AdminAuditEntry::from).collect())
+    }
+}
+
+/// Writes the audit entry on the caller's connection. Mutations pass their transaction, so that
+/// the entry is only kept if the mutation is.
+pub(crate) async fn insert_audit_entry(
+    connection: &mut PgConnection,
+    entry: &NewAdminAuditEntry,
+) -> AppResult<()> {
+    sqlx::query!(
+        "INSERT INTO admin_audit_log (admin_public_key, action, entity_type, entity_id, payload)
+         VALUES ($1, $2, $3, $4, $5)",
+        entry.admin_public_key,
+        entry.action,
+        entry.entity_type,
+        entry.entity_id,
+        entry.payload
+    )
+    .execute(connection)
+    .await
+    .map_err(AppError::from)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::app_error::AppError;
+    use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+    use crate::entities::badge_group::NewBadgeGroup;
+    use crate::use_cases::admin::{AdminPersistence, AdminUseCases};
+    use crate::use_cases::badge_group::BadgeGroupPersistence;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn audit(action: &str, entity_type: &str, entity_id: Option<String>) -> NewAdminAuditEntry {
+        NewAdminAuditEntry {
+            admin_public_key: "admin".to_string(),
+            action: action.to_string(),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            payload: json!({}),
+        }
+    }
+
+    #[tokio::test]
+    async fn mutations_write_their_audit_entry() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = db.persistence();
+
+        let badge_group = persistence
+            .insert_badge_group(
+                &NewBadgeGroup {
+                    title: "Title".to_string(),
+                    description: "Description".to_string(),
+                    sort_order: 0,
+                },
+                &audit("create", "badge_group", None),
+            )
+            .await
+            .unwrap();
+        persistence
+            .create_admin("someone", &audit("create", "admin", None))
+            .await
+            .unwrap();
+
+        let entries = persistence.read_audit_entries(10, 0).await.unwrap();
+        let entries: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.entity_type.as_str(), entry.entity_id.clone()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("admin", None),
+                ("badge_group", Some(badge_group.id.to_string())),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_mutations_leave_no_audit_entry() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = db.persistence();
+
+        // The seeded groups still contain badges
+        let group_id = persistence.read_all_badge_groups().await.unwrap()[0].id;
+        assert!(
+            persistence
+                .delete_badge_group(group_id, &audit("delete", "badge_group", None))
+                .await
+                .is_err()
+        );
+        assert!(
+            !persistence
+                .delete_admin("nobody", &audit("delete", "admin", None))
+                .await
+                .unwrap()
+        );
+
+        assert!(
+            persistence
+                .read_audit_entries(10, 0)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn admins_must_be_solana_addresses() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let admin_use_cases = AdminUseCases::new(Arc::new(db.persistence()), Vec::new());
+
+        for public_key in [
+            "someone",
+            "",
+            "0OIl",
+            &bs58::encode([1u8; 31]).into_string(),
+        ] {
+            assert!(
+                matches!(
+                    admin_use_cases.create_admin("admin", public_key).await,
+                    Err(AppError::BadRequest(_))
+                ),
+                "{:?}",
+                public_key
+            );
+        }
+        let public_key = bs58::encode([1u8; 32]).into_string();
+        admin_use_cases
+            .create_admin("admin", &public_key)
+            .await
+            .unwrap();
+        assert!(admin_use_cases.is_admin(&public_key).await.unwrap());
+    }
+}
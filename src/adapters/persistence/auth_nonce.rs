@@ -0,0 +1,145 @@
+use crate::adapters::persistence::PostgresPersistence;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::auth_nonce::{AuthNonce, NonceClaim};
+use crate::use_cases::auth::AuthNoncePersistence;
+use async_trait::async_trait;
+
+#[async_trait]
+impl AuthNoncePersistence for PostgresPersistence {
+    async fn create_nonce(&self, nonce: &AuthNonce, max_outstanding: i64) -> AppResult<NonceClaim> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        // Serializes requests for the same public key, so concurrent ones cannot all pass the
+        // count below
+        sqlx::query!(
+            "SELECT 1 AS locked FROM pg_advisory_xact_lock(hashtext('auth_nonce:' || $1))",
+            nonce.public_key
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let outstanding = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!", MIN(expires_at) AS oldest_expires_at
+               FROM auth_nonces
+               WHERE public_key = $1 AND consumed_at IS NULL AND expires_at > NOW()"#,
+            nonce.public_key
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        if outstanding.count >= max_outstanding {
+            return Ok(NonceClaim::TooMany {
+                retry_at: outstanding.oldest_expires_at.unwrap_or(nonce.expires_at),
+            });
+        }
+
+        sqlx::query!(
+            "INSERT INTO auth_nonces (nonce, public_key, expires_at, created_at)
+             VALUES ($1, $2, $3, $4)",
+            nonce.nonce,
+            nonce.public_key,
+            nonce.expires_at,
+            nonce.created_at
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(NonceClaim::Claimed)
+    }
+
+    async fn consume_nonce(&self, public_key: &str, nonce: &str) -> AppResult<bool> {
+        let consumed = sqlx::query_scalar!(
+            "UPDATE auth_nonces SET consumed_at = NOW()
+             WHERE nonce = $1
+               AND public_key = $2
+               AND consumed_at IS NULL
+               AND expires_at > NOW()
+             RETURNING nonce",
+            nonce,
+            public_key
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(consumed.is_some())
+    }
+
+    async fn purge_expired_nonces(&self) -> AppResult<u64> {
+        let purged =
+            sqlx::query!("DELETE FROM auth_nonces WHERE expires_at < NOW() - INTERVAL '1 day'")
+                .execute(&self.pool)
+                .await
+                .map_err(AppError::from)?;
+
+        Ok(purged.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::app_error::AppError;
+    use crate::entities::auth_nonce::{AuthNonce, NonceClaim};
+    use crate::use_cases::auth::{AuthLifetimes, AuthNoncePersistence, AuthUseCases};
+    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn wallets_cannot_hoard_nonces_and_expired_ones_are_purged() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let auth_use_cases = AuthUseCases::new(
+            persistence.clone(),
+            persistence.clone(),
+            AuthLifetimes {
+                nonce: Duration::minutes(5),
+                access_token: Duration::minutes(15),
+                refresh_token: Duration::days(30),
+            },
+        );
+        let public_key = "11111111111111111111111111111112";
+
+        let mut issued = Vec::new();
+        for _ in 0..5 {
+            issued.push(auth_use_cases.issue_nonce(public_key).await.unwrap());
+        }
+        match auth_use_cases.issue_nonce(public_key).await {
+            Err(AppError::RateLimited(retry_after)) => {
+                assert!((0..=300).contains(&retry_after), "{}", retry_after)
+            }
+            other => panic!("expected a rate limit, got {:?}", other),
+        }
+        assert!(
+            auth_use_cases
+                .issue_nonce("So11111111111111111111111111111111111111112")
+                .await
+                .is_ok()
+        );
+
+        assert!(
+            auth_use_cases
+                .consume_nonce(public_key, &issued[0].nonce)
+                .await
+                .unwrap()
+        );
+        assert!(auth_use_cases.issue_nonce(public_key).await.is_ok());
+
+        let long_expired = AuthNonce {
+            nonce: "long-expired".to_string(),
+            public_key: public_key.to_string(),
+            expires_at: Utc::now() - Duration::days(2),
+            created_at: Utc::now() - Duration::days(2),
+        };
+        assert_eq!(
+            persistence.create_nonce(&long_expired, 10).await.unwrap(),
+            NonceClaim::Claimed
+        );
+        assert_eq!(auth_use_cases.purge_expired_nonces().await.unwrap(), 1);
+    }
+}
@@ -0,0 +1,411 @@
+use crate::adapters::persistence::PostgresPersistence;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::auth_session::{AuthSession, AuthSessionPurge};
+use crate::use_cases::auth::AuthSessionPersistence;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[derive(sqlx::FromRow, Debug)]
+struct AuthSessionDb {
+    pub id: i32,
+    pub public_key: String,
+    pub access_expires_at: DateTime<Utc>,
+    pub refresh_expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AuthSessionDb> for AuthSession {
+    fn from(session: AuthSessionDb) -> Self {
+        Self {
+            id: session.id,
+            public_key: session.public_key,
+            access_expires_at: session.access_expires_at,
+            refresh_expires_at: session.refresh_expires_at,
+            created_at: session.created_at,
+        }
+    }
+}
+
+#[async_trait]
+impl AuthSessionPersistence for PostgresPersistence {
+    async fn create_session(
+        &self,
+        public_key: &str,
+        access_token_hash: &str,
+        refresh_token_hash: &str,
+        access_expires_at: DateTime<Utc>,
+        refresh_expires_at: DateTime<Utc>,
+    ) -> AppResult<AuthSession> {
+        let session = sqlx::query_as!(
+            AuthSessionDb,
+            "INSERT INTO auth_sessions
+             (public_key, access_token_hash, refresh_token_hash, access_expires_at, refresh_expires_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id, public_key, access_expires_at, refresh_expires_at, created_at",
+            public_key,
+            access_token_hash,
+            refresh_token_hash,
+            access_expires_at,
+            refresh_expires_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(session.into())
+    }
+
+    async fn read_active_session_by_access_token(
+        &self,
+        access_token_hash: &str,
+    ) -> AppResult<Option<AuthSession>> {
+        let session = sqlx::query_as!(
+            AuthSessionDb,
+            "SELECT id, public_key, access_expires_at, refresh_expires_at, created_at
+             FROM auth_sessions
+             WHERE access_token_hash = $1
+               AND revoked_at IS NULL
+               AND access_expires_at > NOW()",
+            access_token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(session.map(AuthSession::from))
+    }
+
+    async fn rotate_session(
+        &self,
+        refresh_token_hash: &str,
+        access_token_hash: &str,
+        new_refresh_token_hash: &str,
+        access_expires_at: DateTime<Utc>,
+        refresh_expires_at: DateTime<Utc>,
+    ) -> AppResult<Option<AuthSession>> {
+        let session = sqlx::query_as!(
+            AuthSessionDb,
+            r#"WITH rotated AS (
+                 UPDATE auth_sessions
+                 SET access_token_hash = $2,
+                     refresh_token_hash = $3,
+                     access_expires_at = $4,
+                     refresh_expires_at = $5,
+                     updated_at = NOW()
+                 WHERE refresh_token_hash = $1
+                   AND revoked_at IS NULL
+                   AND refresh_expires_at > NOW()
+                 RETURNING id, public_key, access_expires_at, refresh_expires_at, created_at
+             ),
+             recorded AS (
+                 INSERT INTO auth_session_rotated_tokens (refresh_token_hash, session_id)
+                 SELECT $1, id FROM rotated
+             )
+             SELECT id AS "id!", public_key AS "public_key!",
+                    access_expires_at AS "access_expires_at!",
+                    refresh_expires_at AS "refresh_expires_at!", created_at AS "created_at!"
+             FROM rotated"#,
+            refresh_token_hash,
+            access_token_hash,
+            new_refresh_token_hash,
+            access_expires_at,
+            refresh_expires_at
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(session.map(AuthSession::from))
+    }
+
+    async fn revoke_session_by_rotated_refresh_token(
+        &self,
+        refresh_token_hash: &str,
+    ) -> AppResult<bool> {
+        let result = sqlx::query!(
+            "UPDATE auth_sessions SET revoked_at = NOW(), updated_at = NOW()
+             WHERE revoked_at IS NULL
+               AND id = (
+                   SELECT session_id FROM auth_session_rotated_tokens
+                   WHERE refresh_token_hash = $1
+               )",
+            refresh_token_hash
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_session_by_access_token(&self, access_token_hash: &str) -> AppResult<bool> {
+        let result = sqlx::query!(
+            "UPDATE auth_sessions SET revoked_at = NOW(), updated_at = NOW()
+             WHERE access_token_hash = $1 AND revoked_at IS NULL",
+            access_token_hash
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn purge_sessions(&self, rotated_before: DateTime<Utc>) -> AppResult<AuthSessionPurge> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let rotated_tokens = sqlx::query!(
+            "DELETE FROM auth_session_rotated_tokens
+             WHERE rotated_at < $1
+                OR session_id IN (
+                    SELECT id FROM auth_sessions
+                    WHERE revoked_at IS NOT NULL OR refresh_expires_at < NOW()
+                )",
+            rotated_before
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        let sessions = sqlx::query!(
+            "DELETE FROM auth_sessions WHERE revoked_at IS NOT NULL OR refresh_expires_at < NOW()"
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(AuthSessionPurge {
+            sessions: sessions.rows_affected(),
+            rotated_tokens: rotated_tokens.rows_affected(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::persistence::in_memory::InMemoryPersistence;
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::use_cases::auth::{AuthLifetimes, AuthUseCases};
+    use chrono::Duration;
+    use std::sync::Arc;
+
+    const PUBLIC_KEY: &str = "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5";
+
+    fn auth_use_cases(db: &TestDatabase, refresh_token: Duration) -> AuthUseCases {
+        AuthUseCases::new(
+            Arc::new(InMemoryPersistence::new()),
+            Arc::new(db.persistence()),
+            AuthLifetimes {
+                nonce: Duration::minutes(5),
+                access_token: Duration::minutes(15),
+                refresh_token,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn access_tokens_authenticate_until_revoked() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let auth = auth_use_cases(&db, Duration::days(30));
+
+        let session = auth.create_session(PUBLIC_KEY).await.unwrap();
+
+        assert_eq!(
+            auth.authenticate_access_token(&session.access_token)
+                .await
+                .unwrap()
+                .map(|session| session.public_key)
+                .as_deref(),
+            Some(PUBLIC_KEY)
+        );
+        assert!(auth.revoke_session(&session.access_token).await.unwrap());
+        assert_eq!(
+            auth.authenticate_access_token(&session.access_token)
+                .await
+                .unwrap()
+                .map(|session| session.public_key),
+            None
+        );
+        assert!(!auth.revoke_session(&session.access_token).await.unwrap());
+        assert!(
+            auth.refresh_session(&session.refresh_token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn refreshing_replaces_both_tokens() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let auth = auth_use_cases(&db, Duration::days(30));
+        let session = auth.create_session(PUBLIC_KEY).await.unwrap();
+
+        let refreshed = auth
+            .refresh_session(&session.refresh_token)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_ne!(refreshed.refresh_token, session.refresh_token);
+        assert_eq!(
+            auth.authenticate_access_token(&session.access_token)
+                .await
+                .unwrap()
+                .map(|session| session.public_key),
+            None
+        );
+        assert_eq!(
+            auth.authenticate_access_token(&refreshed.access_token)
+                .await
+                .unwrap()
+                .map(|session| session.public_key)
+                .as_deref(),
+            Some(PUBLIC_KEY)
+        );
+        assert!(
+            auth.refresh_session(&refreshed.refresh_token)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn reusing_a_rotated_refresh_token_revokes_the_session() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let auth = auth_use_cases(&db, Duration::days(30));
+        let session = auth.create_session(PUBLIC_KEY).await.unwrap();
+        let other = auth.create_session(PUBLIC_KEY).await.unwrap();
+        let refreshed = auth
+            .refresh_session(&session.refresh_token)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let replayed = auth.refresh_session(&session.refresh_token).await.unwrap();
+
+        assert!(replayed.is_none());
+        assert_eq!(
+            auth.authenticate_access_token(&refreshed.access_token)
+                .await
+                .unwrap()
+                .map(|session| session.public_key),
+            None
+        );
+        assert!(
+            auth.refresh_session(&refreshed.refresh_token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            auth.authenticate_access_token(&other.access_token)
+                .await
+                .unwrap()
+                .map(|session| session.public_key)
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn expired_refresh_tokens_are_rejected() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let auth = auth_use_cases(&db, Duration::seconds(-1));
+        let session = auth.create_session(PUBLIC_KEY).await.unwrap();
+
+        assert!(
+            auth.refresh_session(&session.refresh_token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            auth.refresh_session("unknown-token")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn purging_drops_ended_sessions_and_old_rotated_tokens() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let auth = auth_use_cases(&db, Duration::days(30));
+        let active = auth.create_session(PUBLIC_KEY).await.unwrap();
+        let refreshed = auth
+            .refresh_session(&active.refresh_token)
+            .await
+            .unwrap()
+            .unwrap();
+        let revoked = auth.create_session(PUBLIC_KEY).await.unwrap();
+        auth.revoke_session(&revoked.access_token).await.unwrap();
+        auth_use_cases(&db, Duration::seconds(-1))
+            .create_session(PUBLIC_KEY)
+            .await
+            .unwrap();
+
+        let purge = auth.purge_ended_sessions().await.unwrap();
+
+        assert_eq!(purge.sessions, 2);
+        assert_eq!(purge.rotated_tokens, 0);
+        assert!(
+            auth.authenticate_access_token(&refreshed.access_token)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        // Within the refresh lifetime the rotated token still gives the replay away
+        assert!(
+            auth.refresh_session(&active.refresh_token)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(
+            auth.authenticate_access_token(&refreshed.access_token)
+                .await
+                .unwrap()
+                .map(|session| session.public_key),
+            None
+        );
+        let purge = auth.purge_ended_sessions().await.unwrap();
+        assert_eq!(purge.sessions, 1);
+        assert_eq!(purge.rotated_tokens, 1);
+    }
+
+    #[tokio::test]
+    async fn rotated_tokens_are_kept_for_one_refresh_lifetime() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let auth = auth_use_cases(&db, Duration::days(30));
+        let session = auth.create_session(PUBLIC_KEY).await.unwrap();
+        auth.refresh_session(&session.refresh_token)
+            .await
+            .unwrap()
+            .unwrap();
+        sqlx::query(
+            "UPDATE auth_session_rotated_tokens SET rotated_at = NOW() - INTERVAL '31 days'",
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        let purge = auth.purge_ended_sessions().await.unwrap();
+
+        assert_eq!(purge.sessions, 0);
+        assert_eq!(purge.rotated_tokens, 1);
+    }
+}
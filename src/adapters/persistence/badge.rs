@@ -1,14 +1,20 @@
 use crate::adapters::persistence::PostgresPersistence;
+use crate::adapters::persistence::admin::insert_audit_entry;
 use crate::adapters::persistence::beta_applicant_badge::BetaApplicantBadgeDb;
 use crate::app_error::{AppError, AppResult};
-use crate::entities::badge::Badge;
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::entities::badge::{Badge, BadgeDefinition, BadgeUpdate, NewBadge};
 use crate::entities::badge_requirement::BadgeRequirement;
+use crate::entities::badge_rule::{BadgeRule, ProgressionSnapshot, SignalProgress};
 use crate::entities::progression_event_type::ProgressionEventType;
 use crate::use_cases::badge::BadgePersistence;
 use crate::use_cases::beta_applicant::BetaApplicantPersistence;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use rust_decimal::Decimal;
+use sqlx::PgConnection;
+use sqlx::types::Json;
+use std::collections::{HashMap, HashSet};
 
 #[derive(sqlx::FromRow, Debug)]
 pub struct BadgeDb {
@@ -19,7 +25,109 @@ pub struct BadgeDb {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(sqlx::FromRow, Debug)]
+struct BadgeDefinitionDb {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+    pub score: i32,
+    pub badge_group_id: i32,
+    pub sort_order: i32,
+    pub rule: Option<Json<BadgeRule>>,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+struct ProgressionRow {
+    beta_applicant_id: i32,
+    progression_event_type_id: i32,
+    progress_count: Decimal,
+    held_since: Option<DateTime<Utc>>,
+}
+
+impl From<BadgeDefinitionDb> for BadgeDefinition {
+    fn from(badge: BadgeDefinitionDb) -> Self {
+        Self {
+            id: badge.id,
+            title: badge.title,
+            description: badge.description,
+            score: badge.score,
+            badge_group_id: badge.badge_group_id,
+            sort_order: badge.sort_order,
+            rule: badge.rule.map(|rule| rule.0),
+            archived_at: badge.archived_at,
+            created_at: badge.created_at,
+        }
+    }
+}
+
 impl PostgresPersistence {
+    async fn read_badge_definition(&self, id: i32) -> AppResult<BadgeDefinition> {
+        let badge = sqlx::query_as!(
+            BadgeDefinitionDb,
+            r#"SELECT b.id, b.title, b.description, b.score, bgc.badge_group_id, bgc.sort_order,
+                    b.rule AS "rule: Json<BadgeRule>", b.archived_at, b.created_at
+             FROM badges b
+             INNER JOIN badge_group_conjunctions bgc ON b.id = bgc.badge_id
+             WHERE b.id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::NotFound(format!("Badge {} not found", id)))?;
+
+        Ok(badge.into())
+    }
+
+    fn convert_to_snapshots(
+        &self,
+        applicants: Vec<(i32, String)>,
+        progressions: Vec<ProgressionRow>,
+        badges_earned: Vec<(i32, i32)>,
+    ) -> Vec<ProgressionSnapshot> {
+        let now = Utc::now();
+        let mut snapshots: HashMap<i32, ProgressionSnapshot> = applicants
+            .into_iter()
+            .map(|(beta_applicant_id, public_key)| {
+                (
+                    beta_applicant_id,
+                    ProgressionSnapshot {
+                        beta_applicant_id,
+                        public_key,
+                        signals: HashMap::new(),
+                        badge_ids: HashSet::new(),
+                        now,
+                    },
+                )
+            })
+            .collect();
+
+        for row in progressions {
+            if let (Some(snapshot), Some(event_type)) = (
+                snapshots.get_mut(&row.beta_applicant_id),
+                ProgressionEventType::from_id(row.progression_event_type_id),
+            ) {
+                snapshot.signals.insert(
+                    event_type,
+                    SignalProgress {
+                        count: row.progress_count,
+                        held_since: row.held_since,
+                    },
+                );
+            }
+        }
+
+        for (beta_applicant_id, badge_id) in badges_earned {
+            if let Some(snapshot) = snapshots.get_mut(&beta_applicant_id) {
+                snapshot.badge_ids.insert(badge_id);
+            }
+        }
+
+        snapshots.into_values().collect()
+    }
+
     fn convert_to_badges(
         &self,
         badges: Vec<BadgeDb>,
@@ -39,7 +147,7 @@ impl PostgresPersistence {
                 title: badge.title,
                 description: badge.description,
                 score: badge.score,
-                is_unlocked: earned_map.get(&badge.id).is_some(),
+                is_unlocked: earned_map.contains_key(&badge.id),
                 unlocked_at: earned_map.get(&badge.id).map(|b| b.created_at),
                 created_at: badge.created_at,
                 badge_group_id: *group_map.get(&badge.id).unwrap_or(&0),
@@ -57,9 +165,11 @@ impl BadgePersistence for PostgresPersistence {
 
         let badges = sqlx::query_as!(
             BadgeDb,
-            "SELECT b.* FROM badges b 
-             INNER JOIN badge_group_conjunctions bgc ON b.id = bgc.badge_id 
-             ORDER BY bgc.badge_group_id, bgc.sort_order"
+            "SELECT b.id, b.title, b.description, b.score, b.created_at FROM badges b
+             INNER JOIN badge_group_conjunctions bgc ON b.id = bgc.badge_id
+             INNER JOIN badge_groups bg ON bg.id = bgc.badge_group_id
+             WHERE b.archived_at IS NULL AND bg.archived_at IS NULL
+             ORDER BY bg.sort_order, bgc.badge_group_id, bgc.sort_order"
         )
         .fetch_all(&self.pool)
         .await
@@ -88,9 +198,9 @@ impl BadgePersistence for PostgresPersistence {
 
     async fn read_badge_requirements(&self) -> AppResult<Vec<BadgeRequirement>> {
         let requirements = sqlx::query!(
-            "SELECT bc.badge_id, pet.event_type, bc.operation, bc.required_count
-             FROM badge_conditions bc
-             INNER JOIN progression_event_types pet ON bc.progression_event_type_id = pet.id"
+            r#"SELECT id, rule AS "rule!: Json<BadgeRule>" FROM badges
+               WHERE rule IS NOT NULL AND archived_at IS NULL
+               ORDER BY id"#
         )
         .fetch_all(&self.pool)
         .await
@@ -99,70 +209,638 @@ impl BadgePersistence for PostgresPersistence {
         let result = requirements
             .into_iter()
             .map(|row| BadgeRequirement {
-                badge_id: row.badge_id,
-                progression_event_type: row.event_type,
-                operation: row.operation,
-                required_count: row.required_count,
+                badge_id: row.id,
+                rule: row.rule.0,
             })
             .collect();
 
         Ok(result)
     }
 
-    async fn create_badge(&self, public_key: &str, badge_id: i32, value: i32) -> AppResult<()> {
-        let applicant_id = self.read_beta_applicant_by_public_key(public_key).await?.id;
+    async fn read_progression_snapshot(&self, public_key: &str) -> AppResult<ProgressionSnapshot> {
+        let applicant = self.read_beta_applicant_by_public_key(public_key).await?;
+        let applicant_id = applicant.id;
+
+        let progressions = sqlx::query_as!(
+            ProgressionRow,
+            "SELECT beta_applicant_id, progression_event_type_id, progress_count, held_since
+             FROM beta_applicant_progressions
+             WHERE beta_applicant_id = $1",
+            applicant_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        let badges_earned = sqlx::query!(
+            "SELECT beta_applicant_id, badge_id FROM beta_applicant_badges
+             WHERE beta_applicant_id = $1",
+            applicant_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?
+        .into_iter()
+        .map(|row| (row.beta_applicant_id, row.badge_id))
+        .collect();
+
+        self.convert_to_snapshots(
+            vec![(applicant_id, applicant.public_key)],
+            progressions,
+            badges_earned,
+        )
+        .pop()
+        .ok_or_else(|| AppError::Internal("Progression snapshot missing".to_string()))
+    }
+
+    async fn read_progression_snapshots(&self) -> AppResult<Vec<ProgressionSnapshot>> {
+        let applicants = sqlx::query!("SELECT id, public_key FROM beta_applicants ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?
+            .into_iter()
+            .map(|row| (row.id, row.public_key))
+            .collect();
+
+        let progressions = sqlx::query_as!(
+            ProgressionRow,
+            "SELECT beta_applicant_id, progression_event_type_id, progress_count, held_since
+             FROM beta_applicant_progressions"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        let badges_earned =
+            sqlx::query!("SELECT beta_applicant_id, badge_id FROM beta_applicant_badges")
+                .fetch_all(&self.pool)
+                .await
+                .map_err(AppError::from)?
+                .into_iter()
+                .map(|row| (row.beta_applicant_id, row.badge_id))
+                .collect();
+
+        Ok(self.convert_to_snapshots(applicants, progressions, badges_earned))
+    }
+
+    async fn award_badges(&self, beta_applicant_id: i32, badge_ids: &[i32]) -> AppResult<Vec<i32>> {
+        let awarded = sqlx::query_scalar!(
+            "INSERT INTO beta_applicant_badges (beta_applicant_id, badge_id)
+             SELECT $1, UNNEST($2::INTEGER[])
+             ON CONFLICT (beta_applicant_id, badge_id) DO NOTHING
+             RETURNING badge_id",
+            beta_applicant_id,
+            badge_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(awarded)
+    }
+
+    async fn read_badge_definitions(&self) -> AppResult<Vec<BadgeDefinition>> {
+        let badges = sqlx::query_as!(
+            BadgeDefinitionDb,
+            r#"SELECT b.id, b.title, b.description, b.score, bgc.badge_group_id, bgc.sort_order,
+                    b.rule AS "rule: Json<BadgeRule>", b.archived_at, b.created_at
+             FROM badges b
+             INNER JOIN badge_group_conjunctions bgc ON b.id = bgc.badge_id
+             ORDER BY bgc.badge_group_id, bgc.sort_order, b.id"#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(badges.into_iter().map(BadgeDefinition::from).collect())
+    }
+
+    async fn read_signals(&self) -> AppResult<Vec<ProgressionEventType>> {
+        let ids = sqlx::query_scalar!("SELECT id FROM progression_event_types ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(ids
+            .into_iter()
+            .filter_map(ProgressionEventType::from_id)
+            .collect())
+    }
+
+    async fn insert_badge(
+        &self,
+        badge: &NewBadge,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        lock_required_badges(&mut tx, badge.rule.as_ref()).await?;
+
+        let id = sqlx::query_scalar!(
+            "INSERT INTO badges (title, description, score, rule)
+             VALUES ($1, $2, $3, $4)
+             RETURNING id",
+            badge.title,
+            badge.description,
+            badge.score,
+            badge.rule.as_ref().map(Json) as Option<Json<&BadgeRule>>
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
 
         sqlx::query!(
-            r#"
-            INSERT INTO beta_applicant_badges (beta_applicant_id, badge_id)
-            SELECT $1, bc.badge_id
-            FROM badge_conditions bc
-            WHERE bc.badge_id = $2
-              AND (
-                (bc.operation = 'eq' AND $3 = bc.required_count) OR
-                (bc.operation = 'gte' AND $3 >= bc.required_count)
-              )
-            ON CONFLICT (beta_applicant_id, badge_id) DO NOTHING
-            "#,
-            applicant_id,
-            badge_id,
-            value
-        )
-        .execute(&self.pool)
+            "INSERT INTO badge_group_conjunctions (badge_id, badge_group_id, sort_order)
+             VALUES ($1, $2, $3)",
+            id,
+            badge.badge_group_id,
+            badge.sort_order
+        )
+        .execute(&mut *tx)
         .await
         .map_err(AppError::from)?;
 
+        insert_audit_entry(&mut tx, &audit.with_entity_id(id)).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        self.read_badge_definition(id).await
+    }
+
+    async fn update_badge(
+        &self,
+        id: i32,
+        update: &BadgeUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let result = sqlx::query!(
+            "UPDATE badges
+             SET title = COALESCE($2, title),
+                 description = COALESCE($3, description),
+                 score = COALESCE($4, score)
+             WHERE id = $1",
+            id,
+            update.title,
+            update.description,
+            update.score
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Badge {} not found", id)));
+        }
+
+        sqlx::query!(
+            "UPDATE badge_group_conjunctions
+             SET badge_group_id = COALESCE($2, badge_group_id),
+                 sort_order = COALESCE($3, sort_order)
+             WHERE badge_id = $1",
+            id,
+            update.badge_group_id,
+            update.sort_order
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        self.read_badge_definition(id).await
+    }
+
+    async fn update_badge_rule(
+        &self,
+        id: i32,
+        rule: Option<&BadgeRule>,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        lock_required_badges(&mut tx, rule).await?;
+
+        let result = sqlx::query!(
+            "UPDATE badges SET rule = $2 WHERE id = $1",
+            id,
+            rule.map(Json) as Option<Json<&BadgeRule>>
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Badge {} not found", id)));
+        }
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        self.read_badge_definition(id).await
+    }
+
+    async fn archive_badge(
+        &self,
+        id: i32,
+        archived: bool,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let result = sqlx::query!(
+            "UPDATE badges
+             SET archived_at = CASE WHEN $2 THEN COALESCE(archived_at, NOW()) ELSE NULL END
+             WHERE id = $1",
+            id,
+            archived
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Badge {} not found", id)));
+        }
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        self.read_badge_definition(id).await
+    }
+
+    async fn delete_badge(&self, id: i32, audit: &NewAdminAuditEntry) -> AppResult<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        // Rule writers share-lock the badges they require, so once this lock is held no rule
+        // can start requiring the badge, and the rules read below are final
+        let locked = sqlx::query_scalar!("SELECT id FROM badges WHERE id = $1 FOR UPDATE", id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        if locked.is_none() {
+            return Err(AppError::NotFound(format!("Badge {} not found", id)));
+        }
+
+        let rules = sqlx::query!(
+            r#"SELECT id, rule AS "rule!: Json<BadgeRule>"
+             FROM badges
+             WHERE rule IS NOT NULL AND id <> $1
+             ORDER BY id"#,
+            id
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        if let Some(dependent) = rules
+            .into_iter()
+            .find(|row| row.rule.referenced_badge_ids().contains(&id))
+        {
+            return Err(AppError::BadRequest(format!(
+                "Badge {} is required by the rule of badge {}",
+                id, dependent.id
+            )));
+        }
+
+        sqlx::query!("DELETE FROM badges WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
         Ok(())
     }
 
-    async fn award_badge_if_eligible(
+    async fn reorder_badges(
         &self,
-        public_key: &str,
-        event_type: ProgressionEventType,
-        progress_count: i32,
+        badge_group_id: i32,
+        ordered_ids: &[i32],
+        audit: &NewAdminAuditEntry,
     ) -> AppResult<()> {
-        let applicant_id = self.read_beta_applicant_by_public_key(public_key).await?.id;
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
 
         sqlx::query!(
-            r#"
-            INSERT INTO beta_applicant_badges (beta_applicant_id, badge_id)
-            SELECT $1, bc.badge_id
-            FROM badge_conditions bc
-            WHERE bc.progression_event_type_id = $2
-              AND (
-                (bc.operation = 'eq' AND $3 = bc.required_count) OR
-                (bc.operation = 'gte' AND $3 >= bc.required_count)
-              )
-            ON CONFLICT (beta_applicant_id, badge_id) DO NOTHING
-            "#,
-            applicant_id,
-            event_type.id(),
-            progress_count
-        )
-        .execute(&self.pool)
+            "UPDATE badge_group_conjunctions bgc
+             SET sort_order = ordering.position * 10
+             FROM UNNEST($2::INTEGER[]) WITH ORDINALITY AS ordering(badge_id, position)
+             WHERE bgc.badge_id = ordering.badge_id AND bgc.badge_group_id = $1",
+            badge_group_id,
+            ordered_ids
+        )
+        .execute(&mut *tx)
         .await
         .map_err(AppError::from)?;
 
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
         Ok(())
     }
 }
+
+/// Share-locks the badges a rule requires until the transaction ends, so they cannot be
+/// deleted while the rule is written.
+async fn lock_required_badges(
+    connection: &mut PgConnection,
+    rule: Option<&BadgeRule>,
+) -> AppResult<()> {
+    let required = rule
+        .map(BadgeRule::referenced_badge_ids)
+        .unwrap_or_default();
+    if required.is_empty() {
+        return Ok(());
+    }
+
+    let locked: HashSet<i32> = sqlx::query_scalar!(
+        "SELECT id FROM badges WHERE id = ANY($1) ORDER BY id FOR SHARE",
+        &required
+    )
+    .fetch_all(&mut *connection)
+    .await
+    .map_err(AppError::from)?
+    .into_iter()
+    .collect();
+    match required.into_iter().find(|id| !locked.contains(id)) {
+        Some(unknown) => Err(AppError::BadRequest(format!(
+            "Rule references unknown badge {}",
+            unknown
+        ))),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::app_error::AppError;
+    use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+    use crate::entities::badge::NewBadge;
+    use crate::entities::badge_rule::BadgeRule;
+    use crate::entities::badge_rule::{ProgressionSnapshot, SignalProgress};
+    use crate::entities::progression_event_type::ProgressionEventType;
+    use crate::use_cases::badge::{BadgePersistence, BadgeUseCases};
+    use crate::use_cases::badge_group::BadgeGroupPersistence;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use sqlx::types::Json;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+
+    fn audit() -> NewAdminAuditEntry {
+        NewAdminAuditEntry {
+            admin_public_key: "admin".to_string(),
+            action: "test".to_string(),
+            entity_type: "badge".to_string(),
+            entity_id: None,
+            payload: json!({}),
+        }
+    }
+
+    #[tokio::test]
+    async fn badges_required_by_another_rule_cannot_be_deleted() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let badge_use_cases = BadgeUseCases::new(persistence.clone(), persistence.clone());
+        let badge_group_id = persistence.read_all_badge_groups().await.unwrap()[0].id;
+        let new_badge = |title: &str, rule: Option<BadgeRule>| NewBadge {
+            title: title.to_string(),
+            description: "Description".to_string(),
+            score: 10,
+            badge_group_id,
+            sort_order: 0,
+            rule,
+        };
+
+        let required = badge_use_cases
+            .create_definition(new_badge("Required", None), &audit())
+            .await
+            .unwrap();
+        let dependent = badge_use_cases
+            .create_definition(
+                new_badge(
+                    "Dependent",
+                    Some(BadgeRule::HasBadge {
+                        badge_id: required.id,
+                    }),
+                ),
+                &audit(),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            badge_use_cases.delete(required.id, &audit()).await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        badge_use_cases
+            .delete(dependent.id, &audit())
+            .await
+            .unwrap();
+        badge_use_cases.delete(required.id, &audit()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn deleting_waits_for_rules_being_written_that_require_the_badge() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let badge_use_cases = BadgeUseCases::new(persistence.clone(), persistence.clone());
+        let badge_group_id = persistence.read_all_badge_groups().await.unwrap()[0].id;
+        let new_badge = |title: &str| NewBadge {
+            title: title.to_string(),
+            description: "Description".to_string(),
+            score: 10,
+            badge_group_id,
+            sort_order: 0,
+            rule: None,
+        };
+        let required = badge_use_cases
+            .create_definition(new_badge("Required"), &audit())
+            .await
+            .unwrap();
+        let dependent = badge_use_cases
+            .create_definition(new_badge("Dependent"), &audit())
+            .await
+            .unwrap();
+        let rule = BadgeRule::HasBadge {
+            badge_id: required.id,
+        };
+
+        // A rule write that has locked the badge it requires but not yet committed
+        let mut writer = db.pool.begin().await.unwrap();
+        super::lock_required_badges(&mut writer, Some(&rule))
+            .await
+            .unwrap();
+        let deletion = tokio::spawn({
+            let badge_use_cases = badge_use_cases.clone();
+            async move { badge_use_cases.delete(required.id, &audit()).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert!(!deletion.is_finished());
+
+        sqlx::query("UPDATE badges SET rule = $2 WHERE id = $1")
+            .bind(dependent.id)
+            .bind(Json(&rule))
+            .execute(&mut *writer)
+            .await
+            .unwrap();
+        writer.commit().await.unwrap();
+
+        assert!(matches!(
+            deletion.await.unwrap(),
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn rules_cannot_require_a_badge_deleted_while_they_are_written() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let badge_use_cases = BadgeUseCases::new(persistence.clone(), persistence.clone());
+        let badge_group_id = persistence.read_all_badge_groups().await.unwrap()[0].id;
+        let new_badge = |title: &str, rule: Option<BadgeRule>| NewBadge {
+            title: title.to_string(),
+            description: "Description".to_string(),
+            score: 10,
+            badge_group_id,
+            sort_order: 0,
+            rule,
+        };
+        let required = badge_use_cases
+            .create_definition(new_badge("Required", None), &audit())
+            .await
+            .unwrap();
+
+        // A deletion that has locked the badge but not yet committed
+        let mut deleter = db.pool.begin().await.unwrap();
+        sqlx::query("SELECT id FROM badges WHERE id = $1 FOR UPDATE")
+            .bind(required.id)
+            .execute(&mut *deleter)
+            .await
+            .unwrap();
+        let creation = tokio::spawn({
+            let badge_use_cases = badge_use_cases.clone();
+            let badge = new_badge(
+                "Dependent",
+                Some(BadgeRule::HasBadge {
+                    badge_id: required.id,
+                }),
+            );
+            async move { badge_use_cases.create_definition(badge, &audit()).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert!(!creation.is_finished());
+
+        sqlx::query("DELETE FROM badges WHERE id = $1")
+            .bind(required.id)
+            .execute(&mut *deleter)
+            .await
+            .unwrap();
+        deleter.commit().await.unwrap();
+
+        assert!(matches!(
+            creation.await.unwrap(),
+            Err(AppError::BadRequest(message)) if message.contains("unknown badge")
+        ));
+    }
+
+    #[tokio::test]
+    async fn rules_on_unknown_signals_are_rejected() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let badge_use_cases = BadgeUseCases::new(persistence.clone(), persistence.clone());
+        let badge_group_id = persistence.read_all_badge_groups().await.unwrap()[0].id;
+        let gte = |signal: ProgressionEventType| BadgeRule::Gte {
+            signal,
+            value: Decimal::ONE,
+        };
+        let new_badge = |rule: BadgeRule| NewBadge {
+            title: "Signal".to_string(),
+            description: "Description".to_string(),
+            score: 10,
+            badge_group_id,
+            sort_order: 0,
+            rule: Some(rule),
+        };
+
+        let badge = badge_use_cases
+            .create_definition(new_badge(gte(ProgressionEventType::JupStaked)), &audit())
+            .await
+            .unwrap();
+        assert!(matches!(
+            badge_use_cases
+                .create_definition(new_badge(gte(ProgressionEventType::Other(9999))), &audit())
+                .await,
+            Err(AppError::BadRequest(message)) if message.contains("signal_9999")
+        ));
+        assert!(matches!(
+            badge_use_cases
+                .set_rule(
+                    badge.id,
+                    Some(BadgeRule::Not {
+                        rule: Box::new(gte(ProgressionEventType::Other(9999)))
+                    }),
+                    &audit()
+                )
+                .await,
+            Err(AppError::BadRequest(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn several_migrated_conditions_unlock_a_badge_when_any_is_met() {
+        let Some(db) = TestDatabase::create_migrated_before(20260209100000).await else {
+            return;
+        };
+        // Badge 8 needs 100 $CATICS; give it a second condition on staked JUP
+        sqlx::query(
+            "INSERT INTO badge_conditions
+                 (badge_id, progression_event_type_id, operation, required_count)
+             VALUES (8, 5, 'gte', 10)",
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap();
+        db.migrate().await;
+
+        let requirements = db.persistence().read_badge_requirements().await.unwrap();
+        let rule = requirements
+            .into_iter()
+            .find(|requirement| requirement.badge_id == 8)
+            .unwrap()
+            .rule;
+        assert!(matches!(&rule, BadgeRule::Or { rules } if rules.len() == 2));
+
+        let snapshot = |signals: &[(i32, i64)]| ProgressionSnapshot {
+            beta_applicant_id: 1,
+            public_key: "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5".to_string(),
+            signals: signals
+                .iter()
+                .map(|(id, count)| {
+                    (
+                        ProgressionEventType::from_id(*id).unwrap(),
+                        SignalProgress {
+                            count: Decimal::from(*count),
+                            held_since: None,
+                        },
+                    )
+                })
+                .collect::<HashMap<_, _>>(),
+            badge_ids: HashSet::new(),
+            now: Utc::now(),
+        };
+        assert!(rule.evaluate(&snapshot(&[(2, 100)])));
+        assert!(rule.evaluate(&snapshot(&[(5, 10)])));
+        assert!(!rule.evaluate(&snapshot(&[(2, 99), (5, 9)])));
+    }
+}
@@ -1,6 +1,8 @@
 use crate::adapters::persistence::PostgresPersistence;
+use crate::adapters::persistence::admin::insert_audit_entry;
 use crate::app_error::{AppError, AppResult};
-use crate::entities::badge_group::BadgeGroup;
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::entities::badge_group::{BadgeGroup, BadgeGroupUpdate, NewBadgeGroup};
 use crate::use_cases::badge_group::BadgeGroupPersistence;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -10,20 +12,30 @@ pub struct BadgeGroupDb {
     pub id: i32,
     pub title: String,
     pub description: String,
+    pub sort_order: i32,
+    pub archived_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
 
+impl From<BadgeGroupDb> for BadgeGroup {
+    fn from(badge_group: BadgeGroupDb) -> Self {
+        Self {
+            id: badge_group.id,
+            title: badge_group.title,
+            description: badge_group.description,
+            sort_order: badge_group.sort_order,
+            archived_at: badge_group.archived_at,
+            created_at: badge_group.created_at,
+        }
+    }
+}
+
 impl PostgresPersistence {
     fn convert_to_badge_groups(
         &self,
         badge_groups: Vec<BadgeGroupDb>,
     ) -> AppResult<Vec<BadgeGroup>> {
-        let result = badge_groups.into_iter().map(|badge_group| BadgeGroup {
-            id: badge_group.id,
-            title: badge_group.title,
-            description: badge_group.description,
-            created_at: badge_group.created_at,
-        });
+        let result = badge_groups.into_iter().map(BadgeGroup::from);
 
         Ok(result.collect::<Vec<BadgeGroup>>())
     }
@@ -32,12 +44,173 @@ impl PostgresPersistence {
 #[async_trait]
 impl BadgeGroupPersistence for PostgresPersistence {
     async fn read_badge_groups(&self) -> AppResult<Vec<BadgeGroup>> {
-        let badge_groups =
-            sqlx::query_as!(BadgeGroupDb, "SELECT * FROM badge_groups b ORDER BY b.id")
-                .fetch_all(&self.pool)
-                .await
-                .map_err(AppError::from)?;
+        let badge_groups = sqlx::query_as!(
+            BadgeGroupDb,
+            "SELECT id, title, description, sort_order, archived_at, created_at
+             FROM badge_groups b
+             WHERE b.archived_at IS NULL
+             ORDER BY b.sort_order, b.id"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        self.convert_to_badge_groups(badge_groups)
+    }
+
+    async fn read_all_badge_groups(&self) -> AppResult<Vec<BadgeGroup>> {
+        let badge_groups = sqlx::query_as!(
+            BadgeGroupDb,
+            "SELECT id, title, description, sort_order, archived_at, created_at
+             FROM badge_groups b
+             ORDER BY b.sort_order, b.id"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        self.convert_to_badge_groups(badge_groups)
+    }
+
+    async fn insert_badge_group(
+        &self,
+        badge_group: &NewBadgeGroup,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let created = sqlx::query_as!(
+            BadgeGroupDb,
+            "INSERT INTO badge_groups (title, description, sort_order)
+             VALUES ($1, $2, $3)
+             RETURNING id, title, description, sort_order, archived_at, created_at",
+            badge_group.title,
+            badge_group.description,
+            badge_group.sort_order
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, &audit.with_entity_id(created.id)).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(created.into())
+    }
+
+    async fn update_badge_group(
+        &self,
+        id: i32,
+        update: &BadgeGroupUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let updated = sqlx::query_as!(
+            BadgeGroupDb,
+            "UPDATE badge_groups
+             SET title = COALESCE($2, title),
+                 description = COALESCE($3, description),
+                 sort_order = COALESCE($4, sort_order)
+             WHERE id = $1
+             RETURNING id, title, description, sort_order, archived_at, created_at",
+            id,
+            update.title,
+            update.description,
+            update.sort_order
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(updated.into())
+    }
+
+    async fn archive_badge_group(
+        &self,
+        id: i32,
+        archived: bool,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let updated = sqlx::query_as!(
+            BadgeGroupDb,
+            "UPDATE badge_groups
+             SET archived_at = CASE WHEN $2 THEN COALESCE(archived_at, NOW()) ELSE NULL END
+             WHERE id = $1
+             RETURNING id, title, description, sort_order, archived_at, created_at",
+            id,
+            archived
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(updated.into())
+    }
+
+    async fn delete_badge_group(&self, id: i32, audit: &NewAdminAuditEntry) -> AppResult<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let badge_count = sqlx::query_scalar!(
+            "SELECT COUNT(*) FROM badge_group_conjunctions WHERE badge_group_id = $1",
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?
+        .unwrap_or(0);
+
+        if badge_count > 0 {
+            return Err(AppError::BadRequest(format!(
+                "Badge group {} still contains {} badges",
+                id, badge_count
+            )));
+        }
+
+        let result = sqlx::query!("DELETE FROM badge_groups WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Badge group {} not found", id)));
+        }
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    async fn reorder_badge_groups(
+        &self,
+        ordered_ids: &[i32],
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        sqlx::query!(
+            "UPDATE badge_groups bg
+             SET sort_order = ordering.position * 10
+             FROM UNNEST($1::INTEGER[]) WITH ORDINALITY AS ordering(id, position)
+             WHERE bg.id = ordering.id",
+            ordered_ids
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
 
-        Ok(self.convert_to_badge_groups(badge_groups)?)
+        Ok(())
     }
 }
@@ -172,6 +172,15 @@ impl BetaApplicantPersistence for PostgresPersistence {
         Ok(beta_applicants_count)
     }
 
+    async fn read_all_public_keys(&self) -> AppResult<Vec<String>> {
+        let public_keys = sqlx::query_scalar!("SELECT public_key FROM beta_applicants ORDER BY id")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(public_keys)
+    }
+
     async fn count_referrals(&self, id: i32) -> AppResult<i64> {
         let beta_applicants_count = sqlx::query_scalar!(
             "SELECT count(*) FROM beta_applicants WHERE referred_by_id = $1",
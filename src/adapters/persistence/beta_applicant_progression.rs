@@ -1,10 +1,16 @@
 use crate::adapters::persistence::PostgresPersistence;
 use crate::app_error::{AppError, AppResult};
+use crate::entities::progression_event::{
+    ProgressionEvent, ProgressionHistoryQuery, ProgressionSource,
+};
 use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::sync_report::{SignalSyncResult, SyncClaim};
 use crate::entities::user_progression::UserProgression;
 use crate::use_cases::beta_applicant::BetaApplicantPersistence;
 use crate::use_cases::beta_applicant_progression::BetaApplicantProgressionPersistence;
 use async_trait::async_trait;
+use chrono::Duration;
+use rust_decimal::Decimal;
 use std::collections::HashMap;
 
 #[async_trait]
@@ -13,26 +19,120 @@ impl BetaApplicantProgressionPersistence for PostgresPersistence {
         &self,
         public_key: &str,
         event_type: ProgressionEventType,
-        progress_count: i32,
-    ) -> AppResult<()> {
+        progress_count: Decimal,
+        source: ProgressionSource,
+    ) -> AppResult<bool> {
         let applicant_id = self.read_beta_applicant_by_public_key(public_key).await?.id;
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
 
-        sqlx::query!(
-            r#"
-            INSERT INTO beta_applicant_progressions (beta_applicant_id, progression_event_type_id, progress_count)
-            VALUES ($1, $2, $3)
-            ON CONFLICT (beta_applicant_id, progression_event_type_id)
-            DO UPDATE SET progress_count = EXCLUDED.progress_count
-            "#,
+        // A concurrent first write waits on the unique key here and then takes the update
+        // path, so the row exists before it is locked and compared
+        let inserted = sqlx::query_scalar!(
+            "INSERT INTO beta_applicant_progressions
+                 (beta_applicant_id, progression_event_type_id, progress_count, held_since)
+             VALUES ($1, $2, $3, CASE WHEN $3::NUMERIC > 0 THEN NOW() END)
+             ON CONFLICT (beta_applicant_id, progression_event_type_id) DO NOTHING
+             RETURNING progress_count",
             applicant_id,
             event_type.id(),
             progress_count
         )
-        .execute(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(AppError::from)?;
 
-        Ok(())
+        let changed = match inserted {
+            Some(_) => true,
+            None => {
+                let previous = sqlx::query_scalar!(
+                    "SELECT progress_count FROM beta_applicant_progressions
+                     WHERE beta_applicant_id = $1 AND progression_event_type_id = $2
+                     FOR UPDATE",
+                    applicant_id,
+                    event_type.id()
+                )
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(AppError::from)?;
+
+                sqlx::query!(
+                    "UPDATE beta_applicant_progressions
+                     SET progress_count = $3,
+                         held_since = CASE WHEN $3::NUMERIC > 0 THEN COALESCE(held_since, NOW()) END
+                     WHERE beta_applicant_id = $1 AND progression_event_type_id = $2",
+                    applicant_id,
+                    event_type.id(),
+                    progress_count
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::from)?;
+
+                previous != progress_count
+            }
+        };
+
+        // Only changes are logged; repeated syncs of the same value would just add noise
+        if changed {
+            sqlx::query!(
+                "INSERT INTO progression_events
+                 (beta_applicant_id, progression_event_type_id, value, source)
+                 VALUES ($1, $2, $3, $4)",
+                applicant_id,
+                event_type.id(),
+                progress_count,
+                source.as_str()
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        }
+
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(changed)
+    }
+
+    async fn read_progression_history(
+        &self,
+        public_key: &str,
+        event_type: ProgressionEventType,
+        query: &ProgressionHistoryQuery,
+    ) -> AppResult<Vec<ProgressionEvent>> {
+        let applicant_id = self.read_beta_applicant_by_public_key(public_key).await?.id;
+
+        let events = sqlx::query!(
+            r#"SELECT value AS "value!", source AS "source!", recorded_at AS "recorded_at!"
+             FROM (
+                 SELECT id, value, source, recorded_at FROM progression_events
+                 WHERE beta_applicant_id = $1 AND progression_event_type_id = $2
+                   AND ($3::TIMESTAMPTZ IS NULL OR recorded_at >= $3)
+                   AND ($4::TIMESTAMPTZ IS NULL OR recorded_at < $4)
+                 ORDER BY recorded_at DESC, id DESC
+                 LIMIT $5
+             ) latest
+             ORDER BY recorded_at, id"#,
+            applicant_id,
+            event_type.id(),
+            query.from,
+            query.to,
+            i64::from(query.limit)
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        let result = events
+            .into_iter()
+            .map(|row| ProgressionEvent {
+                event_type,
+                value: row.value,
+                source: row.source,
+                recorded_at: row.recorded_at,
+            })
+            .collect();
+
+        Ok(result)
     }
 
     async fn read_user_progressions(&self, public_key: &str) -> AppResult<Vec<UserProgression>> {
@@ -49,13 +149,19 @@ impl BetaApplicantProgressionPersistence for PostgresPersistence {
         .await
         .map_err(AppError::from)?;
 
-        let all_types =
-            sqlx::query!("SELECT id, event_type FROM progression_event_types ORDER BY id")
-                .fetch_all(&self.pool)
-                .await
-                .map_err(AppError::from)?;
+        let all_types = sqlx::query!(
+            r#"SELECT pet.id, pet.event_type, sss.status AS "status?", sss.last_success_at
+             FROM progression_event_types pet
+             LEFT JOIN signal_sync_statuses sss
+                 ON sss.progression_event_type_id = pet.id AND sss.beta_applicant_id = $1
+             ORDER BY pet.id"#,
+            applicant_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
 
-        let user_map: HashMap<String, i32> = user_progress
+        let user_map: HashMap<String, Decimal> = user_progress
             .into_iter()
             .map(|row| (row.event_type, row.progress_count))
             .collect();
@@ -64,7 +170,9 @@ impl BetaApplicantProgressionPersistence for PostgresPersistence {
             .into_iter()
             .map(|row| UserProgression {
                 progression_event_type: row.event_type.clone(),
-                current_progress: *user_map.get(&row.event_type).unwrap_or(&0),
+                current_progress: *user_map.get(&row.event_type).unwrap_or(&Decimal::ZERO),
+                sync_status: row.status.and_then(|status| status.parse().ok()),
+                last_synced_at: row.last_success_at,
             })
             .collect();
 
@@ -75,7 +183,7 @@ impl BetaApplicantProgressionPersistence for PostgresPersistence {
         &self,
         public_key: &str,
         event_type: ProgressionEventType,
-    ) -> AppResult<i32> {
+    ) -> AppResult<Decimal> {
         let applicant_id = self.read_beta_applicant_by_public_key(public_key).await?.id;
 
         let progress = sqlx::query_scalar!(
@@ -88,6 +196,477 @@ impl BetaApplicantProgressionPersistence for PostgresPersistence {
         .await
         .map_err(AppError::from)?;
 
-        Ok(progress.unwrap_or(0))
+        Ok(progress.unwrap_or(Decimal::ZERO))
+    }
+
+    async fn claim_sync(&self, public_key: &str, cooldown: Duration) -> AppResult<SyncClaim> {
+        let applicant_id = self.read_beta_applicant_by_public_key(public_key).await?.id;
+
+        // Only moves the timestamp forward once the cooldown has passed, so concurrent
+        // requests from several replicas cannot both claim the sync
+        let claimed = sqlx::query_scalar!(
+            "INSERT INTO sync_cooldowns (beta_applicant_id, last_requested_at)
+             VALUES ($1, NOW())
+             ON CONFLICT (beta_applicant_id) DO UPDATE SET last_requested_at = NOW()
+             WHERE sync_cooldowns.last_requested_at <= NOW() - make_interval(secs => $2)
+             RETURNING last_requested_at",
+            applicant_id,
+            cooldown.num_seconds() as f64
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        if claimed.is_some() {
+            return Ok(SyncClaim::Claimed);
+        }
+
+        let last_requested_at = sqlx::query_scalar!(
+            "SELECT last_requested_at FROM sync_cooldowns WHERE beta_applicant_id = $1",
+            applicant_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(SyncClaim::CoolingDown {
+            retry_at: last_requested_at + cooldown,
+        })
+    }
+
+    async fn record_sync_results(
+        &self,
+        public_key: &str,
+        results: &[SignalSyncResult],
+    ) -> AppResult<()> {
+        let applicant_id = self.read_beta_applicant_by_public_key(public_key).await?.id;
+        let signal_ids: Vec<i32> = results.iter().map(|result| result.signal.id()).collect();
+        let statuses: Vec<&str> = results
+            .iter()
+            .map(|result| result.outcome.status().as_str())
+            .collect();
+        let errors: Vec<Option<&str>> = results
+            .iter()
+            .map(|result| result.outcome.reason())
+            .collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO signal_sync_statuses
+                (beta_applicant_id, progression_event_type_id, status, error, last_attempt_at, last_success_at)
+            SELECT $1, t.signal_id, t.status, t.error, NOW(),
+                CASE WHEN t.status = 'synced' THEN NOW() END
+            FROM UNNEST($2::INTEGER[], $3::TEXT[], $4::TEXT[]) AS t(signal_id, status, error)
+            ON CONFLICT (beta_applicant_id, progression_event_type_id)
+            DO UPDATE SET status = EXCLUDED.status,
+                error = EXCLUDED.error,
+                last_attempt_at = EXCLUDED.last_attempt_at,
+                last_success_at = COALESCE(EXCLUDED.last_success_at, signal_sync_statuses.last_success_at)
+            "#,
+            applicant_id,
+            &signal_ids,
+            &statuses as &[&str],
+            &errors as &[Option<&str>]
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::app_error::{AppError, AppResult};
+    use crate::entities::progression_event::{
+        ProgressionHistory, ProgressionHistoryQuery, ProgressionSource,
+    };
+    use crate::entities::progression_event_type::ProgressionEventType;
+    use crate::entities::sync_report::{SignalOutcome, SyncClaim};
+    use crate::entities::token_amount::TokenAmount;
+    use crate::entities::wallet_holdings::WalletHoldings;
+    use crate::use_cases::badge::BadgeUseCases;
+    use crate::use_cases::beta_applicant::BetaApplicantUseCases;
+    use crate::use_cases::beta_applicant_progression::{
+        BetaApplicantProgressionPersistence, BetaApplicantProgressionUseCases,
+    };
+    use crate::use_cases::leaderboard::LeaderboardUseCases;
+    use crate::use_cases::wallet_holdings::WalletHoldingsClient;
+    use async_trait::async_trait;
+    use chrono::Duration;
+    use rust_decimal::Decimal;
+    use std::sync::Arc;
+
+    const PUBLIC_KEY: &str = "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5";
+
+    /// For tests that must be turned away before any balance is read.
+    #[derive(Debug)]
+    struct NoHoldings;
+
+    #[async_trait]
+    impl WalletHoldingsClient for NoHoldings {
+        async fn get_wallet_holdings(&self, _: &str) -> AppResult<WalletHoldings> {
+            unreachable!()
+        }
+
+        async fn get_token_balance(&self, _: &str, _: &str) -> AppResult<TokenAmount> {
+            unreachable!()
+        }
+
+        async fn get_staked_token_balance(&self, _: &str, _: &str) -> AppResult<TokenAmount> {
+            unreachable!()
+        }
+    }
+
+    /// Every balance lookup fails, as when all providers are down.
+    #[derive(Debug)]
+    struct DownHoldings;
+
+    #[async_trait]
+    impl WalletHoldingsClient for DownHoldings {
+        async fn get_wallet_holdings(&self, _: &str) -> AppResult<WalletHoldings> {
+            Err(down())
+        }
+
+        async fn get_token_balance(&self, _: &str, _: &str) -> AppResult<TokenAmount> {
+            Err(down())
+        }
+
+        async fn get_staked_token_balance(&self, _: &str, _: &str) -> AppResult<TokenAmount> {
+            Err(down())
+        }
+    }
+
+    fn down() -> AppError {
+        AppError::Upstream {
+            provider: "jupiter".to_string(),
+            message: "Circuit breaker is open".to_string(),
+        }
+    }
+
+    fn use_cases(
+        db: &TestDatabase,
+        cooldown: Duration,
+    ) -> (BetaApplicantProgressionUseCases, Arc<BadgeUseCases>) {
+        use_cases_with_holdings(db, cooldown, Arc::new(NoHoldings))
+    }
+
+    fn use_cases_with_holdings(
+        db: &TestDatabase,
+        cooldown: Duration,
+        holdings: Arc<dyn WalletHoldingsClient>,
+    ) -> (BetaApplicantProgressionUseCases, Arc<BadgeUseCases>) {
+        let persistence = Arc::new(db.persistence());
+        let progression_use_cases = BetaApplicantProgressionUseCases::new(
+            persistence.clone(),
+            persistence.clone(),
+            holdings,
+            persistence.clone(),
+            persistence.clone(),
+            cooldown,
+        );
+        let badge_use_cases = Arc::new(BadgeUseCases::new(persistence.clone(), persistence));
+        (progression_use_cases, badge_use_cases)
+    }
+
+    async fn insert_applicant(db: &TestDatabase) {
+        sqlx::query("INSERT INTO beta_applicants (public_key, referral_code) VALUES ($1, $1)")
+            .bind(PUBLIC_KEY)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn concurrent_sync_claims_admit_one_per_cooldown() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        insert_applicant(&db).await;
+        let persistence = Arc::new(db.persistence());
+        let cooldown = Duration::minutes(5);
+
+        let claims = (0..8).map(|_| {
+            let persistence = persistence.clone();
+            tokio::spawn(async move { persistence.claim_sync(PUBLIC_KEY, cooldown).await })
+        });
+        let mut claimed = 0;
+        for claim in claims {
+            match claim.await.unwrap().unwrap() {
+                SyncClaim::Claimed => claimed += 1,
+                SyncClaim::CoolingDown { retry_at } => {
+                    let remaining = retry_at - chrono::Utc::now();
+                    assert!(remaining > Duration::minutes(4) && remaining <= cooldown);
+                }
+            }
+        }
+        assert_eq!(claimed, 1);
+
+        assert!(matches!(
+            persistence.claim_sync(PUBLIC_KEY, Duration::zero()).await,
+            Ok(SyncClaim::Claimed)
+        ));
+        assert!(matches!(
+            persistence.claim_sync("unregistered", cooldown).await,
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn concurrent_first_writes_log_one_event() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        insert_applicant(&db).await;
+        let persistence = Arc::new(db.persistence());
+
+        let writes = (0..8).map(|_| {
+            let persistence = persistence.clone();
+            tokio::spawn(async move {
+                persistence
+                    .record_progression_event(
+                        PUBLIC_KEY,
+                        ProgressionEventType::JupStaked,
+                        Decimal::from(7),
+                        ProgressionSource::WalletHoldings,
+                    )
+                    .await
+            })
+        });
+        let mut changed = 0;
+        for write in writes {
+            if write.await.unwrap().unwrap() {
+                changed += 1;
+            }
+        }
+
+        assert_eq!(changed, 1);
+        let history = persistence
+            .read_progression_history(
+                PUBLIC_KEY,
+                ProgressionEventType::JupStaked,
+                &ProgressionHistoryQuery {
+                    from: None,
+                    to: None,
+                    limit: 100,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn history_is_read_in_windows_of_the_latest_events() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        insert_applicant(&db).await;
+        let (use_cases, _) = use_cases(&db, Duration::zero());
+        for value in 1..=5 {
+            use_cases
+                .record_progression_event(
+                    PUBLIC_KEY,
+                    ProgressionEventType::CaticsBalanceCheck,
+                    Decimal::from(value),
+                    ProgressionSource::WalletHoldings,
+                )
+                .await
+                .unwrap();
+        }
+        let read = |from, to, limit| {
+            use_cases.read_progression_history(
+                PUBLIC_KEY,
+                ProgressionEventType::CaticsBalanceCheck,
+                ProgressionHistoryQuery { from, to, limit },
+            )
+        };
+        let values = |history: &ProgressionHistory| -> Vec<Decimal> {
+            history.events.iter().map(|event| event.value).collect()
+        };
+
+        let latest = read(None, None, 2).await.unwrap();
+        assert_eq!(values(&latest), [Decimal::from(4), Decimal::from(5)]);
+        assert!(latest.has_more);
+
+        let earlier = read(None, Some(latest.events[0].recorded_at), 2)
+            .await
+            .unwrap();
+        assert_eq!(values(&earlier), [Decimal::from(2), Decimal::from(3)]);
+        assert!(earlier.has_more);
+
+        let rest = read(None, Some(earlier.events[0].recorded_at), 2)
+            .await
+            .unwrap();
+        assert_eq!(values(&rest), [Decimal::from(1)]);
+        assert!(!rest.has_more);
+
+        let since = read(Some(earlier.events[1].recorded_at), None, 10)
+            .await
+            .unwrap();
+        assert_eq!(
+            values(&since),
+            [Decimal::from(3), Decimal::from(4), Decimal::from(5)]
+        );
+        assert!(!since.has_more);
+    }
+
+    #[tokio::test]
+    async fn syncs_within_the_cooldown_are_told_when_to_retry() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        insert_applicant(&db).await;
+        let (progression_use_cases, badge_use_cases) = use_cases(&db, Duration::minutes(5));
+        db.persistence()
+            .claim_sync(PUBLIC_KEY, Duration::minutes(5))
+            .await
+            .unwrap();
+
+        let result = progression_use_cases
+            .sync_with_cooldown(PUBLIC_KEY, badge_use_cases)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::RateLimited(seconds)) if (240..=300).contains(&seconds)
+        ));
+    }
+
+    #[tokio::test]
+    async fn batch_syncs_take_between_one_and_a_hundred_wallets() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let (progression_use_cases, badge_use_cases) = use_cases(&db, Duration::minutes(5));
+        let public_keys = |count: usize| -> Vec<String> {
+            (0..count).map(|index| format!("wallet{}", index)).collect()
+        };
+
+        for count in [0, 101] {
+            assert!(matches!(
+                progression_use_cases
+                    .sync_many(&public_keys(count), badge_use_cases.clone())
+                    .await,
+                Err(AppError::BadRequest(_))
+            ));
+        }
+
+        // None of them is registered, so each is reported on its own without a sync
+        let results = progression_use_cases
+            .sync_many(&public_keys(100), badge_use_cases)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 100);
+        assert!(
+            results
+                .iter()
+                .all(|(_, result)| matches!(result, Err(AppError::NotFound(_))))
+        );
+    }
+
+    #[tokio::test]
+    async fn signups_unlock_nothing_on_balances_that_could_not_be_read() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let (progression_use_cases, badge_use_cases) =
+            use_cases_with_holdings(&db, Duration::minutes(5), Arc::new(DownHoldings));
+        let leaderboard_use_cases = Arc::new(LeaderboardUseCases::new(
+            persistence.clone(),
+            Duration::seconds(60),
+            persistence.clone(),
+        ));
+        sqlx::query(
+            "INSERT INTO tracked_tokens (mint, symbol, decimals, kind, progression_event_type_id)
+             VALUES ('catics', 'CATICS', 6, 'held', 2)",
+        )
+        .execute(&db.pool)
+        .await
+        .unwrap();
+        let badge_id: i32 = sqlx::query_scalar(
+            r#"UPDATE badges
+               SET rule = '{"op": "lte", "signal": "catics_balance_check", "value": 0}'
+               WHERE id = (SELECT MIN(id) FROM badges)
+               RETURNING id"#,
+        )
+        .fetch_one(&db.pool)
+        .await
+        .unwrap();
+
+        let (_, report) = BetaApplicantUseCases::new(persistence)
+            .create(
+                PUBLIC_KEY,
+                None,
+                Arc::new(progression_use_cases),
+                badge_use_cases,
+                leaderboard_use_cases,
+            )
+            .await
+            .unwrap();
+
+        let catics = report
+            .signals
+            .iter()
+            .find(|result| result.signal == ProgressionEventType::CaticsBalanceCheck)
+            .unwrap();
+        assert_eq!(
+            catics.outcome,
+            SignalOutcome::Unavailable("Upstream provider jupiter is unavailable".to_string())
+        );
+        assert!(!report.unlocked_badge_ids.contains(&badge_id));
+        let held: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM beta_applicant_badges WHERE badge_id = $1")
+                .bind(badge_id)
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        assert_eq!(held, 0);
+    }
+
+    #[tokio::test]
+    async fn stored_sync_errors_are_reduced_to_what_users_may_see() {
+        let Some(db) = TestDatabase::create_migrated_before(20260305090000).await else {
+            return;
+        };
+        insert_applicant(&db).await;
+        for (signal, error) in [
+            (
+                2,
+                "Upstream error from jupiter: Request failed with status 500: <html>",
+            ),
+            (5, "Database error: relation \"x\" does not exist"),
+            (6, "Internal error: CATICS balance 0.1 does not fit"),
+        ] {
+            sqlx::query(
+                "INSERT INTO signal_sync_statuses
+                     (beta_applicant_id, progression_event_type_id, status, error)
+                 SELECT id, $1, 'unavailable', $2 FROM beta_applicants",
+            )
+            .bind(signal)
+            .bind(error)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        }
+        db.migrate().await;
+
+        let errors: Vec<String> = sqlx::query_scalar(
+            "SELECT error FROM signal_sync_statuses ORDER BY progression_event_type_id",
+        )
+        .fetch_all(&db.pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            errors,
+            vec![
+                "Upstream provider jupiter is unavailable",
+                "Database exception",
+                "Internal server error",
+            ]
+        );
     }
 }
@@ -47,7 +47,7 @@ impl PostgresPersistence {
 
             levels_by_cat
                 .entry(level_db.cat_id)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(cat_level);
         }
 
@@ -0,0 +1,160 @@
+use crate::adapters::persistence::PostgresPersistence;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::user_event::UserEvent;
+use crate::use_cases::event::{EventBus, EventPublisher};
+use async_trait::async_trait;
+use sqlx::postgres::PgListener;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+const USER_EVENTS_CHANNEL: &str = "user_events";
+const LEADERBOARD_CHANGES_CHANNEL: &str = "leaderboard_changes";
+/// Postgres rejects notification payloads of 8000 bytes or more.
+const MAX_PAYLOAD_BYTES: usize = 7000;
+const LISTENER_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+#[async_trait]
+impl EventPublisher for PostgresPersistence {
+    async fn publish(&self, events: &[UserEvent]) -> AppResult<()> {
+        for payload in notification_payloads(events)? {
+            sqlx::query!("SELECT pg_notify($1, $2)", USER_EVENTS_CHANNEL, payload)
+                .execute(&self.pool)
+                .await
+                .map_err(AppError::from)?;
+        }
+        Ok(())
+    }
+
+    async fn publish_leaderboard_change(&self) -> AppResult<()> {
+        sqlx::query!("SELECT pg_notify($1, '')", LEADERBOARD_CHANGES_CHANNEL)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    }
+}
+
+impl PostgresPersistence {
+    /// Forwards the events and leaderboard changes published by any replica into `event_bus`
+    /// until the process exits.
+    pub fn spawn_event_listener(&self, event_bus: EventBus) -> JoinHandle<()> {
+        let pool = self.pool.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = forward_notifications(&pool, &event_bus).await {
+                    error!("User event listener failed: {}", e);
+                }
+                tokio::time::sleep(LISTENER_RETRY_DELAY).await;
+            }
+        })
+    }
+}
+
+async fn forward_notifications(pool: &sqlx::PgPool, event_bus: &EventBus) -> sqlx::Result<()> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener
+        .listen_all([USER_EVENTS_CHANNEL, LEADERBOARD_CHANGES_CHANNEL])
+        .await?;
+    info!("Listening for user events");
+    // The leaderboard may have changed while nobody was listening
+    event_bus.send_leaderboard_change();
+
+    loop {
+        let notification = listener.recv().await?;
+        if notification.channel() == LEADERBOARD_CHANGES_CHANNEL {
+            event_bus.send_leaderboard_change();
+            continue;
+        }
+        match serde_json::from_str::<Vec<UserEvent>>(notification.payload()) {
+            Ok(events) => events.into_iter().for_each(|event| event_bus.send(event)),
+            Err(e) => warn!("Ignoring malformed user event notification: {}", e),
+        }
+    }
+}
+
+/// Splits the events into JSON arrays that each fit into a single notification.
+fn notification_payloads(events: &[UserEvent]) -> AppResult<Vec<String>> {
+    let mut payloads = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut current_bytes = 2;
+
+    for event in events {
+        let json = serde_json::to_string(event).map_err(|e| AppError::Internal(e.to_string()))?;
+        if json.len() + 2 > MAX_PAYLOAD_BYTES {
+            warn!("Dropping user event of {} bytes", json.len());
+            continue;
+        }
+        if !current.is_empty() && current_bytes + 1 + json.len() > MAX_PAYLOAD_BYTES {
+            payloads.push(format!("[{}]", current.join(",")));
+            current.clear();
+            current_bytes = 2;
+        }
+        current_bytes += json.len() + usize::from(!current.is_empty());
+        current.push(json);
+    }
+
+    if !current.is_empty() {
+        payloads.push(format!("[{}]", current.join(",")));
+    }
+    Ok(payloads)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::entities::user_event::UserEventKind;
+    use tokio::sync::broadcast;
+    use tokio::time::timeout;
+
+    fn badge_unlocked(badge_id: i32) -> UserEvent {
+        UserEvent::new(
+            "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5",
+            UserEventKind::BadgeUnlocked { badge_id },
+        )
+    }
+
+    #[test]
+    fn payloads_fit_into_notifications_and_keep_every_event() {
+        let events: Vec<UserEvent> = (0..500).map(badge_unlocked).collect();
+
+        let payloads = notification_payloads(&events).unwrap();
+
+        assert!(payloads.len() > 1);
+        assert!(payloads.iter().all(|p| p.len() <= MAX_PAYLOAD_BYTES));
+        let decoded: Vec<UserEvent> = payloads
+            .iter()
+            .flat_map(|p| serde_json::from_str::<Vec<UserEvent>>(p).unwrap())
+            .collect();
+        assert_eq!(decoded, events);
+    }
+
+    #[test]
+    fn no_events_need_no_notification() {
+        assert!(notification_payloads(&[]).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn leaderboard_changes_reach_the_event_bus() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = db.persistence();
+        let event_bus = EventBus::new(16);
+        let mut changes = event_bus.subscribe_leaderboard_changes();
+        let listener = persistence.spawn_event_listener(event_bus.clone());
+
+        let next_change = async |changes: &mut broadcast::Receiver<()>| {
+            timeout(Duration::from_secs(5), changes.recv())
+                .await
+                .unwrap()
+                .unwrap()
+        };
+        // Announced on connecting, since changes may have been missed before
+        next_change(&mut changes).await;
+        persistence.publish_leaderboard_change().await.unwrap();
+        next_change(&mut changes).await;
+        listener.abort();
+    }
+}
@@ -0,0 +1,59 @@
+use crate::app_error::AppResult;
+use crate::entities::auth_nonce::{AuthNonce, NonceClaim};
+use crate::use_cases::auth::AuthNoncePersistence;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Process-local store for short-lived data. Only suitable for single-replica deployments,
+/// since nothing is shared between instances.
+#[derive(Debug, Default)]
+pub struct InMemoryPersistence {
+    nonces: Mutex<HashMap<String, AuthNonce>>,
+}
+
+impl InMemoryPersistence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl AuthNoncePersistence for InMemoryPersistence {
+    async fn create_nonce(&self, nonce: &AuthNonce, max_outstanding: i64) -> AppResult<NonceClaim> {
+        let now = Utc::now();
+        let mut nonces = self.nonces.lock().unwrap();
+        let outstanding: Vec<_> = nonces
+            .values()
+            .filter(|stored| stored.public_key == nonce.public_key && stored.expires_at > now)
+            .map(|stored| stored.expires_at)
+            .collect();
+        if outstanding.len() as i64 >= max_outstanding {
+            return Ok(NonceClaim::TooMany {
+                retry_at: outstanding.into_iter().min().unwrap_or(nonce.expires_at),
+            });
+        }
+
+        nonces.insert(nonce.nonce.clone(), nonce.clone());
+        Ok(NonceClaim::Claimed)
+    }
+
+    async fn consume_nonce(&self, public_key: &str, nonce: &str) -> AppResult<bool> {
+        let mut nonces = self.nonces.lock().unwrap();
+
+        // Removing the entry up front makes the nonce single-use even if it turns out invalid
+        match nonces.remove(nonce) {
+            Some(stored) => Ok(stored.public_key == public_key && stored.expires_at > Utc::now()),
+            None => Ok(false),
+        }
+    }
+
+    async fn purge_expired_nonces(&self) -> AppResult<u64> {
+        let now = Utc::now();
+        let mut nonces = self.nonces.lock().unwrap();
+        let before = nonces.len();
+        nonces.retain(|_, stored| stored.expires_at > now);
+        Ok((before - nonces.len()) as u64)
+    }
+}
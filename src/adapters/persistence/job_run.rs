@@ -0,0 +1,416 @@
+use crate::adapters::persistence::PostgresPersistence;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::job_run::{JobRun, JobRunStatus};
+use crate::use_cases::job::{JobLock, JobPersistence};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::Postgres;
+use sqlx::pool::PoolConnection;
+
+#[derive(sqlx::FromRow, Debug)]
+struct JobRunDb {
+    pub id: i32,
+    pub job_name: String,
+    pub status: String,
+    pub summary: Value,
+    pub error: Option<String>,
+    pub scheduled_for: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<JobRunDb> for JobRun {
+    fn from(run: JobRunDb) -> Self {
+        Self {
+            id: run.id,
+            job_name: run.job_name,
+            status: run.status,
+            summary: run.summary,
+            error: run.error,
+            scheduled_for: run.scheduled_for,
+            started_at: run.started_at,
+            finished_at: run.finished_at,
+        }
+    }
+}
+
+/// Session-level advisory lock. The connection is closed instead of being returned to the
+/// pool when dropped, which releases the lock even if the job panicked. That close happens in
+/// the background, so `release` unlocks explicitly for the next run to find the lock free.
+#[derive(Debug)]
+struct AdvisoryLock {
+    connection: PoolConnection<Postgres>,
+    key: String,
+}
+
+#[async_trait]
+impl JobLock for AdvisoryLock {
+    async fn release(mut self: Box<Self>) -> AppResult<()> {
+        sqlx::query_scalar!("SELECT pg_advisory_unlock(hashtext($1))", self.key)
+            .fetch_one(&mut *self.connection)
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl JobPersistence for PostgresPersistence {
+    async fn try_lock_job(&self, job_name: &str) -> AppResult<Option<Box<dyn JobLock>>> {
+        let mut connection = self.pool.acquire().await.map_err(AppError::from)?;
+        connection.close_on_drop();
+
+        let key = format!("job:{}", job_name);
+        let acquired = sqlx::query_scalar!("SELECT pg_try_advisory_lock(hashtext($1))", key)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(AppError::from)?
+            .unwrap_or(false);
+
+        if !acquired {
+            return Ok(None);
+        }
+
+        Ok(Some(Box::new(AdvisoryLock { connection, key })))
+    }
+
+    async fn create_job_run(
+        &self,
+        job_name: &str,
+        scheduled_for: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<JobRun>> {
+        let run = sqlx::query_as!(
+            JobRunDb,
+            "INSERT INTO job_runs (job_name, status, scheduled_for)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (job_name, scheduled_for) DO NOTHING
+             RETURNING id, job_name, status, summary, error, scheduled_for, started_at, finished_at",
+            job_name,
+            JobRunStatus::Running.as_str(),
+            scheduled_for
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(run.map(JobRun::from))
+    }
+
+    async fn finish_job_run(
+        &self,
+        id: i32,
+        status: JobRunStatus,
+        summary: &Value,
+        error: Option<&str>,
+    ) -> AppResult<JobRun> {
+        let run = sqlx::query_as!(
+            JobRunDb,
+            "UPDATE job_runs
+             SET status = $2, summary = $3, error = $4, finished_at = NOW()
+             WHERE id = $1
+             RETURNING id, job_name, status, summary, error, scheduled_for, started_at, finished_at",
+            id,
+            status.as_str(),
+            summary,
+            error
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(run.into())
+    }
+
+    async fn abandon_running_job_runs(&self, job_name: &str) -> AppResult<u64> {
+        let result = sqlx::query!(
+            "UPDATE job_runs SET status = $3, finished_at = NOW()
+             WHERE job_name = $1 AND status = $2",
+            job_name,
+            JobRunStatus::Running.as_str(),
+            JobRunStatus::Abandoned.as_str()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn read_job_runs(&self, limit: u32) -> AppResult<Vec<JobRun>> {
+        let runs = sqlx::query_as!(
+            JobRunDb,
+            "SELECT id, job_name, status, summary, error, scheduled_for, started_at, finished_at
+             FROM job_runs
+             ORDER BY started_at DESC
+             LIMIT $1",
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(runs.into_iter().map(JobRun::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::app_error::{AppError, AppResult};
+    use crate::entities::job_run::JobRunStatus;
+    use crate::use_cases::job::{Job, JobPersistence, JobUseCases};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use serde_json::{Value, json};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Advisory locks are shared by every test database on the server, so each test names its
+    /// job differently.
+    #[derive(Debug, Default)]
+    struct CountingJob {
+        name: &'static str,
+        runs: AtomicUsize,
+        fail: bool,
+    }
+
+    impl CountingJob {
+        fn named(name: &'static str) -> Self {
+            Self {
+                name,
+                ..Self::default()
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Job for CountingJob {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn run(&self) -> AppResult<Value> {
+            let runs = self.runs.fetch_add(1, Ordering::SeqCst) + 1;
+            if self.fail {
+                return Err(AppError::Internal("upstream unavailable".to_string()));
+            }
+            Ok(json!({ "runs": runs }))
+        }
+    }
+
+    /// A job whose lock is shared with other jobs, which tests hold as `shared`.
+    #[derive(Debug, Default)]
+    struct SharingJob {
+        runs: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Job for SharingJob {
+        fn name(&self) -> &'static str {
+            "sharing"
+        }
+
+        fn lock_name(&self) -> &'static str {
+            "shared"
+        }
+
+        async fn run(&self) -> AppResult<Value> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Ok(json!({}))
+        }
+    }
+
+    fn slot() -> Option<DateTime<Utc>> {
+        Some(
+            DateTime::parse_from_rfc3339("2026-02-01T01:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    #[tokio::test]
+    async fn records_successful_runs() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let job_use_cases = JobUseCases::new(Arc::new(db.persistence()));
+        let job = CountingJob::named("records_successful_runs");
+
+        let run = job_use_cases
+            .run_exclusive(&job, None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(run.status, JobRunStatus::Succeeded.as_str());
+        assert_eq!(run.summary, json!({ "runs": 1 }));
+        assert!(run.finished_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn skips_jobs_locked_elsewhere() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let job_use_cases = JobUseCases::new(persistence.clone());
+        let job = CountingJob::named("skips_jobs_locked_elsewhere");
+
+        let lock = persistence.try_lock_job(job.name()).await.unwrap();
+        assert!(lock.is_some());
+        assert!(
+            job_use_cases
+                .run_exclusive(&job, None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(job.runs.load(Ordering::SeqCst), 0);
+
+        lock.unwrap().release().await.unwrap();
+        assert!(
+            job_use_cases
+                .run_exclusive(&job, None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(job.runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn scheduled_slots_wait_for_jobs_sharing_their_lock() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let job_use_cases = Arc::new(JobUseCases::new(persistence.clone()));
+        let job = Arc::new(SharingJob::default());
+
+        let lock = persistence.try_lock_job("shared").await.unwrap();
+        assert!(
+            job_use_cases
+                .run_exclusive(job.as_ref(), None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let scheduled = tokio::spawn({
+            let job_use_cases = job_use_cases.clone();
+            let job = job.clone();
+            async move {
+                job_use_cases
+                    .run_scheduled(job.as_ref(), slot().unwrap())
+                    .await
+            }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        assert_eq!(job.runs.load(Ordering::SeqCst), 0);
+
+        lock.unwrap().release().await.unwrap();
+        let run = scheduled.await.unwrap().unwrap().unwrap();
+
+        assert_eq!(run.job_name, "sharing");
+        assert_eq!(job.runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn runs_each_scheduled_slot_once() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let job_use_cases = JobUseCases::new(Arc::new(db.persistence()));
+        let job = CountingJob::named("runs_each_scheduled_slot_once");
+
+        // Another replica waking up for the same slot after the first run finished
+        assert!(
+            job_use_cases
+                .run_exclusive(&job, slot())
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            job_use_cases
+                .run_exclusive(&job, slot())
+                .await
+                .unwrap()
+                .is_none()
+        );
+        // On-demand runs have no slot
+        assert!(
+            job_use_cases
+                .run_exclusive(&job, None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            job_use_cases
+                .run_exclusive(&job, None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+
+        assert_eq!(job.runs.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn marks_runs_left_running_as_abandoned() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let job_use_cases = JobUseCases::new(persistence.clone());
+        let job = CountingJob::named("marks_runs_left_running_as_abandoned");
+
+        // A process that died mid-run never finishes its run
+        let crashed = persistence
+            .create_job_run(job.name(), slot())
+            .await
+            .unwrap()
+            .unwrap();
+        job_use_cases
+            .run_exclusive(&job, None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let runs = persistence.read_job_runs(10).await.unwrap();
+        let crashed = runs.iter().find(|run| run.id == crashed.id).unwrap();
+        assert_eq!(crashed.status, JobRunStatus::Abandoned.as_str());
+        assert!(crashed.finished_at.is_some());
+        // Its slot stays claimed
+        assert!(
+            job_use_cases
+                .run_exclusive(&job, slot())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn records_failures() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let job_use_cases = JobUseCases::new(Arc::new(db.persistence()));
+        let job = CountingJob {
+            fail: true,
+            ..CountingJob::named("records_failures")
+        };
+
+        let run = job_use_cases
+            .run_exclusive(&job, None)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(run.status, JobRunStatus::Failed.as_str());
+        assert!(run.error.unwrap().contains("upstream unavailable"));
+        assert_eq!(run.summary, json!({}));
+    }
+}
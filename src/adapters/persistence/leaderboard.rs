@@ -1,9 +1,18 @@
 use crate::adapters::persistence::PostgresPersistence;
+use crate::adapters::persistence::admin::insert_audit_entry;
 use crate::app_error::{AppError, AppResult};
-use crate::entities::leaderboard_entry::LeaderboardEntry;
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::entities::leaderboard_entry::{LeaderboardEntry, UserStanding};
+use crate::entities::leaderboard_kind::LeaderboardKind;
+use crate::entities::leaderboard_refresh::{
+    LeaderboardStanding, RankedEntry, rank_standings, temporary_rank,
+};
+use crate::entities::leaderboard_snapshot::{LeaderboardSnapshot, MoverDirection, RankMovement};
+use crate::entities::season::{NewSeason, Season, SeasonRollover, SeasonUpdate};
 use crate::use_cases::leaderboard::LeaderboardPersistence;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 
 #[derive(sqlx::FromRow, Debug)]
 struct LeaderboardEntryDb {
@@ -14,17 +23,85 @@ struct LeaderboardEntryDb {
     pub created_at: DateTime<Utc>,
 }
 
-/// Database row for real-time leaderboard calculation using CTE and window functions.
+impl From<LeaderboardEntryDb> for LeaderboardEntry {
+    fn from(entry: LeaderboardEntryDb) -> Self {
+        Self {
+            public_key: entry.public_key,
+            total_score: entry.total_score,
+            score: None,
+            rank: entry.rank as u32,
+            previous_rank: entry.previous_rank.map(|r| r as u32),
+            created_at: entry.created_at,
+        }
+    }
+}
+
+/// Database row for real-time leaderboard kinds calculated using CTE and window functions.
 /// Fields are Optional because SQLx cannot guarantee non-nullability with complex queries,
 /// even though logically these should always have values for existing users.
 #[derive(sqlx::FromRow, Debug)]
 struct RealtimeLeaderboardEntryDb {
     pub public_key: Option<String>,
     pub total_score: Option<i32>,
+    pub score: Option<Decimal>,
     pub rank: Option<i64>,
     pub created_at: Option<DateTime<Utc>>,
 }
 
+/// Badge group and progression event type filters of the real-time kind query; the total
+/// board is served from `leaderboard_entries` and matches neither.
+fn kind_filters(kind: &LeaderboardKind) -> (Option<i32>, Option<i32>) {
+    match kind {
+        LeaderboardKind::Total => (None, None),
+        LeaderboardKind::BadgeGroup(id) => (Some(*id), None),
+        LeaderboardKind::Signal(signal) => (None, Some(signal.id())),
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct SeasonDb {
+    pub id: i32,
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
+impl From<SeasonDb> for Season {
+    fn from(season: SeasonDb) -> Self {
+        Self {
+            id: season.id,
+            name: season.name,
+            starts_at: season.starts_at,
+            ends_at: season.ends_at,
+            finalized_at: season.finalized_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug)]
+struct LeaderboardStandingDb {
+    pub beta_applicant_id: i32,
+    pub public_key: String,
+    pub applicant_created_at: DateTime<Utc>,
+    pub total_score: Option<i32>,
+    pub rank: Option<i32>,
+    pub badge_total: i32,
+}
+
+impl From<LeaderboardStandingDb> for LeaderboardStanding {
+    fn from(standing: LeaderboardStandingDb) -> Self {
+        Self {
+            beta_applicant_id: standing.beta_applicant_id,
+            public_key: standing.public_key,
+            applicant_created_at: standing.applicant_created_at,
+            total_score: standing.total_score,
+            rank: standing.rank,
+            badge_total: standing.badge_total,
+        }
+    }
+}
+
 #[async_trait]
 impl LeaderboardPersistence for PostgresPersistence {
     async fn get_leaderboard_entries(
@@ -50,6 +127,7 @@ impl LeaderboardPersistence for PostgresPersistence {
             .map(|entry| LeaderboardEntry {
                 public_key: entry.public_key,
                 total_score: entry.total_score,
+                score: None,
                 rank: entry.rank as u32,
                 previous_rank: entry.previous_rank.map(|r| r as u32),
                 created_at: entry.created_at,
@@ -68,16 +146,54 @@ impl LeaderboardPersistence for PostgresPersistence {
         Ok(count.unwrap_or(0) as u32)
     }
 
-    async fn get_user_rank(&self, public_key: &str) -> AppResult<u32> {
-        let result = sqlx::query!(
-            "SELECT rank FROM leaderboard_entries WHERE public_key = $1",
+    async fn get_user_standing(&self, public_key: &str) -> AppResult<Option<UserStanding>> {
+        // Counting the applicants ahead avoids ranking the whole table for a single user.
+        let standing = sqlx::query!(
+            r#"
+            WITH badge_totals AS (
+                SELECT bab.beta_applicant_id, SUM(b.score)::INTEGER AS badge_total
+                FROM beta_applicant_badges bab
+                JOIN badges b ON bab.badge_id = b.id
+                GROUP BY bab.beta_applicant_id
+            ),
+            projected AS (
+                SELECT ba.id,
+                       ba.public_key,
+                       ba.created_at,
+                       le.rank,
+                       le.previous_rank,
+                       COALESCE(le.total_score, 0) AS official_score,
+                       COALESCE(le.total_score, 0) + COALESCE(bt.badge_total, 0) AS projected_score
+                FROM beta_applicants ba
+                LEFT JOIN leaderboard_entries le ON le.beta_applicant_id = ba.id
+                LEFT JOIN badge_totals bt ON bt.beta_applicant_id = ba.id
+            )
+            SELECT me.rank AS "official_rank?",
+                   me.previous_rank AS "previous_rank?",
+                   me.official_score AS "official_score!",
+                   me.projected_score AS "projected_score!",
+                   (
+                       SELECT COUNT(*) FROM projected ahead
+                       WHERE ahead.projected_score > me.projected_score
+                          OR (ahead.projected_score = me.projected_score
+                              AND (ahead.created_at, ahead.id) < (me.created_at, me.id))
+                   ) + 1 AS "projected_rank!"
+            FROM projected me
+            WHERE me.public_key = $1
+            "#,
             public_key
         )
         .fetch_optional(&self.pool)
         .await
         .map_err(AppError::from)?;
 
-        Ok(result.map(|row| row.rank as u32).unwrap_or(0))
+        Ok(standing.map(|standing| UserStanding {
+            official_rank: standing.official_rank.map(|r| r as u32),
+            official_score: standing.official_score,
+            previous_rank: standing.previous_rank.map(|r| r as u32),
+            projected_rank: standing.projected_rank as u32,
+            projected_score: standing.projected_score,
+        }))
     }
 
     async fn get_user_leaderboard_entry(
@@ -98,65 +214,1129 @@ impl LeaderboardPersistence for PostgresPersistence {
         Ok(result.map(|entry| LeaderboardEntry {
             public_key: entry.public_key,
             total_score: entry.total_score,
+            score: None,
             rank: entry.rank as u32,
             previous_rank: entry.previous_rank.map(|r| r as u32),
             created_at: entry.created_at,
         }))
     }
 
-    async fn get_user_realtime_entry(
+    async fn add_new_user_to_leaderboard(
+        &self,
+        beta_applicant_id: i32,
+        public_key: &str,
+    ) -> AppResult<()> {
+        sqlx::query!(
+            "INSERT INTO leaderboard_entries 
+             (beta_applicant_id, public_key, total_score, rank, previous_rank, created_at, updated_at)
+             VALUES ($1, $2, 0, (SELECT COALESCE(MAX(rank), 0) + 1 FROM leaderboard_entries), NULL, NOW(), NOW())",
+            beta_applicant_id,
+            public_key
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    async fn refresh_daily(
+        &self,
+        credited_on: Option<NaiveDate>,
+        audit: Option<&NewAdminAuditEntry>,
+    ) -> AppResult<Option<Vec<RankedEntry>>> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        // New applicants get a rank on signup, so keep them out until the ranks are final.
+        sqlx::query!("LOCK TABLE leaderboard_entries IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        // Dropping the transaction on an already credited day leaves every score untouched.
+        if let Some(credited_on) = credited_on {
+            let credited = sqlx::query_scalar!(
+                r#"
+                INSERT INTO leaderboard_credits (season_id, credited_on)
+                SELECT id, $1 FROM seasons WHERE finalized_at IS NULL
+                ON CONFLICT (season_id, credited_on) DO NOTHING
+                RETURNING season_id
+                "#,
+                credited_on
+            )
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+            if credited.is_none() {
+                return Ok(None);
+            }
+        }
+
+        let standings = sqlx::query_as!(
+            LeaderboardStandingDb,
+            r#"
+            SELECT ba.id AS beta_applicant_id,
+                   ba.public_key,
+                   ba.created_at AS applicant_created_at,
+                   le.total_score AS "total_score?",
+                   le.rank AS "rank?",
+                   COALESCE(SUM(b.score), 0)::INTEGER AS "badge_total!"
+            FROM beta_applicants ba
+            LEFT JOIN leaderboard_entries le ON le.beta_applicant_id = ba.id
+            LEFT JOIN beta_applicant_badges bab ON ba.id = bab.beta_applicant_id
+            LEFT JOIN badges b ON bab.badge_id = b.id
+            GROUP BY ba.id, ba.public_key, ba.created_at, le.total_score, le.rank
+            "#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let mut standings: Vec<LeaderboardStanding> =
+            standings.into_iter().map(Into::into).collect();
+        if credited_on.is_none() {
+            for standing in &mut standings {
+                standing.badge_total = 0;
+            }
+        }
+        let ranked = rank_standings(standings);
+
+        let ids: Vec<i32> = ranked.iter().map(|e| e.beta_applicant_id).collect();
+        let public_keys: Vec<String> = ranked.iter().map(|e| e.public_key.clone()).collect();
+        let scores: Vec<i32> = ranked.iter().map(|e| e.total_score).collect();
+        let temporary_ranks: Vec<i32> = ids.iter().copied().map(temporary_rank).collect();
+        let previous_ranks: Vec<Option<i32>> = ranked.iter().map(|e| e.previous_rank).collect();
+        let ranks: Vec<i32> = ranked.iter().map(|e| e.rank).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO leaderboard_entries
+                (beta_applicant_id, public_key, total_score, rank, previous_rank, created_at, updated_at)
+            SELECT u.beta_applicant_id, u.public_key, u.total_score, u.rank, u.previous_rank, NOW(), NOW()
+            FROM UNNEST($1::INTEGER[], $2::TEXT[], $3::INTEGER[], $4::INTEGER[], $5::INTEGER[])
+                AS u(beta_applicant_id, public_key, total_score, rank, previous_rank)
+            ON CONFLICT (beta_applicant_id)
+            DO UPDATE SET
+                total_score = EXCLUDED.total_score,
+                rank = EXCLUDED.rank,
+                previous_rank = EXCLUDED.previous_rank,
+                updated_at = NOW()
+            "#,
+            &ids,
+            &public_keys,
+            &scores,
+            &temporary_ranks,
+            &previous_ranks as &[Option<i32>]
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query!(
+            r#"
+            UPDATE leaderboard_entries
+            SET rank = u.rank
+            FROM UNNEST($1::INTEGER[], $2::INTEGER[]) AS u(beta_applicant_id, rank)
+            WHERE leaderboard_entries.beta_applicant_id = u.beta_applicant_id
+            "#,
+            &ids,
+            &ranks
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO leaderboard_snapshots (beta_applicant_id, season_id, rank, total_score)
+            SELECT u.beta_applicant_id,
+                   (SELECT id FROM seasons WHERE finalized_at IS NULL),
+                   u.rank,
+                   u.total_score
+            FROM UNNEST($1::INTEGER[], $2::INTEGER[], $3::INTEGER[])
+                AS u(beta_applicant_id, rank, total_score)
+            "#,
+            &ids,
+            &ranks,
+            &scores
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        if let Some(audit) = audit {
+            insert_audit_entry(&mut tx, audit).await?;
+        }
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(Some(ranked))
+    }
+
+    async fn read_seasons(&self) -> AppResult<Vec<Season>> {
+        let seasons = sqlx::query_as!(
+            SeasonDb,
+            "SELECT id, name, starts_at, ends_at, finalized_at
+             FROM seasons
+             ORDER BY starts_at DESC, id DESC"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(seasons.into_iter().map(Season::from).collect())
+    }
+
+    async fn read_season(&self, id: i32) -> AppResult<Season> {
+        let season = sqlx::query_as!(
+            SeasonDb,
+            "SELECT id, name, starts_at, ends_at, finalized_at FROM seasons WHERE id = $1",
+            id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(season.into())
+    }
+
+    async fn read_open_season(&self) -> AppResult<Option<Season>> {
+        let season = sqlx::query_as!(
+            SeasonDb,
+            "SELECT id, name, starts_at, ends_at, finalized_at
+             FROM seasons
+             WHERE finalized_at IS NULL"
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(season.map(Season::from))
+    }
+
+    async fn update_open_season(
         &self,
+        update: &SeasonUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<Season> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let season = sqlx::query_as!(
+            SeasonDb,
+            "UPDATE seasons
+             SET name = COALESCE($1, name), ends_at = COALESCE($2, ends_at)
+             WHERE finalized_at IS NULL
+             RETURNING id, name, starts_at, ends_at, finalized_at",
+            update.name,
+            update.ends_at
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, &audit.with_entity_id(season.id)).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(season.into())
+    }
+
+    async fn finalize_open_season(
+        &self,
+        next: &NewSeason,
+        audit: Option<&NewAdminAuditEntry>,
+    ) -> AppResult<SeasonRollover> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        sqlx::query!("LOCK TABLE leaderboard_entries IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        let finalized = sqlx::query_as!(
+            SeasonDb,
+            "UPDATE seasons
+             SET finalized_at = NOW(), ends_at = LEAST(COALESCE(ends_at, NOW()), NOW())
+             WHERE finalized_at IS NULL
+             RETURNING id, name, starts_at, ends_at, finalized_at"
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::NotFound("No open season".to_string()))?;
+
+        let archived_entries = sqlx::query!(
+            "INSERT INTO season_standings (season_id, beta_applicant_id, public_key, total_score, rank)
+             SELECT $1, beta_applicant_id, public_key, total_score, rank
+             FROM leaderboard_entries",
+            finalized.id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?
+        .rows_affected();
+
+        let next = sqlx::query_as!(
+            SeasonDb,
+            "INSERT INTO seasons (name, starts_at, ends_at)
+             VALUES ($1, NOW(), $2)
+             RETURNING id, name, starts_at, ends_at, finalized_at",
+            next.name,
+            next.ends_at
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        // Same two-phase re-rank as the daily refresh, see `temporary_rank`.
+        sqlx::query!(
+            "UPDATE leaderboard_entries
+             SET total_score = 0, previous_rank = NULL, rank = -beta_applicant_id, updated_at = NOW()"
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query!(
+            r#"
+            UPDATE leaderboard_entries
+            SET rank = fresh_ranks.new_rank
+            FROM (
+                SELECT le.beta_applicant_id,
+                       ROW_NUMBER() OVER (ORDER BY ba.created_at ASC, ba.id ASC)::INTEGER AS new_rank
+                FROM leaderboard_entries le
+                JOIN beta_applicants ba ON le.beta_applicant_id = ba.id
+            ) fresh_ranks
+            WHERE leaderboard_entries.beta_applicant_id = fresh_ranks.beta_applicant_id
+            "#
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        if let Some(audit) = audit {
+            insert_audit_entry(&mut tx, &audit.with_entity_id(finalized.id)).await?;
+        }
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(SeasonRollover {
+            finalized: finalized.into(),
+            next: next.into(),
+            archived_entries,
+        })
+    }
+
+    async fn get_season_entries(
+        &self,
+        season_id: i32,
+        limit: u32,
+        offset: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>> {
+        let entries = sqlx::query!(
+            "SELECT public_key, total_score, rank, created_at
+             FROM season_standings
+             WHERE season_id = $1
+             ORDER BY rank
+             LIMIT $2 OFFSET $3",
+            season_id,
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| LeaderboardEntry {
+                public_key: entry.public_key,
+                total_score: entry.total_score,
+                score: None,
+                rank: entry.rank as u32,
+                previous_rank: None,
+                created_at: entry.created_at,
+            })
+            .collect())
+    }
+
+    async fn get_season_total(&self, season_id: i32) -> AppResult<u32> {
+        let count = sqlx::query_scalar!(
+            "SELECT COUNT(*) FROM season_standings WHERE season_id = $1",
+            season_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(count.unwrap_or(0) as u32)
+    }
+
+    async fn get_user_season_entry(
+        &self,
+        season_id: i32,
         public_key: &str,
     ) -> AppResult<Option<LeaderboardEntry>> {
-        let result = sqlx::query_as!(
+        let entry = sqlx::query!(
+            "SELECT public_key, total_score, rank, created_at
+             FROM season_standings
+             WHERE season_id = $1 AND public_key = $2",
+            season_id,
+            public_key
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(entry.map(|entry| LeaderboardEntry {
+            public_key: entry.public_key,
+            total_score: entry.total_score,
+            score: None,
+            rank: entry.rank as u32,
+            previous_rank: None,
+            created_at: entry.created_at,
+        }))
+    }
+
+    async fn get_rank_history(
+        &self,
+        public_key: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AppResult<Vec<LeaderboardSnapshot>> {
+        let snapshots = sqlx::query!(
+            "SELECT ls.rank, ls.total_score, ls.taken_at
+             FROM leaderboard_snapshots ls
+             JOIN beta_applicants ba ON ls.beta_applicant_id = ba.id
+             WHERE ba.public_key = $1 AND ls.taken_at >= $2 AND ls.taken_at <= $3
+             ORDER BY ls.taken_at",
+            public_key,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(snapshots
+            .into_iter()
+            .map(|snapshot| LeaderboardSnapshot {
+                rank: snapshot.rank as u32,
+                total_score: snapshot.total_score,
+                taken_at: snapshot.taken_at,
+            })
+            .collect())
+    }
+
+    async fn get_top_movers(
+        &self,
+        since: DateTime<Utc>,
+        direction: MoverDirection,
+        limit: u32,
+    ) -> AppResult<Vec<RankMovement>> {
+        // Fallers are ordered by a negated change so one query serves both directions.
+        let sign = match direction {
+            MoverDirection::Climbers => 1,
+            MoverDirection::Fallers => -1,
+        };
+
+        let movements = sqlx::query!(
+            r#"
+            WITH baseline AS (
+                SELECT DISTINCT ON (beta_applicant_id) beta_applicant_id, rank
+                FROM leaderboard_snapshots
+                WHERE taken_at >= $1
+                  AND season_id = (SELECT id FROM seasons WHERE finalized_at IS NULL)
+                ORDER BY beta_applicant_id, taken_at ASC
+            )
+            SELECT le.public_key,
+                   le.rank,
+                   le.total_score,
+                   b.rank AS baseline_rank,
+                   (b.rank - le.rank) AS "change!"
+            FROM leaderboard_entries le
+            JOIN baseline b ON b.beta_applicant_id = le.beta_applicant_id
+            WHERE (b.rank - le.rank) * $2 > 0
+            ORDER BY (b.rank - le.rank) * $2 DESC, le.rank
+            LIMIT $3
+            "#,
+            since,
+            sign,
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(movements
+            .into_iter()
+            .map(|movement| RankMovement {
+                public_key: movement.public_key,
+                rank: movement.rank as u32,
+                total_score: movement.total_score,
+                baseline_rank: movement.baseline_rank as u32,
+                change: movement.change,
+            })
+            .collect())
+    }
+
+    async fn get_kind_entries(
+        &self,
+        kind: &LeaderboardKind,
+        limit: u32,
+        offset: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>> {
+        let (badge_group_id, event_type_id) = kind_filters(kind);
+        // Badge scores are whole numbers; only signals carry an exact score
+        let is_signal = matches!(kind, LeaderboardKind::Signal(_));
+
+        let entries = sqlx::query_as!(
             RealtimeLeaderboardEntryDb,
             r#"
-            WITH ranked_users AS (
-                SELECT 
-                    ba.public_key,
-                    ba.created_at,
-                    COALESCE(SUM(b.score), 0)::INTEGER as total_score,
-                    ROW_NUMBER() OVER (ORDER BY COALESCE(SUM(b.score), 0) DESC, ba.created_at ASC) as rank
+            WITH scores AS (
+                SELECT ba.id, ba.public_key, ba.created_at, SUM(b.score)::NUMERIC AS score
                 FROM beta_applicants ba
-                LEFT JOIN beta_applicant_badges bab ON ba.id = bab.beta_applicant_id  
-                LEFT JOIN badges b ON bab.badge_id = b.id
+                JOIN beta_applicant_badges bab ON ba.id = bab.beta_applicant_id
+                JOIN badges b ON bab.badge_id = b.id
+                WHERE EXISTS (
+                    SELECT 1 FROM badge_group_conjunctions bgc
+                    WHERE bgc.badge_id = b.id AND bgc.badge_group_id = $1
+                )
                 GROUP BY ba.id, ba.public_key, ba.created_at
+                UNION ALL
+                SELECT ba.id, ba.public_key, ba.created_at, bap.progress_count
+                FROM beta_applicants ba
+                JOIN beta_applicant_progressions bap ON ba.id = bap.beta_applicant_id
+                WHERE bap.progression_event_type_id = $2
             )
-            SELECT public_key, created_at, total_score, rank 
-            FROM ranked_users 
-            WHERE public_key = $1
+            -- Ranked on the exact balance; total_score is its whole part, capped to fit
+            SELECT public_key, created_at, score,
+                   LEAST(FLOOR(score), 2147483647)::INTEGER AS total_score,
+                   ROW_NUMBER() OVER (ORDER BY score DESC, created_at ASC, id ASC) AS rank
+            FROM scores
+            WHERE score > 0
+            ORDER BY rank
+            LIMIT $3 OFFSET $4
             "#,
+            badge_group_id,
+            event_type_id,
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| LeaderboardEntry {
+                public_key: entry.public_key.unwrap_or_default(),
+                total_score: entry.total_score.unwrap_or(0),
+                score: entry.score.filter(|_| is_signal),
+                rank: entry.rank.unwrap_or(0) as u32,
+                previous_rank: None,
+                created_at: entry.created_at.unwrap_or_else(chrono::Utc::now),
+            })
+            .collect())
+    }
+
+    async fn get_kind_total(&self, kind: &LeaderboardKind) -> AppResult<u32> {
+        let (badge_group_id, event_type_id) = kind_filters(kind);
+
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) FROM (
+                SELECT ba.id
+                FROM beta_applicants ba
+                JOIN beta_applicant_badges bab ON ba.id = bab.beta_applicant_id
+                JOIN badges b ON bab.badge_id = b.id
+                WHERE EXISTS (
+                    SELECT 1 FROM badge_group_conjunctions bgc
+                    WHERE bgc.badge_id = b.id AND bgc.badge_group_id = $1
+                )
+                GROUP BY ba.id
+                HAVING SUM(b.score) > 0
+                UNION ALL
+                SELECT bap.beta_applicant_id
+                FROM beta_applicant_progressions bap
+                WHERE bap.progression_event_type_id = $2 AND bap.progress_count > 0
+            ) ranked
+            "#,
+            badge_group_id,
+            event_type_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(count.unwrap_or(0) as u32)
+    }
+
+    async fn get_user_kind_entry(
+        &self,
+        kind: &LeaderboardKind,
+        public_key: &str,
+    ) -> AppResult<Option<LeaderboardEntry>> {
+        let (badge_group_id, event_type_id) = kind_filters(kind);
+        // Badge scores are whole numbers; only signals carry an exact score
+        let is_signal = matches!(kind, LeaderboardKind::Signal(_));
+
+        let entry = sqlx::query_as!(
+            RealtimeLeaderboardEntryDb,
+            r#"
+            WITH scores AS (
+                SELECT ba.id, ba.public_key, ba.created_at, SUM(b.score)::NUMERIC AS score
+                FROM beta_applicants ba
+                JOIN beta_applicant_badges bab ON ba.id = bab.beta_applicant_id
+                JOIN badges b ON bab.badge_id = b.id
+                WHERE EXISTS (
+                    SELECT 1 FROM badge_group_conjunctions bgc
+                    WHERE bgc.badge_id = b.id AND bgc.badge_group_id = $1
+                )
+                GROUP BY ba.id, ba.public_key, ba.created_at
+                UNION ALL
+                SELECT ba.id, ba.public_key, ba.created_at, bap.progress_count
+                FROM beta_applicants ba
+                JOIN beta_applicant_progressions bap ON ba.id = bap.beta_applicant_id
+                WHERE bap.progression_event_type_id = $2
+            ),
+            ranked AS (
+                SELECT public_key, created_at, score,
+                       ROW_NUMBER() OVER (ORDER BY score DESC, created_at ASC, id ASC) AS rank
+                FROM scores
+                WHERE score > 0
+            )
+            SELECT public_key, created_at, score,
+                   LEAST(FLOOR(score), 2147483647)::INTEGER AS total_score, rank
+            FROM ranked
+            WHERE public_key = $3
+            "#,
+            badge_group_id,
+            event_type_id,
             public_key
         )
         .fetch_optional(&self.pool)
         .await
         .map_err(AppError::from)?;
 
-        Ok(result.map(|entry| LeaderboardEntry {
+        Ok(entry.map(|entry| LeaderboardEntry {
             public_key: entry.public_key.unwrap_or_default(),
             total_score: entry.total_score.unwrap_or(0),
+            score: entry.score.filter(|_| is_signal),
             rank: entry.rank.unwrap_or(0) as u32,
-            previous_rank: None, // Real-time queries don't have previous rank data
-            created_at: entry.created_at.unwrap_or_else(|| chrono::Utc::now()),
+            previous_rank: None,
+            created_at: entry.created_at.unwrap_or_else(chrono::Utc::now),
         }))
     }
 
-    async fn add_new_user_to_leaderboard(
+    async fn get_leaderboard_entries_after(
         &self,
-        beta_applicant_id: i32,
-        public_key: &str,
-    ) -> AppResult<()> {
-        sqlx::query!(
-            "INSERT INTO leaderboard_entries 
-             (beta_applicant_id, public_key, total_score, rank, previous_rank, created_at, updated_at)
-             VALUES ($1, $2, 0, (SELECT COALESCE(MAX(rank), 0) + 1 FROM leaderboard_entries), NULL, NOW(), NOW())",
-            beta_applicant_id,
-            public_key
+        after_rank: u32,
+        limit: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>> {
+        let entries = sqlx::query_as!(
+            LeaderboardEntryDb,
+            "SELECT public_key, total_score, rank, previous_rank, created_at
+             FROM leaderboard_entries
+             WHERE rank > $1
+             ORDER BY rank
+             LIMIT $2",
+            after_rank as i32,
+            limit as i64
         )
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await
         .map_err(AppError::from)?;
 
-        Ok(())
+        Ok(entries.into_iter().map(LeaderboardEntry::from).collect())
+    }
+
+    async fn get_leaderboard_entries_before(
+        &self,
+        before_rank: u32,
+        limit: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>> {
+        let mut entries = sqlx::query_as!(
+            LeaderboardEntryDb,
+            "SELECT public_key, total_score, rank, previous_rank, created_at
+             FROM leaderboard_entries
+             WHERE rank > 0 AND rank < $1
+             ORDER BY rank DESC
+             LIMIT $2",
+            before_rank as i32,
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        entries.reverse();
+        Ok(entries.into_iter().map(LeaderboardEntry::from).collect())
+    }
+
+    async fn get_leaderboard_entries_between(
+        &self,
+        from_rank: u32,
+        to_rank: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>> {
+        let entries = sqlx::query_as!(
+            LeaderboardEntryDb,
+            "SELECT public_key, total_score, rank, previous_rank, created_at
+             FROM leaderboard_entries
+             WHERE rank BETWEEN $1 AND $2
+             ORDER BY rank",
+            from_rank as i32,
+            to_rank as i32
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(entries.into_iter().map(LeaderboardEntry::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::app_error::AppError;
+    use crate::entities::leaderboard_kind::LeaderboardKind;
+    use crate::entities::leaderboard_snapshot::MoverDirection;
+    use crate::entities::progression_event_type::ProgressionEventType;
+    use crate::use_cases::leaderboard::{LeaderboardPersistence, LeaderboardUseCases};
+    use chrono::{Duration, NaiveDate, Utc};
+    use rust_decimal::Decimal;
+    use sqlx::PgPool;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    async fn insert_applicant(pool: &PgPool, public_key: &str, badge_ids: &[i32]) -> i32 {
+        let id: i32 = sqlx::query_scalar(
+            "INSERT INTO beta_applicants (public_key, referral_code)
+             VALUES ($1, $1)
+             RETURNING id",
+        )
+        .bind(public_key)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        for badge_id in badge_ids {
+            sqlx::query(
+                "INSERT INTO beta_applicant_badges (beta_applicant_id, badge_id) VALUES ($1, $2)",
+            )
+            .bind(id)
+            .bind(badge_id)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+        id
+    }
+
+    /// `(public_key, total_score, rank, previous_rank)` ordered by rank.
+    async fn entries(pool: &PgPool) -> Vec<(String, i32, i32, Option<i32>)> {
+        sqlx::query_as(
+            "SELECT public_key, total_score, rank, previous_rank
+             FROM leaderboard_entries
+             ORDER BY rank",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn refresh_credits_each_day_once() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = db.persistence();
+        let (badge_id, badge_score): (i32, i32) =
+            sqlx::query_as("SELECT id, score FROM badges WHERE score > 0 ORDER BY id LIMIT 1")
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        insert_applicant(&db.pool, "first", &[]).await;
+        insert_applicant(&db.pool, "second", &[badge_id]).await;
+        let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        assert!(
+            persistence
+                .refresh_daily(Some(day), None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        let credited = entries(&db.pool).await;
+        assert_eq!(
+            credited,
+            vec![
+                ("second".to_string(), badge_score, 1, None),
+                ("first".to_string(), 0, 2, None),
+            ]
+        );
+
+        assert!(
+            persistence
+                .refresh_daily(Some(day), None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(entries(&db.pool).await, credited);
+
+        let next_day = day.succ_opt().unwrap();
+        assert!(
+            persistence
+                .refresh_daily(Some(next_day), None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            entries(&db.pool).await,
+            vec![
+                ("second".to_string(), 2 * badge_score, 1, Some(1)),
+                ("first".to_string(), 0, 2, Some(2)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reranking_leaves_the_day_to_be_credited() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = db.persistence();
+        let (badge_id, badge_score): (i32, i32) =
+            sqlx::query_as("SELECT id, score FROM badges WHERE score > 0 ORDER BY id LIMIT 1")
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        insert_applicant(&db.pool, "first", &[]).await;
+        insert_applicant(&db.pool, "second", &[badge_id]).await;
+        let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        // An admin re-rank before the badge sync ranks on the stored scores only
+        assert!(
+            persistence
+                .refresh_daily(None, None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            entries(&db.pool).await,
+            vec![
+                ("first".to_string(), 0, 1, None),
+                ("second".to_string(), 0, 2, None),
+            ]
+        );
+
+        assert!(
+            persistence
+                .refresh_daily(Some(day), None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            entries(&db.pool).await,
+            vec![
+                ("second".to_string(), badge_score, 1, Some(2)),
+                ("first".to_string(), 0, 2, Some(1)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn finalizing_a_due_season_archives_standings_and_starts_fresh() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let leaderboard_use_cases = LeaderboardUseCases::new(
+            persistence.clone(),
+            Duration::seconds(60),
+            persistence.clone(),
+        );
+        let (badge_id, badge_score): (i32, i32) =
+            sqlx::query_as("SELECT id, score FROM badges WHERE score > 0 ORDER BY id LIMIT 1")
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        insert_applicant(&db.pool, "first", &[]).await;
+        insert_applicant(&db.pool, "second", &[badge_id]).await;
+        let today = Utc::now().date_naive();
+        persistence.refresh_daily(Some(today), None).await.unwrap();
+
+        assert!(
+            leaderboard_use_cases
+                .finalize_due_season()
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let season = persistence.read_open_season().await.unwrap().unwrap();
+        sqlx::query("UPDATE seasons SET ends_at = NOW() - INTERVAL '1 minute' WHERE id = $1")
+            .bind(season.id)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        let rollover = leaderboard_use_cases
+            .finalize_due_season()
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(rollover.finalized.id, season.id);
+        assert!(!rollover.finalized.is_open());
+        assert_eq!(rollover.archived_entries, 2);
+        assert_eq!(
+            persistence.read_open_season().await.unwrap().unwrap().id,
+            rollover.next.id
+        );
+        let archived = persistence
+            .get_season_entries(season.id, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(
+            archived
+                .iter()
+                .map(|entry| (entry.public_key.as_str(), entry.total_score, entry.rank))
+                .collect::<Vec<_>>(),
+            vec![("second", badge_score, 1), ("first", 0, 2)]
+        );
+
+        // The new season starts from zero, ranked by signup, and may be credited today
+        assert_eq!(
+            entries(&db.pool).await,
+            vec![
+                ("first".to_string(), 0, 1, None),
+                ("second".to_string(), 0, 2, None),
+            ]
+        );
+        assert!(
+            persistence
+                .refresh_daily(Some(today), None)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            persistence
+                .get_user_season_entry(season.id, "second")
+                .await
+                .unwrap()
+                .map(|entry| entry.total_score),
+            Some(badge_score)
+        );
+    }
+
+    #[tokio::test]
+    async fn movers_are_measured_from_the_first_snapshot_in_the_window() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = Arc::new(db.persistence());
+        let leaderboard_use_cases = LeaderboardUseCases::new(
+            persistence.clone(),
+            Duration::seconds(60),
+            persistence.clone(),
+        );
+        let badge_id: i32 =
+            sqlx::query_scalar("SELECT id FROM badges WHERE score > 0 ORDER BY id LIMIT 1")
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        insert_applicant(&db.pool, "first", &[]).await;
+        insert_applicant(&db.pool, "second", &[]).await;
+        let third = insert_applicant(&db.pool, "third", &[]).await;
+        let today = Utc::now().date_naive();
+
+        persistence
+            .refresh_daily(Some(today - Duration::days(1)), None)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE leaderboard_snapshots SET taken_at = NOW() - INTERVAL '2 days'")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO beta_applicant_badges (beta_applicant_id, badge_id) VALUES ($1, $2)",
+        )
+        .bind(third)
+        .bind(badge_id)
+        .execute(&db.pool)
+        .await
+        .unwrap();
+        persistence.refresh_daily(Some(today), None).await.unwrap();
+
+        let movements = |direction| {
+            let leaderboard_use_cases = &leaderboard_use_cases;
+            async move {
+                leaderboard_use_cases
+                    .get_top_movers(direction, 7, 10)
+                    .await
+                    .unwrap()
+                    .into_iter()
+                    .map(|movement| (movement.public_key, movement.baseline_rank, movement.change))
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(
+            movements(MoverDirection::Climbers).await,
+            vec![("third".to_string(), 3, 2)]
+        );
+        assert_eq!(
+            movements(MoverDirection::Fallers).await,
+            vec![("first".to_string(), 1, -1), ("second".to_string(), 2, -1)]
+        );
+
+        // Within the last day the only snapshot is the current standing
+        assert!(
+            leaderboard_use_cases
+                .get_top_movers(MoverDirection::Climbers, 1, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            leaderboard_use_cases
+                .get_top_movers(MoverDirection::Climbers, 0, 10)
+                .await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        let history = leaderboard_use_cases
+            .get_rank_history("third", Utc::now() - Duration::days(7), Utc::now())
+            .await
+            .unwrap();
+        assert_eq!(
+            history
+                .iter()
+                .map(|snapshot| snapshot.rank)
+                .collect::<Vec<_>>(),
+            vec![3, 1]
+        );
+    }
+
+    #[tokio::test]
+    async fn signal_boards_rank_and_report_the_exact_score() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = db.persistence();
+        let signal = ProgressionEventType::JupStaked;
+        for (public_key, progress) in [("first", "1.25"), ("second", "1.75"), ("third", "0")] {
+            let id = insert_applicant(&db.pool, public_key, &[]).await;
+            sqlx::query(
+                "INSERT INTO beta_applicant_progressions
+                     (beta_applicant_id, progression_event_type_id, progress_count)
+                 VALUES ($1, $2, $3::NUMERIC)",
+            )
+            .bind(id)
+            .bind(signal.id())
+            .bind(progress)
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        }
+        let kind = LeaderboardKind::Signal(signal);
+
+        let entries = persistence.get_kind_entries(&kind, 10, 0).await.unwrap();
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| (
+                    entry.public_key.as_str(),
+                    entry.rank,
+                    entry.total_score,
+                    entry.score
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("second", 1, 1, Some(Decimal::from_str("1.75").unwrap())),
+                ("first", 2, 1, Some(Decimal::from_str("1.25").unwrap())),
+            ]
+        );
+        let entry = persistence
+            .get_user_kind_entry(&kind, "first")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            (entry.rank, entry.score),
+            (2, Some(Decimal::from_str("1.25").unwrap()))
+        );
+        assert!(
+            persistence
+                .get_user_kind_entry(&kind, "third")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn user_standing_projects_the_rank_of_the_next_refresh() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let persistence = db.persistence();
+        let badge_id: i32 =
+            sqlx::query_scalar("SELECT id FROM badges WHERE score > 0 ORDER BY id LIMIT 1")
+                .fetch_one(&db.pool)
+                .await
+                .unwrap();
+        let public_keys = ["first", "second", "third", "fourth", "fifth"];
+        let mut ids = Vec::new();
+        for (index, public_key) in public_keys.iter().enumerate() {
+            let badges = if index % 2 == 0 {
+                vec![badge_id]
+            } else {
+                vec![]
+            };
+            ids.push(insert_applicant(&db.pool, public_key, &badges).await);
+        }
+        // Equal signup times leave the id as the only tie-break
+        sqlx::query("UPDATE beta_applicants SET created_at = '2026-01-01T00:00:00Z'")
+            .execute(&db.pool)
+            .await
+            .unwrap();
+        let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        persistence.refresh_daily(Some(day), None).await.unwrap();
+        sqlx::query(
+            "INSERT INTO beta_applicant_badges (beta_applicant_id, badge_id) VALUES ($1, $2)",
+        )
+        .bind(ids[3])
+        .bind(badge_id)
+        .execute(&db.pool)
+        .await
+        .unwrap();
+
+        let mut projected = Vec::new();
+        for public_key in public_keys {
+            let standing = persistence
+                .get_user_standing(public_key)
+                .await
+                .unwrap()
+                .unwrap();
+            projected.push((
+                public_key.to_string(),
+                standing.projected_rank as i32,
+                standing.projected_score,
+            ));
+        }
+        let mut ranked: Vec<(String, i32, i32)> = persistence
+            .refresh_daily(Some(day.succ_opt().unwrap()), None)
+            .await
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|entry| (entry.public_key, entry.rank, entry.total_score))
+            .collect();
+        ranked
+            .sort_by_key(|(public_key, _, _)| public_keys.iter().position(|key| key == public_key));
+
+        assert_eq!(projected, ranked);
     }
 }
@@ -1,13 +1,22 @@
 use crate::app_error::AppError;
 use sqlx::{Error, PgPool};
 
+mod admin;
+mod auth_nonce;
+mod auth_session;
 mod badge;
 mod badge_group;
 mod beta_applicant;
 mod beta_applicant_badge;
 mod beta_applicant_progression;
 mod cat;
+mod event;
+pub mod in_memory;
+mod job_run;
 mod leaderboard;
+#[cfg(test)]
+pub mod test_support;
+mod tracked_token;
 
 #[derive(Clone, Debug)]
 pub struct PostgresPersistence {
@@ -24,6 +33,9 @@ impl From<sqlx::Error> for AppError {
     fn from(value: Error) -> Self {
         match value {
             sqlx::Error::RowNotFound => AppError::NotFound("Resource not found".to_string()),
+            sqlx::Error::Database(ref db_err) if db_err.is_foreign_key_violation() => {
+                AppError::BadRequest("Referenced resource does not exist".to_string())
+            }
             _ => AppError::Database(value.to_string()),
         }
     }
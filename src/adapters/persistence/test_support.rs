@@ -0,0 +1,118 @@
+use crate::adapters::persistence::PostgresPersistence;
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use sqlx::migrate::Migrator;
+use sqlx::postgres::PgPoolOptions;
+use sqlx::{Connection, PgConnection, PgPool};
+use std::env;
+
+/// Empty, fully migrated database for a single test, dropped again when the value goes out of
+/// scope. Tests that need one are skipped unless `TEST_DATABASE_URL` points at a server where
+/// the user may create databases.
+pub struct TestDatabase {
+    pub pool: PgPool,
+    server_url: String,
+    name: String,
+}
+
+impl TestDatabase {
+    pub async fn create() -> Option<Self> {
+        let database = Self::create_empty().await?;
+        database.migrate().await;
+        Some(database)
+    }
+
+    /// Only runs the migrations older than `version`, so that a test can add the data a later
+    /// migration converts and then run the rest with [`TestDatabase::migrate`].
+    pub async fn create_migrated_before(version: i64) -> Option<Self> {
+        let database = Self::create_empty().await?;
+        let migrator = migrator();
+        let earlier = Migrator {
+            migrations: migrator
+                .iter()
+                .filter(|migration| migration.version < version)
+                .cloned()
+                .collect::<Vec<_>>()
+                .into(),
+            ..migrator
+        };
+        earlier.run(&database.pool).await.unwrap();
+        Some(database)
+    }
+
+    pub async fn migrate(&self) {
+        migrator().run(&self.pool).await.unwrap();
+    }
+
+    async fn create_empty() -> Option<Self> {
+        let Ok(server_url) = env::var("TEST_DATABASE_URL") else {
+            eprintln!("TEST_DATABASE_URL is not set, skipping database test");
+            return None;
+        };
+
+        let suffix: String = rand::rng()
+            .sample_iter(&Alphanumeric)
+            .take(12)
+            .map(|c| char::from(c).to_ascii_lowercase())
+            .collect();
+        let name = format!("catalytics_test_{}", suffix);
+
+        let mut connection = PgConnection::connect(&server_url).await.unwrap();
+        sqlx::query(&format!("CREATE DATABASE {}", name))
+            .execute(&mut connection)
+            .await
+            .unwrap();
+        connection.close().await.unwrap();
+
+        // Built before migrating, so that the database is dropped even if a migration fails
+        Some(Self {
+            pool: PgPoolOptions::new()
+                .max_connections(8)
+                .connect_lazy(&database_url(&server_url, &name))
+                .unwrap(),
+            server_url,
+            name,
+        })
+    }
+
+    pub fn persistence(&self) -> PostgresPersistence {
+        PostgresPersistence::new(self.pool.clone())
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        let server_url = self.server_url.clone();
+        let name = self.name.clone();
+        // The test's runtime may already be shutting down, so the drop gets its own
+        let _ = std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap()
+                .block_on(async {
+                    let mut connection = PgConnection::connect(&server_url).await?;
+                    sqlx::query(&format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", name))
+                        .execute(&mut connection)
+                        .await?;
+                    connection.close().await
+                })
+        })
+        .join();
+    }
+}
+
+fn migrator() -> Migrator {
+    sqlx::migrate!("./migrations")
+}
+
+/// Points `server_url` at another database, keeping credentials and query parameters.
+fn database_url(server_url: &str, name: &str) -> String {
+    let (base, query) = match server_url.split_once('?') {
+        Some((base, query)) => (base, format!("?{}", query)),
+        None => (server_url, String::new()),
+    };
+    let (scheme, rest) = base.split_once("://").unwrap_or(("postgres", base));
+    let authority = rest.split('/').next().unwrap_or(rest);
+    format!("{}://{}/{}{}", scheme, authority, name, query)
+}
@@ -0,0 +1,238 @@
+use crate::adapters::persistence::PostgresPersistence;
+use crate::adapters::persistence::admin::insert_audit_entry;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::tracked_token::{NewTrackedToken, TrackedToken, TrackedTokenUpdate};
+use crate::use_cases::tracked_token::TrackedTokenPersistence;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct TrackedTokenDb {
+    pub id: i32,
+    pub mint: String,
+    pub symbol: String,
+    pub decimals: i32,
+    pub kind: String,
+    pub progression_event_type_id: i32,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<TrackedTokenDb> for TrackedToken {
+    type Error = AppError;
+
+    fn try_from(token: TrackedTokenDb) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: token.id,
+            mint: token.mint,
+            symbol: token.symbol,
+            decimals: token.decimals,
+            kind: token.kind.parse().map_err(AppError::Internal)?,
+            signal: ProgressionEventType::from_id(token.progression_event_type_id).ok_or_else(
+                || {
+                    AppError::Internal(format!(
+                        "Invalid progression event type {}",
+                        token.progression_event_type_id
+                    ))
+                },
+            )?,
+            enabled: token.enabled,
+            created_at: token.created_at,
+        })
+    }
+}
+
+fn map_unique_violation(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::BadRequest("This token is already tracked".to_string())
+        }
+        _ => AppError::from(e),
+    }
+}
+
+#[async_trait]
+impl TrackedTokenPersistence for PostgresPersistence {
+    async fn read_tracked_tokens(&self) -> AppResult<Vec<TrackedToken>> {
+        sqlx::query_as!(
+            TrackedTokenDb,
+            "SELECT id, mint, symbol, decimals, kind, progression_event_type_id, enabled, created_at
+             FROM tracked_tokens
+             ORDER BY id"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?
+        .into_iter()
+        .map(TrackedToken::try_from)
+        .collect()
+    }
+
+    async fn insert_tracked_token(
+        &self,
+        token: &NewTrackedToken,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<TrackedToken> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        // The signal is named after its id, which is only known after the insert
+        let event_type_id = sqlx::query_scalar!(
+            "INSERT INTO progression_event_types (event_type) VALUES ('') RETURNING id"
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+        let signal_name = ProgressionEventType::Other(event_type_id).name();
+
+        sqlx::query!(
+            "UPDATE progression_event_types SET event_type = $2 WHERE id = $1",
+            event_type_id,
+            signal_name.as_ref()
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let inserted = sqlx::query_as!(
+            TrackedTokenDb,
+            "INSERT INTO tracked_tokens (mint, symbol, decimals, kind, progression_event_type_id)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING id, mint, symbol, decimals, kind, progression_event_type_id, enabled, created_at",
+            token.mint.trim(),
+            token.symbol.trim(),
+            token.decimals,
+            token.kind.as_str(),
+            event_type_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(map_unique_violation)?;
+
+        insert_audit_entry(&mut tx, &audit.with_entity_id(inserted.id)).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        inserted.try_into()
+    }
+
+    async fn seed_tracked_token(
+        &self,
+        token: &NewTrackedToken,
+        signal: ProgressionEventType,
+    ) -> AppResult<bool> {
+        let inserted = sqlx::query!(
+            "INSERT INTO tracked_tokens (mint, symbol, decimals, kind, progression_event_type_id)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT DO NOTHING",
+            token.mint.trim(),
+            token.symbol.trim(),
+            token.decimals,
+            token.kind.as_str(),
+            signal.id()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(inserted.rows_affected() > 0)
+    }
+
+    async fn update_tracked_token(
+        &self,
+        id: i32,
+        update: &TrackedTokenUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<TrackedToken> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let updated = sqlx::query_as!(
+            TrackedTokenDb,
+            "UPDATE tracked_tokens
+             SET symbol = COALESCE($2, symbol),
+                 decimals = COALESCE($3, decimals),
+                 enabled = COALESCE($4, enabled)
+             WHERE id = $1
+             RETURNING id, mint, symbol, decimals, kind, progression_event_type_id, enabled, created_at",
+            id,
+            update.symbol.as_deref().map(str::trim),
+            update.decimals,
+            update.enabled
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        insert_audit_entry(&mut tx, audit).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        updated.try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::persistence::test_support::TestDatabase;
+    use crate::app_error::AppError;
+    use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+    use crate::entities::tracked_token::{NewTrackedToken, TokenKind};
+    use crate::infrastructure::wallet_holdings::JUP_MINT;
+    use crate::use_cases::tracked_token::TrackedTokenUseCases;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    const BONK_MINT: &str = "DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263";
+
+    fn audit() -> NewAdminAuditEntry {
+        NewAdminAuditEntry {
+            admin_public_key: "admin".to_string(),
+            action: "create".to_string(),
+            entity_type: "token".to_string(),
+            entity_id: None,
+            payload: json!({}),
+        }
+    }
+
+    fn token(mint: &str, kind: TokenKind) -> NewTrackedToken {
+        NewTrackedToken {
+            mint: mint.to_string(),
+            symbol: "TOKEN".to_string(),
+            decimals: 5,
+            kind,
+        }
+    }
+
+    #[tokio::test]
+    async fn only_valid_mints_and_the_supported_staked_mint_are_tracked() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let use_cases = TrackedTokenUseCases::new(Arc::new(db.persistence()), JUP_MINT.to_string());
+
+        for mint in [
+            "",
+            "not a mint",
+            "0OIl",
+            "3yFwqXBfZY4jBVUafQ1YEXw189y2dN3V5KQq9uzBDy",
+        ] {
+            assert!(matches!(
+                use_cases
+                    .create(token(mint, TokenKind::Held), &audit())
+                    .await,
+                Err(AppError::BadRequest(_))
+            ));
+        }
+        assert!(matches!(
+            use_cases
+                .create(token(BONK_MINT, TokenKind::Staked), &audit())
+                .await,
+            Err(AppError::BadRequest(_))
+        ));
+
+        let held = use_cases
+            .create(token(BONK_MINT, TokenKind::Held), &audit())
+            .await
+            .unwrap();
+        assert_eq!(held.mint, BONK_MINT);
+    }
+}
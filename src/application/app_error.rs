@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum AppError {
     #[error("Database error: {0}")]
     Database(String),
@@ -10,6 +10,37 @@ pub enum AppError {
 
     #[error("Not found: {0}")]
     NotFound(String),
+
+    #[error("Bad request: {0}")]
+    BadRequest(String),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// Carries the number of seconds until the request may be retried.
+    #[error("Rate limited: retry in {0} seconds")]
+    RateLimited(i64),
+
+    #[error("Upstream error from {provider}: {message}")]
+    Upstream { provider: String, message: String },
+}
+
+impl AppError {
+    /// What users may see. Database, internal and upstream details only go to the logs.
+    pub fn public_message(&self) -> String {
+        match self {
+            AppError::Database(_) => "Database exception".to_string(),
+            AppError::Internal(_) => "Internal server error".to_string(),
+            AppError::NotFound(_) => "Resource not found".to_string(),
+            AppError::BadRequest(message) | AppError::Conflict(message) => message.clone(),
+            AppError::RateLimited(seconds) => {
+                format!("Too many requests, retry in {} seconds", seconds.max(&1))
+            }
+            AppError::Upstream { provider, .. } => {
+                format!("Upstream provider {} is unavailable", provider)
+            }
+        }
+    }
 }
 
 pub type AppResult<T> = Result<T, AppError>;
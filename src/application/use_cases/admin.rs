@@ -0,0 +1,110 @@
+use crate::app_error::{AppError, AppResult};
+use crate::entities::admin::Admin;
+use crate::entities::admin_audit_entry::{AdminAuditEntry, NewAdminAuditEntry};
+use crate::use_cases::auth::validate_address;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait AdminPersistence: Send + Sync + Debug {
+    async fn is_admin(&self, public_key: &str) -> AppResult<bool>;
+    async fn read_admins(&self) -> AppResult<Vec<Admin>>;
+    async fn create_admin(&self, public_key: &str, audit: &NewAdminAuditEntry) -> AppResult<Admin>;
+    /// Returns whether the admin existed; the audit entry is only written if it did.
+    async fn delete_admin(&self, public_key: &str, audit: &NewAdminAuditEntry) -> AppResult<bool>;
+    async fn record_audit_entry(&self, entry: &NewAdminAuditEntry) -> AppResult<()>;
+    async fn read_audit_entries(&self, limit: u32, offset: u32) -> AppResult<Vec<AdminAuditEntry>>;
+}
+
+#[derive(Clone, Debug)]
+pub struct AdminUseCases {
+    persistence: Arc<dyn AdminPersistence>,
+    configured_admins: Vec<String>,
+}
+
+impl AdminUseCases {
+    pub fn new(persistence: Arc<dyn AdminPersistence>, configured_admins: Vec<String>) -> Self {
+        Self {
+            persistence,
+            configured_admins,
+        }
+    }
+
+    pub async fn is_admin(&self, public_key: &str) -> AppResult<bool> {
+        if self
+            .configured_admins
+            .iter()
+            .any(|admin| admin == public_key)
+        {
+            return Ok(true);
+        }
+        self.persistence.is_admin(public_key).await
+    }
+
+    /// Records an action that spans several transactions, such as a batch sync. Single
+    /// mutations pass their entry to persistence instead, which writes it in the same
+    /// transaction.
+    pub async fn record(
+        &self,
+        admin_public_key: &str,
+        action: &str,
+        entity_type: &str,
+        entity_id: Option<String>,
+        payload: Value,
+    ) -> AppResult<()> {
+        self.persistence
+            .record_audit_entry(&NewAdminAuditEntry {
+                admin_public_key: admin_public_key.to_string(),
+                action: action.to_string(),
+                entity_type: entity_type.to_string(),
+                entity_id,
+                payload,
+            })
+            .await
+    }
+
+    pub async fn read_audit_log(&self, page: u32, limit: u32) -> AppResult<Vec<AdminAuditEntry>> {
+        let offset = (page - 1) * limit;
+        self.persistence.read_audit_entries(limit, offset).await
+    }
+
+    pub async fn read_admins(&self) -> AppResult<Vec<Admin>> {
+        self.persistence.read_admins().await
+    }
+
+    pub async fn create_admin(&self, admin_public_key: &str, public_key: &str) -> AppResult<Admin> {
+        validate_address("publicKey", public_key)?;
+
+        let audit = admin_audit_entry(admin_public_key, "create", public_key);
+        self.persistence.create_admin(public_key, &audit).await
+    }
+
+    pub async fn delete_admin(&self, admin_public_key: &str, public_key: &str) -> AppResult<()> {
+        if admin_public_key == public_key {
+            return Err(AppError::BadRequest(
+                "Admins cannot remove themselves".to_string(),
+            ));
+        }
+
+        let audit = admin_audit_entry(admin_public_key, "delete", public_key);
+        if !self.persistence.delete_admin(public_key, &audit).await? {
+            return Err(AppError::NotFound(format!(
+                "Admin {} not found",
+                public_key
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn admin_audit_entry(admin_public_key: &str, action: &str, public_key: &str) -> NewAdminAuditEntry {
+    NewAdminAuditEntry {
+        admin_public_key: admin_public_key.to_string(),
+        action: action.to_string(),
+        entity_type: "admin".to_string(),
+        entity_id: Some(public_key.to_string()),
+        payload: json!({}),
+    }
+}
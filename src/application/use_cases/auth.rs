@@ -0,0 +1,247 @@
+use crate::app_error::{AppError, AppResult};
+use crate::entities::auth_nonce::{AuthNonce, NonceClaim};
+use crate::entities::auth_session::{AuthSession, AuthSessionPurge, IssuedSession};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use sha2::{Digest, Sha256};
+use std::fmt::Debug;
+use std::sync::Arc;
+use tracing::warn;
+
+const NONCE_LENGTH: usize = 32;
+/// Unused nonces a public key may hold at once. Sign-in needs one, so a few cover retries and
+/// several open tabs while keeping anonymous callers from filling the table.
+const MAX_OUTSTANDING_NONCES: i64 = 5;
+const TOKEN_LENGTH: usize = 48;
+/// Size of a Solana address, whether a wallet's public key or a token mint.
+const ADDRESS_BYTES: usize = 32;
+
+#[async_trait]
+pub trait AuthNoncePersistence: Send + Sync + Debug {
+    /// Stores the nonce unless its public key already has `max_outstanding` nonces that are
+    /// neither consumed nor expired.
+    async fn create_nonce(&self, nonce: &AuthNonce, max_outstanding: i64) -> AppResult<NonceClaim>;
+    /// Marks the nonce as used. Returns `false` if it does not exist, belongs to another
+    /// public key, has expired or was already consumed.
+    async fn consume_nonce(&self, public_key: &str, nonce: &str) -> AppResult<bool>;
+    /// Returns the number of nonces deleted.
+    async fn purge_expired_nonces(&self) -> AppResult<u64>;
+}
+
+#[async_trait]
+pub trait AuthSessionPersistence: Send + Sync + Debug {
+    async fn create_session(
+        &self,
+        public_key: &str,
+        access_token_hash: &str,
+        refresh_token_hash: &str,
+        access_expires_at: DateTime<Utc>,
+        refresh_expires_at: DateTime<Utc>,
+    ) -> AppResult<AuthSession>;
+    async fn read_active_session_by_access_token(
+        &self,
+        access_token_hash: &str,
+    ) -> AppResult<Option<AuthSession>>;
+    /// Swaps both token hashes of the session owning `refresh_token_hash`, so the presented
+    /// refresh token can never be used again. Returns `None` for unknown, expired or revoked
+    /// refresh tokens.
+    async fn rotate_session(
+        &self,
+        refresh_token_hash: &str,
+        access_token_hash: &str,
+        new_refresh_token_hash: &str,
+        access_expires_at: DateTime<Utc>,
+        refresh_expires_at: DateTime<Utc>,
+    ) -> AppResult<Option<AuthSession>>;
+    /// Revokes the session a refresh token was rotated away from. Returns `false` if the
+    /// token was never rotated or its session is already revoked.
+    async fn revoke_session_by_rotated_refresh_token(
+        &self,
+        refresh_token_hash: &str,
+    ) -> AppResult<bool>;
+    async fn revoke_session_by_access_token(&self, access_token_hash: &str) -> AppResult<bool>;
+    /// Deletes sessions that were revoked or whose refresh token expired, along with their
+    /// rotated refresh tokens, and rotated refresh tokens swapped out before `rotated_before`.
+    async fn purge_sessions(&self, rotated_before: DateTime<Utc>) -> AppResult<AuthSessionPurge>;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct AuthLifetimes {
+    pub nonce: Duration,
+    pub access_token: Duration,
+    pub refresh_token: Duration,
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthUseCases {
+    nonce_persistence: Arc<dyn AuthNoncePersistence>,
+    session_persistence: Arc<dyn AuthSessionPersistence>,
+    lifetimes: AuthLifetimes,
+}
+
+impl AuthUseCases {
+    pub fn new(
+        nonce_persistence: Arc<dyn AuthNoncePersistence>,
+        session_persistence: Arc<dyn AuthSessionPersistence>,
+        lifetimes: AuthLifetimes,
+    ) -> Self {
+        Self {
+            nonce_persistence,
+            session_persistence,
+            lifetimes,
+        }
+    }
+
+    pub async fn issue_nonce(&self, public_key: &str) -> AppResult<AuthNonce> {
+        let now = Utc::now();
+        let nonce = AuthNonce {
+            nonce: random_string(NONCE_LENGTH),
+            public_key: public_key.to_string(),
+            expires_at: now + self.lifetimes.nonce,
+            created_at: now,
+        };
+
+        match self
+            .nonce_persistence
+            .create_nonce(&nonce, MAX_OUTSTANDING_NONCES)
+            .await?
+        {
+            NonceClaim::Claimed => Ok(nonce),
+            NonceClaim::TooMany { retry_at } => {
+                Err(AppError::RateLimited((retry_at - now).num_seconds()))
+            }
+        }
+    }
+
+    /// Deletes expired nonces; the scheduler calls this instead of every request doing so.
+    pub async fn purge_expired_nonces(&self) -> AppResult<u64> {
+        self.nonce_persistence.purge_expired_nonces().await
+    }
+
+    /// Deletes sessions that can no longer be used. A rotated refresh token is kept for one
+    /// refresh lifetime, after which it would have expired anyway, so presenting it again
+    /// still revokes its session until then.
+    pub async fn purge_ended_sessions(&self) -> AppResult<AuthSessionPurge> {
+        self.session_persistence
+            .purge_sessions(Utc::now() - self.lifetimes.refresh_token)
+            .await
+    }
+
+    pub async fn consume_nonce(&self, public_key: &str, nonce: &str) -> AppResult<bool> {
+        self.nonce_persistence
+            .consume_nonce(public_key, nonce)
+            .await
+    }
+
+    /// Starts a session for a wallet whose signature has already been verified.
+    pub async fn create_session(&self, public_key: &str) -> AppResult<IssuedSession> {
+        let now = Utc::now();
+        let access_token = random_string(TOKEN_LENGTH);
+        let refresh_token = random_string(TOKEN_LENGTH);
+
+        let session = self
+            .session_persistence
+            .create_session(
+                public_key,
+                &hash_token(&access_token),
+                &hash_token(&refresh_token),
+                now + self.lifetimes.access_token,
+                now + self.lifetimes.refresh_token,
+            )
+            .await?;
+
+        Ok(IssuedSession {
+            public_key: session.public_key,
+            access_token,
+            refresh_token,
+            access_expires_at: session.access_expires_at,
+            refresh_expires_at: session.refresh_expires_at,
+        })
+    }
+
+    /// Swaps the refresh token for a new token pair. Presenting a refresh token that was
+    /// already swapped means it was copied, so the whole session is revoked.
+    pub async fn refresh_session(&self, refresh_token: &str) -> AppResult<Option<IssuedSession>> {
+        let now = Utc::now();
+        let refresh_token_hash = hash_token(refresh_token);
+        let access_token = random_string(TOKEN_LENGTH);
+        let new_refresh_token = random_string(TOKEN_LENGTH);
+
+        let session = self
+            .session_persistence
+            .rotate_session(
+                &refresh_token_hash,
+                &hash_token(&access_token),
+                &hash_token(&new_refresh_token),
+                now + self.lifetimes.access_token,
+                now + self.lifetimes.refresh_token,
+            )
+            .await?;
+
+        let Some(session) = session else {
+            if self
+                .session_persistence
+                .revoke_session_by_rotated_refresh_token(&refresh_token_hash)
+                .await?
+            {
+                warn!("Rotated refresh token was presented again, session revoked");
+            }
+            return Ok(None);
+        };
+
+        Ok(Some(IssuedSession {
+            public_key: session.public_key,
+            access_token,
+            refresh_token: new_refresh_token,
+            access_expires_at: session.access_expires_at,
+            refresh_expires_at: session.refresh_expires_at,
+        }))
+    }
+
+    /// Resolves an access token to the session it was issued for.
+    pub async fn authenticate_access_token(
+        &self,
+        access_token: &str,
+    ) -> AppResult<Option<AuthSession>> {
+        self.session_persistence
+            .read_active_session_by_access_token(&hash_token(access_token))
+            .await
+    }
+
+    pub async fn revoke_session(&self, access_token: &str) -> AppResult<bool> {
+        self.session_persistence
+            .revoke_session_by_access_token(&hash_token(access_token))
+            .await
+    }
+}
+
+/// Accepts only base58 encoded 32-byte Solana addresses, such as wallet public keys and mints.
+pub fn validate_address(field: &str, value: &str) -> AppResult<()> {
+    let is_address = bs58::decode(value)
+        .into_vec()
+        .is_ok_and(|bytes| bytes.len() == ADDRESS_BYTES);
+    if !is_address {
+        return Err(AppError::BadRequest(format!(
+            "{} must be a base58 encoded {}-byte address",
+            field, ADDRESS_BYTES
+        )));
+    }
+    Ok(())
+}
+
+fn random_string(length: usize) -> String {
+    rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
+
+fn hash_token(token: &str) -> String {
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
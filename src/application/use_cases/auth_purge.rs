@@ -0,0 +1,36 @@
+use crate::app_error::AppResult;
+use crate::use_cases::auth::AuthUseCases;
+use crate::use_cases::job::Job;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// Deletes expired sign-in nonces and ended sessions, so neither table grows with every
+/// sign-in.
+#[derive(Clone, Debug)]
+pub struct AuthPurgeJob {
+    auth_use_cases: Arc<AuthUseCases>,
+}
+
+impl AuthPurgeJob {
+    pub fn new(auth_use_cases: Arc<AuthUseCases>) -> Self {
+        Self { auth_use_cases }
+    }
+}
+
+#[async_trait]
+impl Job for AuthPurgeJob {
+    fn name(&self) -> &'static str {
+        "auth_purge"
+    }
+
+    async fn run(&self) -> AppResult<Value> {
+        let nonces = self.auth_use_cases.purge_expired_nonces().await?;
+        let sessions = self.auth_use_cases.purge_ended_sessions().await?;
+        Ok(json!({
+            "noncesPurged": nonces,
+            "sessionsPurged": sessions.sessions,
+            "rotatedTokensPurged": sessions.rotated_tokens,
+        }))
+    }
+}
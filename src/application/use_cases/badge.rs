@@ -1,34 +1,83 @@
-use crate::app_error::AppResult;
-use crate::entities::badge::{Badge, BadgeDto};
+use crate::app_error::{AppError, AppResult};
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::entities::badge::{Badge, BadgeDefinition, BadgeDto, BadgeUpdate, NewBadge};
 use crate::entities::badge_requirement::{BadgeRequirement, BadgeRequirementDto};
+use crate::entities::badge_rule::{BadgeRule, ProgressionSnapshot, unlocked_badge_ids};
 use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::user_event::{UserEvent, UserEventKind};
+use crate::use_cases::badge_group::{validate_text, validate_unique_ids};
+use crate::use_cases::event::{EventPublisher, publish_events};
 
 use async_trait::async_trait;
+use std::collections::HashSet;
 use std::fmt::Debug;
 use std::sync::Arc;
+use tracing::info;
 
 #[async_trait]
 pub trait BadgePersistence: Send + Sync + Debug {
     async fn read_badges(&self, public_key: &str) -> AppResult<Vec<Badge>>;
+    /// Rules of all badges that are not archived and have a rule.
     async fn read_badge_requirements(&self) -> AppResult<Vec<BadgeRequirement>>;
-    async fn create_badge(&self, public_key: &str, badge_id: i32, value: i32) -> AppResult<()>;
+    async fn read_progression_snapshot(&self, public_key: &str) -> AppResult<ProgressionSnapshot>;
+    async fn read_progression_snapshots(&self) -> AppResult<Vec<ProgressionSnapshot>>;
+    /// Returns the ids of the badges that were not held before.
+    async fn award_badges(&self, beta_applicant_id: i32, badge_ids: &[i32]) -> AppResult<Vec<i32>>;
 
-    async fn award_badge_if_eligible(
+    async fn read_badge_definitions(&self) -> AppResult<Vec<BadgeDefinition>>;
+    /// Every signal with a `progression_event_types` row.
+    async fn read_signals(&self) -> AppResult<Vec<ProgressionEventType>>;
+    // Mutations write their admin audit entry in the same transaction.
+    async fn insert_badge(
         &self,
-        public_key: &str,
-        event_type: ProgressionEventType,
-        progress_count: i32,
+        badge: &NewBadge,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition>;
+    async fn update_badge(
+        &self,
+        id: i32,
+        update: &BadgeUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition>;
+    async fn update_badge_rule(
+        &self,
+        id: i32,
+        rule: Option<&BadgeRule>,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition>;
+    async fn archive_badge(
+        &self,
+        id: i32,
+        archived: bool,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition>;
+    /// Fails with `BadRequest` while the rule of another badge requires this one. Inserting
+    /// or updating a rule fails the same way when a badge it requires no longer exists; both
+    /// checks happen under row locks, so a rule can never end up requiring a deleted badge.
+    async fn delete_badge(&self, id: i32, audit: &NewAdminAuditEntry) -> AppResult<()>;
+    async fn reorder_badges(
+        &self,
+        badge_group_id: i32,
+        ordered_ids: &[i32],
+        audit: &NewAdminAuditEntry,
     ) -> AppResult<()>;
 }
 
 #[derive(Clone, Debug)]
 pub struct BadgeUseCases {
     persistence: Arc<dyn BadgePersistence>,
+    event_publisher: Arc<dyn EventPublisher>,
 }
 
 impl BadgeUseCases {
-    pub fn new(persistence: Arc<dyn BadgePersistence>) -> Self {
-        Self { persistence }
+    pub fn new(
+        persistence: Arc<dyn BadgePersistence>,
+        event_publisher: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            persistence,
+            event_publisher,
+        }
     }
 
     pub async fn read_all(&self, public_key: &str) -> AppResult<Vec<BadgeDto>> {
@@ -44,21 +93,200 @@ impl BadgeUseCases {
             .collect())
     }
 
-    pub async fn award_badge_if_eligible(
+    /// Evaluates every badge rule against the user's progressions and awards what is newly
+    /// satisfied. Rules that look at one of the `unavailable_signals` are left for a later
+    /// sync. Returns the newly unlocked badge ids.
+    pub async fn evaluate_badges(
         &self,
         public_key: &str,
-        event_type: ProgressionEventType,
-        progress_count: i32,
+        unavailable_signals: &HashSet<ProgressionEventType>,
+    ) -> AppResult<Vec<i32>> {
+        let rules: Vec<(i32, BadgeRule)> = self
+            .read_rules()
+            .await?
+            .into_iter()
+            .filter(|(_, rule)| {
+                rule.referenced_signals()
+                    .iter()
+                    .all(|signal| !unavailable_signals.contains(signal))
+            })
+            .collect();
+        let snapshot = self
+            .persistence
+            .read_progression_snapshot(public_key)
+            .await?;
+        self.award_unlocked(&rules, &snapshot).await
+    }
+
+    /// Re-evaluates all rules for every applicant, e.g. after a rule changed. Badges are only
+    /// ever awarded, never revoked. Returns the number of newly awarded badges.
+    pub async fn evaluate_all_badges(&self) -> AppResult<usize> {
+        let rules = self.read_rules().await?;
+        let snapshots = self.persistence.read_progression_snapshots().await?;
+
+        let mut awarded = 0;
+        for snapshot in snapshots {
+            awarded += self.award_unlocked(&rules, &snapshot).await?.len();
+        }
+
+        info!("Badge re-evaluation awarded {} badges", awarded);
+        Ok(awarded)
+    }
+
+    async fn read_rules(&self) -> AppResult<Vec<(i32, BadgeRule)>> {
+        let requirements = self.persistence.read_badge_requirements().await?;
+        Ok(requirements
+            .into_iter()
+            .map(|requirement| (requirement.badge_id, requirement.rule))
+            .collect())
+    }
+
+    async fn award_unlocked(
+        &self,
+        rules: &[(i32, BadgeRule)],
+        snapshot: &ProgressionSnapshot,
+    ) -> AppResult<Vec<i32>> {
+        let unlocked = unlocked_badge_ids(rules, snapshot);
+        if unlocked.is_empty() {
+            return Ok(unlocked);
+        }
+        let awarded = self
+            .persistence
+            .award_badges(snapshot.beta_applicant_id, &unlocked)
+            .await?;
+
+        let events: Vec<UserEvent> = awarded
+            .iter()
+            .map(|badge_id| {
+                UserEvent::new(
+                    &snapshot.public_key,
+                    UserEventKind::BadgeUnlocked {
+                        badge_id: *badge_id,
+                    },
+                )
+            })
+            .collect();
+        publish_events(self.event_publisher.as_ref(), &events).await;
+
+        Ok(awarded)
+    }
+
+    /// Includes archived badges, for the admin API.
+    pub async fn read_definitions(&self) -> AppResult<Vec<BadgeDefinition>> {
+        self.persistence.read_badge_definitions().await
+    }
+
+    /// A badge created with a rule is only awarded once a [`BadgeEvaluationJob`] ran.
+    ///
+    /// [`BadgeEvaluationJob`]: crate::use_cases::badge_evaluation::BadgeEvaluationJob
+    pub async fn create_definition(
+        &self,
+        badge: NewBadge,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition> {
+        validate_text("title", &badge.title)?;
+        validate_text("description", &badge.description)?;
+        validate_score(badge.score)?;
+        if let Some(rule) = &badge.rule {
+            self.validate_rule(None, rule).await?;
+        }
+
+        self.persistence.insert_badge(&badge, audit).await
+    }
+
+    pub async fn update_definition(
+        &self,
+        id: i32,
+        update: BadgeUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition> {
+        if let Some(title) = &update.title {
+            validate_text("title", title)?;
+        }
+        if let Some(description) = &update.description {
+            validate_text("description", description)?;
+        }
+        if let Some(score) = update.score {
+            validate_score(score)?;
+        }
+        self.persistence.update_badge(id, &update, audit).await
+    }
+
+    pub async fn archive(
+        &self,
+        id: i32,
+        archived: bool,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition> {
+        self.persistence.archive_badge(id, archived, audit).await
+    }
+
+    /// Refused while the rule of another badge, archived or not, requires this one.
+    pub async fn delete(&self, id: i32, audit: &NewAdminAuditEntry) -> AppResult<()> {
+        self.persistence.delete_badge(id, audit).await
+    }
+
+    pub async fn reorder(
+        &self,
+        badge_group_id: i32,
+        ordered_ids: Vec<i32>,
+        audit: &NewAdminAuditEntry,
     ) -> AppResult<()> {
+        validate_unique_ids(&ordered_ids)?;
         self.persistence
-            .award_badge_if_eligible(public_key, event_type, progress_count)
+            .reorder_badges(badge_group_id, &ordered_ids, audit)
             .await
     }
 
-    pub async fn create_badge(&self, public_key: &str, badge_id: i32, value: i32) -> AppResult<()> {
-        Ok(self
-            .persistence
-            .create_badge(public_key, badge_id, value)
-            .await?)
+    /// Replaces the badge's rule. Everyone who already satisfies a new rule is awarded the
+    /// badge by a [`BadgeEvaluationJob`].
+    ///
+    /// [`BadgeEvaluationJob`]: crate::use_cases::badge_evaluation::BadgeEvaluationJob
+    pub async fn set_rule(
+        &self,
+        id: i32,
+        rule: Option<BadgeRule>,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeDefinition> {
+        if let Some(rule) = &rule {
+            self.validate_rule(Some(id), rule).await?;
+        }
+
+        self.persistence
+            .update_badge_rule(id, rule.as_ref(), audit)
+            .await
+    }
+
+    async fn validate_rule(&self, badge_id: Option<i32>, rule: &BadgeRule) -> AppResult<()> {
+        rule.validate().map_err(AppError::BadRequest)?;
+
+        let signals = rule.referenced_signals();
+        if !signals.is_empty() {
+            let known: HashSet<ProgressionEventType> =
+                self.persistence.read_signals().await?.into_iter().collect();
+            if let Some(unknown) = signals.into_iter().find(|signal| !known.contains(signal)) {
+                return Err(AppError::BadRequest(format!(
+                    "Rule references unknown signal {}",
+                    unknown.name()
+                )));
+            }
+        }
+
+        // Whether the required badges exist is checked as the rule is written
+        if badge_id.is_some_and(|id| rule.referenced_badge_ids().contains(&id)) {
+            return Err(AppError::BadRequest(
+                "A badge rule must not require the badge itself".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn validate_score(score: i32) -> AppResult<()> {
+    if score < 0 {
+        return Err(AppError::BadRequest(
+            "score must not be negative".to_string(),
+        ));
     }
+    Ok(())
 }
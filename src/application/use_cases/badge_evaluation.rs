@@ -0,0 +1,30 @@
+use crate::app_error::AppResult;
+use crate::use_cases::badge::BadgeUseCases;
+use crate::use_cases::job::Job;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// Re-evaluates every badge rule for all applicants, started after an admin changed a rule.
+#[derive(Clone, Debug)]
+pub struct BadgeEvaluationJob {
+    badge_use_cases: Arc<BadgeUseCases>,
+}
+
+impl BadgeEvaluationJob {
+    pub fn new(badge_use_cases: Arc<BadgeUseCases>) -> Self {
+        Self { badge_use_cases }
+    }
+}
+
+#[async_trait]
+impl Job for BadgeEvaluationJob {
+    fn name(&self) -> &'static str {
+        "badge_evaluation"
+    }
+
+    async fn run(&self) -> AppResult<Value> {
+        let awarded = self.badge_use_cases.evaluate_all_badges().await?;
+        Ok(json!({ "badgesAwarded": awarded }))
+    }
+}
@@ -1,5 +1,6 @@
-use crate::app_error::AppResult;
-use crate::entities::badge_group::BadgeGroup;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::entities::badge_group::{BadgeGroup, BadgeGroupUpdate, NewBadgeGroup};
 use async_trait::async_trait;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -7,6 +8,31 @@ use std::sync::Arc;
 #[async_trait]
 pub trait BadgeGroupPersistence: Send + Sync + Debug {
     async fn read_badge_groups(&self) -> AppResult<Vec<BadgeGroup>>;
+    async fn read_all_badge_groups(&self) -> AppResult<Vec<BadgeGroup>>;
+    // Mutations write their admin audit entry in the same transaction.
+    async fn insert_badge_group(
+        &self,
+        badge_group: &NewBadgeGroup,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup>;
+    async fn update_badge_group(
+        &self,
+        id: i32,
+        update: &BadgeGroupUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup>;
+    async fn archive_badge_group(
+        &self,
+        id: i32,
+        archived: bool,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup>;
+    async fn delete_badge_group(&self, id: i32, audit: &NewAdminAuditEntry) -> AppResult<()>;
+    async fn reorder_badge_groups(
+        &self,
+        ordered_ids: &[i32],
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<()>;
 }
 
 #[derive(Clone, Debug)]
@@ -23,4 +49,83 @@ impl BadgeGroupUseCases {
         let badge_groups = self.persistence.read_badge_groups().await?;
         Ok(badge_groups)
     }
+
+    /// Includes archived groups, for the admin API.
+    pub async fn read_all_including_archived(&self) -> AppResult<Vec<BadgeGroup>> {
+        self.persistence.read_all_badge_groups().await
+    }
+
+    pub async fn create(
+        &self,
+        badge_group: NewBadgeGroup,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup> {
+        validate_text("title", &badge_group.title)?;
+        validate_text("description", &badge_group.description)?;
+        self.persistence
+            .insert_badge_group(&badge_group, audit)
+            .await
+    }
+
+    pub async fn update(
+        &self,
+        id: i32,
+        update: BadgeGroupUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup> {
+        if let Some(title) = &update.title {
+            validate_text("title", title)?;
+        }
+        if let Some(description) = &update.description {
+            validate_text("description", description)?;
+        }
+        self.persistence
+            .update_badge_group(id, &update, audit)
+            .await
+    }
+
+    pub async fn archive(
+        &self,
+        id: i32,
+        archived: bool,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<BadgeGroup> {
+        self.persistence
+            .archive_badge_group(id, archived, audit)
+            .await
+    }
+
+    pub async fn delete(&self, id: i32, audit: &NewAdminAuditEntry) -> AppResult<()> {
+        self.persistence.delete_badge_group(id, audit).await
+    }
+
+    pub async fn reorder(
+        &self,
+        ordered_ids: Vec<i32>,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<()> {
+        validate_unique_ids(&ordered_ids)?;
+        self.persistence
+            .reorder_badge_groups(&ordered_ids, audit)
+            .await
+    }
+}
+
+pub(crate) fn validate_text(field: &str, value: &str) -> AppResult<()> {
+    if value.trim().is_empty() {
+        return Err(AppError::BadRequest(format!("{} must not be empty", field)));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_unique_ids(ids: &[i32]) -> AppResult<()> {
+    let mut sorted = ids.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+    if sorted.len() != ids.len() {
+        return Err(AppError::BadRequest(
+            "Ordering must not contain duplicate ids".to_string(),
+        ));
+    }
+    Ok(())
 }
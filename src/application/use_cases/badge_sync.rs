@@ -0,0 +1,120 @@
+use crate::app_error::{AppError, AppResult};
+use crate::entities::leaderboard_refresh::RefreshOutcome;
+use crate::use_cases::badge::BadgeUseCases;
+use crate::use_cases::beta_applicant::BetaApplicantUseCases;
+use crate::use_cases::beta_applicant_progression::BetaApplicantProgressionUseCases;
+use crate::use_cases::job::Job;
+use crate::use_cases::leaderboard::LeaderboardUseCases;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::error;
+
+/// Name of the badge sync job, and of the lock every leaderboard refresh runs under.
+pub const BADGE_SYNC_JOB: &str = "badge_sync";
+
+/// Syncs the progressions and badges of every applicant, then credits the day's badge totals
+/// to the leaderboard.
+#[derive(Clone, Debug)]
+pub struct BadgeSyncJob {
+    beta_applicant_use_cases: Arc<BetaApplicantUseCases>,
+    progression_use_cases: Arc<BetaApplicantProgressionUseCases>,
+    badge_use_cases: Arc<BadgeUseCases>,
+    leaderboard_use_cases: Arc<LeaderboardUseCases>,
+    concurrency: usize,
+}
+
+impl BadgeSyncJob {
+    pub fn new(
+        beta_applicant_use_cases: Arc<BetaApplicantUseCases>,
+        progression_use_cases: Arc<BetaApplicantProgressionUseCases>,
+        badge_use_cases: Arc<BadgeUseCases>,
+        leaderboard_use_cases: Arc<LeaderboardUseCases>,
+        concurrency: usize,
+    ) -> Self {
+        Self {
+            beta_applicant_use_cases,
+            progression_use_cases,
+            badge_use_cases,
+            leaderboard_use_cases,
+            concurrency: concurrency.max(1),
+        }
+    }
+}
+
+#[async_trait]
+impl Job for BadgeSyncJob {
+    fn name(&self) -> &'static str {
+        BADGE_SYNC_JOB
+    }
+
+    async fn run(&self) -> AppResult<Value> {
+        let public_keys = self.beta_applicant_use_cases.read_all_public_keys().await?;
+        let applicants = public_keys.len();
+
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        let mut tasks = JoinSet::new();
+
+        for public_key in public_keys {
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .map_err(|e| AppError::Internal(e.to_string()))?;
+            let progression_use_cases = self.progression_use_cases.clone();
+            let badge_use_cases = self.badge_use_cases.clone();
+
+            tasks.spawn(async move {
+                let result = progression_use_cases
+                    .sync_all_progressions(&public_key, badge_use_cases)
+                    .await;
+                drop(permit);
+                match result {
+                    Ok(report) => Some((report.unlocked_badge_ids.len(), report.failed_signals())),
+                    Err(e) => {
+                        error!("Failed to sync {}: {}", public_key, e);
+                        None
+                    }
+                }
+            });
+        }
+
+        let mut synced = 0;
+        let mut failed = 0;
+        let mut badges_unlocked = 0;
+        let mut signal_errors = 0;
+        while let Some(result) = tasks.join_next().await {
+            match result {
+                Ok(Some((unlocked, errors))) => {
+                    synced += 1;
+                    badges_unlocked += unlocked;
+                    signal_errors += errors;
+                }
+                Ok(None) => failed += 1,
+                Err(e) => {
+                    error!("Badge sync task panicked: {}", e);
+                    failed += 1;
+                }
+            }
+        }
+
+        let leaderboard = self.leaderboard_use_cases.refresh(true, None).await?;
+        let rollover = self.leaderboard_use_cases.finalize_due_season().await?;
+
+        Ok(json!({
+            "applicants": applicants,
+            "synced": synced,
+            "failed": failed,
+            "badgesUnlocked": badges_unlocked,
+            "signalErrors": signal_errors,
+            "leaderboardCredited": matches!(leaderboard, RefreshOutcome::Credited(_)),
+            "leaderboardEntries": leaderboard
+                .stats()
+                .map(|stats| stats.entries_updated + stats.entries_created),
+            "leaderboardMovers": leaderboard.stats().map(|stats| stats.movers),
+            "finalizedSeason": rollover.map(|rollover| rollover.finalized.id),
+        }))
+    }
+}
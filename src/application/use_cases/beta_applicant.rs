@@ -1,10 +1,13 @@
 use crate::app_error::AppResult;
 use crate::entities::beta_applicant::BetaApplicant;
+use crate::entities::progression_event::ProgressionSource;
 use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::sync_report::SyncReport;
 use crate::use_cases::badge::BadgeUseCases;
 use crate::use_cases::beta_applicant_progression::BetaApplicantProgressionUseCases;
 use crate::use_cases::leaderboard::LeaderboardUseCases;
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 use std::fmt::Debug;
 use std::sync::Arc;
 
@@ -28,6 +31,7 @@ pub trait BetaApplicantPersistence: Send + Sync + Debug {
         email: Option<&str>,
     ) -> AppResult<BetaApplicant>;
     async fn count_beta_applicants(&self) -> AppResult<i64>;
+    async fn read_all_public_keys(&self) -> AppResult<Vec<String>>;
     async fn count_referrals(&self, id: i32) -> AppResult<i64>;
     async fn count_referrals_by_public_key(&self, public_key: &str) -> AppResult<i32>;
 }
@@ -42,6 +46,7 @@ impl BetaApplicantUseCases {
         Self { persistence }
     }
 
+    /// Registers the applicant and runs their first sync, whose report is returned alongside.
     pub async fn create(
         &self,
         public_key: &str,
@@ -49,7 +54,7 @@ impl BetaApplicantUseCases {
         progression_use_cases: Arc<BetaApplicantProgressionUseCases>,
         badge_use_cases: Arc<BadgeUseCases>,
         leaderboard_use_cases: Arc<LeaderboardUseCases>,
-    ) -> AppResult<BetaApplicant> {
+    ) -> AppResult<(BetaApplicant, SyncReport)> {
         let applicant = self
             .persistence
             .create_beta_applicant(public_key, referral_code)
@@ -61,18 +66,21 @@ impl BetaApplicantUseCases {
             .await?;
 
         progression_use_cases
-            .record_progression_event(public_key, ProgressionEventType::BetaApplicantCreated, 1)
+            .record_progression_event(
+                public_key,
+                ProgressionEventType::BetaApplicantCreated,
+                Decimal::ONE,
+                ProgressionSource::Signup,
+            )
             .await?;
 
-        badge_use_cases
-            .award_badge_if_eligible(public_key, ProgressionEventType::BetaApplicantCreated, 1)
-            .await?;
-
-        progression_use_cases
+        // Badges are only evaluated by the sync, once every signal has been read or marked
+        // unavailable; evaluating earlier would treat the signals not synced yet as 0.
+        let report = progression_use_cases
             .sync_all_progressions(public_key, badge_use_cases)
             .await?;
 
-        Ok(applicant)
+        Ok((applicant, report))
     }
 
     pub async fn read(&self, public_key: &str) -> AppResult<BetaApplicant> {
@@ -92,6 +100,10 @@ impl BetaApplicantUseCases {
         Ok(updated_applicant)
     }
 
+    pub async fn read_all_public_keys(&self) -> AppResult<Vec<String>> {
+        self.persistence.read_all_public_keys().await
+    }
+
     pub async fn count(&self) -> AppResult<i64> {
         let applicant = self.persistence.count_beta_applicants().await?;
         Ok(applicant)
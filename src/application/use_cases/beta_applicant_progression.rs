@@ -1,29 +1,58 @@
-use crate::app_error::AppResult;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::progression_event::{
+    ProgressionEvent, ProgressionHistory, ProgressionHistoryQuery, ProgressionSource,
+};
 use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::sync_report::{SignalOutcome, SignalSyncResult, SyncClaim, SyncReport};
+use crate::entities::tracked_token::{TokenKind, TrackedToken};
+use crate::entities::user_event::{UserEvent, UserEventKind};
 use crate::entities::user_progression::{UserProgression, UserProgressionDto};
 use crate::use_cases::badge::BadgeUseCases;
 use crate::use_cases::beta_applicant::BetaApplicantPersistence;
+use crate::use_cases::event::{EventPublisher, publish_events};
+use crate::use_cases::tracked_token::TrackedTokenPersistence;
 use crate::use_cases::wallet_holdings::WalletHoldingsClient;
 use async_trait::async_trait;
-use std::env;
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
 use std::fmt::Debug;
 use std::sync::Arc;
 use tracing::{error, info};
 
+const MAX_BATCH_SYNC_WALLETS: usize = 100;
+
 #[async_trait]
 pub trait BetaApplicantProgressionPersistence: Send + Sync + Debug {
+    /// Returns whether the progress count changed.
     async fn record_progression_event(
         &self,
         public_key: &str,
         event_type: ProgressionEventType,
-        progress_count: i32,
-    ) -> AppResult<()>;
+        progress_count: Decimal,
+        source: ProgressionSource,
+    ) -> AppResult<bool>;
+    /// The latest `query.limit` events in the window, oldest first.
+    async fn read_progression_history(
+        &self,
+        public_key: &str,
+        event_type: ProgressionEventType,
+        query: &ProgressionHistoryQuery,
+    ) -> AppResult<Vec<ProgressionEvent>>;
     async fn read_user_progressions(&self, public_key: &str) -> AppResult<Vec<UserProgression>>;
     async fn get_user_progression(
         &self,
         public_key: &str,
         event_type: ProgressionEventType,
-    ) -> AppResult<i32>;
+    ) -> AppResult<Decimal>;
+    /// Records a sync request unless the previous one was less than `cooldown` ago. Fails with
+    /// `NotFound` for wallets that are not registered.
+    async fn claim_sync(&self, public_key: &str, cooldown: Duration) -> AppResult<SyncClaim>;
+    /// Stores the outcome of each synced signal. Only synced signals move the last success.
+    async fn record_sync_results(
+        &self,
+        public_key: &str,
+        results: &[SignalSyncResult],
+    ) -> AppResult<()>;
 }
 
 #[derive(Clone, Debug)]
@@ -31,6 +60,9 @@ pub struct BetaApplicantProgressionUseCases {
     persistence: Arc<dyn BetaApplicantProgressionPersistence>,
     beta_applicant_persistence: Arc<dyn BetaApplicantPersistence>,
     wallet_holdings_client: Arc<dyn WalletHoldingsClient>,
+    tracked_token_persistence: Arc<dyn TrackedTokenPersistence>,
+    event_publisher: Arc<dyn EventPublisher>,
+    sync_cooldown: Duration,
 }
 
 impl BetaApplicantProgressionUseCases {
@@ -38,11 +70,17 @@ impl BetaApplicantProgressionUseCases {
         persistence: Arc<dyn BetaApplicantProgressionPersistence>,
         beta_applicant_persistence: Arc<dyn BetaApplicantPersistence>,
         wallet_holdings_client: Arc<dyn WalletHoldingsClient>,
+        tracked_token_persistence: Arc<dyn TrackedTokenPersistence>,
+        event_publisher: Arc<dyn EventPublisher>,
+        sync_cooldown: Duration,
     ) -> Self {
         Self {
             persistence,
             beta_applicant_persistence,
             wallet_holdings_client,
+            tracked_token_persistence,
+            event_publisher,
+            sync_cooldown,
         }
     }
 
@@ -50,11 +88,51 @@ impl BetaApplicantProgressionUseCases {
         &self,
         public_key: &str,
         event_type: ProgressionEventType,
-        progress_count: i32,
+        progress_count: Decimal,
+        source: ProgressionSource,
     ) -> AppResult<()> {
-        self.persistence
-            .record_progression_event(public_key, event_type, progress_count)
-            .await
+        let changed = self
+            .persistence
+            .record_progression_event(public_key, event_type, progress_count, source)
+            .await?;
+
+        if changed {
+            let event = UserEvent::new(
+                public_key,
+                UserEventKind::ProgressionUpdated {
+                    signal: event_type,
+                    count: progress_count,
+                },
+            );
+            publish_events(self.event_publisher.as_ref(), &[event]).await;
+        }
+        Ok(())
+    }
+
+    pub async fn read_progression_history(
+        &self,
+        public_key: &str,
+        event_type: ProgressionEventType,
+        query: ProgressionHistoryQuery,
+    ) -> AppResult<ProgressionHistory> {
+        // One extra event tells whether the window holds more
+        let mut events = self
+            .persistence
+            .read_progression_history(
+                public_key,
+                event_type,
+                &ProgressionHistoryQuery {
+                    limit: query.limit + 1,
+                    ..query
+                },
+            )
+            .await?;
+        let has_more = events.len() > query.limit as usize;
+        if has_more {
+            events.remove(0);
+        }
+
+        Ok(ProgressionHistory { events, has_more })
     }
 
     pub async fn read_user_progressions(
@@ -72,176 +150,228 @@ impl BetaApplicantProgressionUseCases {
         &self,
         public_key: &str,
         event_type: ProgressionEventType,
-    ) -> AppResult<i32> {
+    ) -> AppResult<Decimal> {
         self.persistence
             .get_user_progression(public_key, event_type)
             .await
     }
 
-    pub async fn sync_all_progressions(
+    /// Sync requested by a user or other untrusted caller, limited to one per cooldown and
+    /// to registered wallets.
+    pub async fn sync_with_cooldown(
         &self,
         public_key: &str,
         badge_use_cases: Arc<BadgeUseCases>,
-    ) -> AppResult<()> {
-        info!("Starting progression sync for user: {}", public_key);
-
-        let beta_applicant_progress = self
-            .sync_beta_applicant_created_progression(public_key)
-            .await
-            .unwrap_or_else(|e| {
-                error!(
-                    "Failed to sync beta applicant progression for {}: {}",
-                    public_key, e
-                );
-                0
-            });
-
-        let catics_progress = self
-            .sync_catics_balance_progression(public_key)
-            .await
-            .unwrap_or_else(|e| {
-                error!(
-                    "Failed to sync CATICS balance progression for {}: {}",
-                    public_key, e
-                );
-                0
-            });
-
-        let jup_progress = self
-            .sync_jup_staked_progression(public_key)
-            .await
-            .unwrap_or_else(|e| {
-                error!(
-                    "Failed to sync JUP staked progression for {}: {}",
-                    public_key, e
-                );
-                0
-            });
-
-        let referral_progress = self
-            .sync_referral_created_progression(public_key)
-            .await
-            .unwrap_or_else(|e| {
-                error!(
-                    "Failed to sync referral progression for {}: {}",
-                    public_key, e
-                );
-                0
-            });
-
-        if let Err(e) = badge_use_cases
-            .award_badge_if_eligible(
-                public_key,
-                ProgressionEventType::BetaApplicantCreated,
-                beta_applicant_progress,
-            )
-            .await
+    ) -> AppResult<SyncReport> {
+        match self
+            .persistence
+            .claim_sync(public_key, self.sync_cooldown)
+            .await?
         {
-            error!(
-                "Failed to award beta applicant badge for {}: {}",
-                public_key, e
-            );
+            SyncClaim::Claimed => {
+                self.sync_all_progressions(public_key, badge_use_cases)
+                    .await
+            }
+            SyncClaim::CoolingDown { retry_at } => {
+                Err(AppError::RateLimited((retry_at - Utc::now()).num_seconds()))
+            }
         }
+    }
 
-        if let Err(e) = badge_use_cases
-            .award_badge_if_eligible(
-                public_key,
-                ProgressionEventType::CaticsBalanceCheck,
-                catics_progress,
-            )
-            .await
-        {
-            error!("Failed to award CATICS badge for {}: {}", public_key, e);
+    /// Syncs the given registered wallets one after another, for batch callers. Each wallet
+    /// gets its own result so that one failure does not hide the others.
+    pub async fn sync_many(
+        &self,
+        public_keys: &[String],
+        badge_use_cases: Arc<BadgeUseCases>,
+    ) -> AppResult<Vec<(String, AppResult<SyncReport>)>> {
+        if public_keys.is_empty() || public_keys.len() > MAX_BATCH_SYNC_WALLETS {
+            return Err(AppError::BadRequest(format!(
+                "Between 1 and {} public keys can be synced at once",
+                MAX_BATCH_SYNC_WALLETS
+            )));
         }
 
-        if let Err(e) = badge_use_cases
-            .award_badge_if_eligible(public_key, ProgressionEventType::JupStaked, jup_progress)
-            .await
-        {
-            error!("Failed to award JUP badge for {}: {}", public_key, e);
+        let mut results = Vec::with_capacity(public_keys.len());
+        for public_key in public_keys {
+            let result = match self
+                .beta_applicant_persistence
+                .read_beta_applicant_by_public_key(public_key)
+                .await
+            {
+                Ok(_) => {
+                    self.sync_all_progressions(public_key, badge_use_cases.clone())
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+            results.push((public_key.clone(), result));
         }
+        Ok(results)
+    }
+
+    /// Syncs every signal of the user and awards the badges that became eligible. A signal
+    /// whose source fails is reported as unavailable: its stored progress is kept, and badges
+    /// depending on it wait for a later sync. It does not stop the other signals.
+    pub async fn sync_all_progressions(
+        &self,
+        public_key: &str,
+        badge_use_cases: Arc<BadgeUseCases>,
+    ) -> AppResult<SyncReport> {
+        info!("Starting progression sync for user: {}", public_key);
+
+        let tokens = self.tracked_token_persistence.read_tracked_tokens().await?;
 
-        if let Err(e) = badge_use_cases
-            .award_badge_if_eligible(
+        let mut signals = vec![signal_result(
+            public_key,
+            ProgressionEventType::BetaApplicantCreated,
+            self.sync_beta_applicant_created_progression(public_key)
+                .await,
+        )];
+        for token in &tokens {
+            if !token.enabled {
+                signals.push(SignalSyncResult {
+                    signal: token.signal,
+                    outcome: SignalOutcome::Skipped("Token is disabled".to_string()),
+                });
+                continue;
+            }
+            signals.push(signal_result(
                 public_key,
-                ProgressionEventType::ReferralCreated,
-                referral_progress,
-            )
-            .await
-        {
-            error!("Failed to award referral badge for {}: {}", public_key, e);
+                token.signal,
+                self.sync_token_progression(public_key, token).await,
+            ));
         }
+        signals.push(signal_result(
+            public_key,
+            ProgressionEventType::ReferralCreated,
+            self.sync_referral_created_progression(public_key).await,
+        ));
 
-        info!("Completed progression sync for user: {}", public_key);
-        Ok(())
-    }
+        self.persistence
+            .record_sync_results(public_key, &signals)
+            .await?;
 
-    async fn sync_beta_applicant_created_progression(&self, public_key: &str) -> AppResult<i32> {
-        self.record_progression_event(public_key, ProgressionEventType::BetaApplicantCreated, 1)
+        let mut report = SyncReport {
+            signals,
+            unlocked_badge_ids: Vec::new(),
+        };
+        report.unlocked_badge_ids = badge_use_cases
+            .evaluate_badges(public_key, &report.unavailable_signals())
             .await?;
+
         info!(
-            "Synced beta applicant created progression for {}: 1",
-            public_key
+            "Completed progression sync for user: {} ({} badges unlocked, {} signals unavailable)",
+            public_key,
+            report.unlocked_badge_ids.len(),
+            report.failed_signals()
         );
-        Ok(1)
+        Ok(report)
     }
 
-    async fn sync_catics_balance_progression(&self, public_key: &str) -> AppResult<i32> {
-        let catics_token_address = env::var("CATICS_TOKEN_ADDRESS").map_err(|_| {
-            crate::app_error::AppError::Internal("CATICS_TOKEN_ADDRESS not set".to_string())
-        })?;
-
-        let balance = self
-            .wallet_holdings_client
-            .get_token_balance(public_key, &catics_token_address)
-            .await?;
-        let balance_int = balance as i32;
-
+    async fn sync_beta_applicant_created_progression(
+        &self,
+        public_key: &str,
+    ) -> AppResult<Decimal> {
         self.record_progression_event(
             public_key,
-            ProgressionEventType::CaticsBalanceCheck,
-            balance_int,
+            ProgressionEventType::BetaApplicantCreated,
+            Decimal::ONE,
+            ProgressionSource::Signup,
         )
         .await?;
-
         info!(
-            "Synced CATICS balance progression for {}: {}",
-            public_key, balance_int
+            "Synced beta applicant created progression for {}: 1",
+            public_key
         );
-        Ok(balance_int)
+        Ok(Decimal::ONE)
     }
 
-    async fn sync_jup_staked_progression(&self, public_key: &str) -> AppResult<i32> {
-        let jup_token_address = env::var("JUP_TOKEN_ADDRESS").map_err(|_| {
-            crate::app_error::AppError::Internal("JUP_TOKEN_ADDRESS not set".to_string())
-        })?;
-
-        let staked = self
-            .wallet_holdings_client
-            .get_staked_token_balance(public_key, &jup_token_address)
-            .await?;
-        let staked_int = staked as i32;
+    async fn sync_token_progression(
+        &self,
+        public_key: &str,
+        token: &TrackedToken,
+    ) -> AppResult<Decimal> {
+        let balance = match token.kind {
+            TokenKind::Held => {
+                self.wallet_holdings_client
+                    .get_token_balance(public_key, &token.mint)
+                    .await?
+            }
+            TokenKind::Staked => {
+                self.wallet_holdings_client
+                    .get_staked_token_balance(public_key, &token.mint)
+                    .await?
+            }
+        };
+        // Providers report each balance at its own precision; store it at the registered one
+        let balance = u8::try_from(token.decimals)
+            .ok()
+            .and_then(|decimals| balance.with_decimals(decimals))
+            .ok_or_else(|| {
+                AppError::Internal(format!(
+                    "{} balance {} does not fit the {} decimals the token is registered with",
+                    token.symbol, balance, token.decimals
+                ))
+            })?;
+        let progress = balance.to_decimal().map_err(AppError::Internal)?;
 
-        self.record_progression_event(public_key, ProgressionEventType::JupStaked, staked_int)
-            .await?;
+        self.record_progression_event(
+            public_key,
+            token.signal,
+            progress,
+            ProgressionSource::WalletHoldings,
+        )
+        .await?;
 
         info!(
-            "Synced JUP staked progression for {}: {}",
-            public_key, staked_int
+            "Synced {} {} progression for {}: {}",
+            token.symbol, token.kind, public_key, progress
         );
-        Ok(staked_int)
+        Ok(progress)
     }
 
-    async fn sync_referral_created_progression(&self, public_key: &str) -> AppResult<i32> {
-        let count = self
-            .beta_applicant_persistence
-            .count_referrals_by_public_key(public_key)
-            .await?;
+    async fn sync_referral_created_progression(&self, public_key: &str) -> AppResult<Decimal> {
+        let count = Decimal::from(
+            self.beta_applicant_persistence
+                .count_referrals_by_public_key(public_key)
+                .await?,
+        );
 
-        self.record_progression_event(public_key, ProgressionEventType::ReferralCreated, count)
-            .await?;
+        self.record_progression_event(
+            public_key,
+            ProgressionEventType::ReferralCreated,
+            count,
+            ProgressionSource::Referrals,
+        )
+        .await?;
 
         info!("Synced referral progression for {}: {}", public_key, count);
         Ok(count)
     }
 }
+
+fn signal_result(
+    public_key: &str,
+    signal: ProgressionEventType,
+    result: AppResult<Decimal>,
+) -> SignalSyncResult {
+    match result {
+        Ok(value) => SignalSyncResult {
+            signal,
+            outcome: SignalOutcome::Value(value),
+        },
+        Err(e) => {
+            error!(
+                "Failed to sync {} progression for {}: {}",
+                signal.name(),
+                public_key,
+                e
+            );
+            SignalSyncResult {
+                signal,
+                outcome: SignalOutcome::Unavailable(e.public_message()),
+            }
+        }
+    }
+}
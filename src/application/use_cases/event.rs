@@ -0,0 +1,71 @@
+use crate::app_error::AppResult;
+use crate::entities::user_event::UserEvent;
+use async_trait::async_trait;
+use std::fmt::Debug;
+use tokio::sync::broadcast;
+use tracing::error;
+
+#[async_trait]
+pub trait EventPublisher: Send + Sync + Debug {
+    /// Delivers the events to the event bus of every replica, including this one.
+    async fn publish(&self, events: &[UserEvent]) -> AppResult<()>;
+    /// Tells the event bus of every replica, including this one, that the leaderboard was
+    /// re-ranked.
+    async fn publish_leaderboard_change(&self) -> AppResult<()>;
+}
+
+/// Events are pushed after the change that caused them is committed. Failing to deliver them
+/// only delays what users see until their next reload, so it never fails the change itself.
+pub async fn publish_events(publisher: &dyn EventPublisher, events: &[UserEvent]) {
+    if events.is_empty() {
+        return;
+    }
+    if let Err(e) = publisher.publish(events).await {
+        error!("Failed to publish {} user events: {}", events.len(), e);
+    }
+}
+
+/// Replicas that miss the change keep serving their cached leaderboard until it expires.
+pub async fn publish_leaderboard_change(publisher: &dyn EventPublisher) {
+    if let Err(e) = publisher.publish_leaderboard_change().await {
+        error!("Failed to publish leaderboard change: {}", e);
+    }
+}
+
+/// Fans events out to the streams open on this replica, and leaderboard changes to whatever
+/// caches the leaderboard.
+#[derive(Clone, Debug)]
+pub struct EventBus {
+    sender: broadcast::Sender<UserEvent>,
+    leaderboard_changes: broadcast::Sender<()>,
+}
+
+impl EventBus {
+    /// Subscribers that fall more than `capacity` events behind skip the oldest ones.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        // A lagging subscriber still learns that something changed, so one slot is enough
+        let (leaderboard_changes, _) = broadcast::channel(1);
+        Self {
+            sender,
+            leaderboard_changes,
+        }
+    }
+
+    pub fn send(&self, event: UserEvent) {
+        // Only fails when nobody is subscribed, in which case there is nobody to tell
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<UserEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn send_leaderboard_change(&self) {
+        let _ = self.leaderboard_changes.send(());
+    }
+
+    pub fn subscribe_leaderboard_changes(&self) -> broadcast::Receiver<()> {
+        self.leaderboard_changes.subscribe()
+    }
+}
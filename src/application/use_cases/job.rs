@@ -0,0 +1,164 @@
+use crate::app_error::AppResult;
+use crate::entities::job_run::{JobRun, JobRunStatus};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::{Value, json};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+/// How often `spawn` retries a job that is running elsewhere.
+const SPAWN_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+/// How often, and how many times, `run_scheduled` retries a slot it could not start.
+const SCHEDULED_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+const SCHEDULED_RETRIES: u32 = 60;
+
+/// Exclusive right to run a job across all replicas. Dropping it releases the lock
+/// eventually; `release` does so before returning.
+#[async_trait]
+pub trait JobLock: Send + Sync + Debug {
+    async fn release(self: Box<Self>) -> AppResult<()>;
+}
+
+#[async_trait]
+pub trait JobPersistence: Send + Sync + Debug {
+    /// Returns `None` if another process currently holds the lock for `job_name`.
+    async fn try_lock_job(&self, job_name: &str) -> AppResult<Option<Box<dyn JobLock>>>;
+    /// Returns `None` if a run for the same `scheduled_for` slot already exists. Runs without
+    /// a slot never conflict.
+    async fn create_job_run(
+        &self,
+        job_name: &str,
+        scheduled_for: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<JobRun>>;
+    async fn finish_job_run(
+        &self,
+        id: i32,
+        status: JobRunStatus,
+        summary: &Value,
+        error: Option<&str>,
+    ) -> AppResult<JobRun>;
+    /// Marks runs still recorded as running as abandoned. Only called while holding the lock.
+    async fn abandon_running_job_runs(&self, job_name: &str) -> AppResult<u64>;
+    async fn read_job_runs(&self, limit: u32) -> AppResult<Vec<JobRun>>;
+}
+
+/// A unit of background work. The returned value is stored as the run summary.
+#[async_trait]
+pub trait Job: Send + Sync + Debug {
+    fn name(&self) -> &'static str;
+    /// Jobs that must not overlap share a lock name. Defaults to the job's own name.
+    fn lock_name(&self) -> &'static str {
+        self.name()
+    }
+    async fn run(&self) -> AppResult<Value>;
+}
+
+#[derive(Clone, Debug)]
+pub struct JobUseCases {
+    persistence: Arc<dyn JobPersistence>,
+}
+
+impl JobUseCases {
+    pub fn new(persistence: Arc<dyn JobPersistence>) -> Self {
+        Self { persistence }
+    }
+
+    /// Runs the job unless another replica is already running it, recording the outcome.
+    /// `scheduled_for` is the cron slot of a scheduled run, which runs once even though every
+    /// replica wakes up for it; `None` for on-demand runs. Returns `None` if the job was
+    /// skipped.
+    pub async fn run_exclusive(
+        &self,
+        job: &dyn Job,
+        scheduled_for: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<JobRun>> {
+        let Some(lock) = self.persistence.try_lock_job(job.lock_name()).await? else {
+            info!("Skipping job {}: already running elsewhere", job.name());
+            return Ok(None);
+        };
+
+        self.persistence
+            .abandon_running_job_runs(job.name())
+            .await?;
+        let Some(run) = self
+            .persistence
+            .create_job_run(job.name(), scheduled_for)
+            .await?
+        else {
+            info!(
+                "Skipping job {}: the run scheduled for {} already happened",
+                job.name(),
+                scheduled_for
+                    .map(|slot| slot.to_string())
+                    .unwrap_or_default()
+            );
+            lock.release().await?;
+            return Ok(None);
+        };
+        info!("Started job {} (run {})", job.name(), run.id);
+
+        let finished = match job.run().await {
+            Ok(summary) => {
+                info!("Job {} succeeded: {}", job.name(), summary);
+                self.persistence
+                    .finish_job_run(run.id, JobRunStatus::Succeeded, &summary, None)
+                    .await?
+            }
+            Err(e) => {
+                error!("Job {} failed: {}", job.name(), e);
+                self.persistence
+                    .finish_job_run(
+                        run.id,
+                        JobRunStatus::Failed,
+                        &json!({}),
+                        Some(&e.to_string()),
+                    )
+                    .await?
+            }
+        };
+        lock.release().await?;
+
+        Ok(Some(finished))
+    }
+
+    /// Runs the cron slot `scheduled_for`. Another job sharing the lock, such as an admin
+    /// re-rank, may hold it for a moment, so a skipped slot is retried for a minute; the slot
+    /// still runs only once.
+    pub async fn run_scheduled(
+        &self,
+        job: &dyn Job,
+        scheduled_for: DateTime<Utc>,
+    ) -> AppResult<Option<JobRun>> {
+        for _ in 0..SCHEDULED_RETRIES {
+            if let Some(run) = self.run_exclusive(job, Some(scheduled_for)).await? {
+                return Ok(Some(run));
+            }
+            tokio::time::sleep(SCHEDULED_RETRY_INTERVAL).await;
+        }
+        self.run_exclusive(job, Some(scheduled_for)).await
+    }
+
+    /// Runs the job in the background. A run already in progress elsewhere may have started
+    /// before the caller's changes, so the job waits for it to end and then runs again.
+    pub fn spawn(self: &Arc<Self>, job: Arc<dyn Job>) {
+        let job_use_cases = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match job_use_cases.run_exclusive(job.as_ref(), None).await {
+                    Ok(Some(_)) => return,
+                    Ok(None) => tokio::time::sleep(SPAWN_RETRY_INTERVAL).await,
+                    Err(e) => {
+                        error!("Job {} could not run: {}", job.name(), e);
+                        return;
+                    }
+                }
+            }
+        });
+    }
+
+    pub async fn read_runs(&self, limit: u32) -> AppResult<Vec<JobRun>> {
+        self.persistence.read_job_runs(limit).await
+    }
+}
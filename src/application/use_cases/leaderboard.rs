@@ -1,8 +1,29 @@
-use crate::app_error::AppResult;
-use crate::entities::leaderboard_entry::{LeaderboardEntry, LeaderboardEntryDto};
+use crate::app_error::{AppError, AppResult};
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::entities::leaderboard_entry::{
+    LeaderboardCursor, LeaderboardEntry, LeaderboardEntryDto, LeaderboardSlice, UserStanding,
+};
+use crate::entities::leaderboard_kind::LeaderboardKind;
+use crate::entities::leaderboard_refresh::{RankedEntry, RefreshOutcome, refresh_stats};
+use crate::entities::leaderboard_snapshot::{LeaderboardSnapshot, MoverDirection, RankMovement};
+use crate::entities::season::{NewSeason, Season, SeasonRollover, SeasonUpdate};
+use crate::entities::user_event::{UserEvent, UserEventKind};
+use crate::use_cases::badge_group::validate_text;
+use crate::use_cases::event::{
+    EventBus, EventPublisher, publish_events, publish_leaderboard_change,
+};
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use std::fmt::Debug;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+const MAX_HISTORY_DAYS: i64 = 366;
+const MAX_MOVER_DAYS: u32 = 90;
+/// The public cache holds this many entries; smaller limits are served from it.
+pub const PUBLIC_LEADERBOARD_MAX_LIMIT: u32 = 100;
 
 #[async_trait]
 pub trait LeaderboardPersistence: Send + Sync + Debug {
@@ -12,12 +33,24 @@ pub trait LeaderboardPersistence: Send + Sync + Debug {
         offset: u32,
     ) -> AppResult<Vec<LeaderboardEntry>>;
     async fn get_total_users_with_badges(&self) -> AppResult<u32>;
-    async fn get_user_rank(&self, public_key: &str) -> AppResult<u32>;
-    async fn get_user_leaderboard_entry(
+    async fn get_leaderboard_entries_after(
         &self,
-        public_key: &str,
-    ) -> AppResult<Option<LeaderboardEntry>>;
-    async fn get_user_realtime_entry(
+        after_rank: u32,
+        limit: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>>;
+    /// Returned in ascending rank order.
+    async fn get_leaderboard_entries_before(
+        &self,
+        before_rank: u32,
+        limit: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>>;
+    async fn get_leaderboard_entries_between(
+        &self,
+        from_rank: u32,
+        to_rank: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>>;
+    async fn get_user_standing(&self, public_key: &str) -> AppResult<Option<UserStanding>>;
+    async fn get_user_leaderboard_entry(
         &self,
         public_key: &str,
     ) -> AppResult<Option<LeaderboardEntry>>;
@@ -26,16 +59,169 @@ pub trait LeaderboardPersistence: Send + Sync + Debug {
         beta_applicant_id: i32,
         public_key: &str,
     ) -> AppResult<()>;
+    /// Adds everyone's current badge total to their score and re-ranks the whole table with
+    /// `rank_standings` in one transaction, keeping the old rank as `previous_rank` and
+    /// recording a snapshot of every entry. Writes `audit` in the same transaction when an
+    /// admin triggered the refresh. Each day is credited to the open season once; returns
+    /// `None` without touching anything when `credited_on` already was. Without
+    /// `credited_on`, only re-ranks on the stored scores.
+    async fn refresh_daily(
+        &self,
+        credited_on: Option<NaiveDate>,
+        audit: Option<&NewAdminAuditEntry>,
+    ) -> AppResult<Option<Vec<RankedEntry>>>;
+
+    async fn read_seasons(&self) -> AppResult<Vec<Season>>;
+    async fn read_season(&self, id: i32) -> AppResult<Season>;
+    async fn read_open_season(&self) -> AppResult<Option<Season>>;
+    /// The audit entry gets the season's id.
+    async fn update_open_season(
+        &self,
+        update: &SeasonUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<Season>;
+    /// Archives the current standings as the final standings of the open season, closes it,
+    /// opens `next` and resets every entry to a score of zero, all in one transaction together
+    /// with `audit`, which gets the finalized season's id.
+    async fn finalize_open_season(
+        &self,
+        next: &NewSeason,
+        audit: Option<&NewAdminAuditEntry>,
+    ) -> AppResult<SeasonRollover>;
+    async fn get_season_entries(
+        &self,
+        season_id: i32,
+        limit: u32,
+        offset: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>>;
+    async fn get_season_total(&self, season_id: i32) -> AppResult<u32>;
+    async fn get_user_season_entry(
+        &self,
+        season_id: i32,
+        public_key: &str,
+    ) -> AppResult<Option<LeaderboardEntry>>;
+    /// Real-time board of any kind but `Total`, holding only applicants with a positive score.
+    async fn get_kind_entries(
+        &self,
+        kind: &LeaderboardKind,
+        limit: u32,
+        offset: u32,
+    ) -> AppResult<Vec<LeaderboardEntry>>;
+    async fn get_kind_total(&self, kind: &LeaderboardKind) -> AppResult<u32>;
+    async fn get_user_kind_entry(
+        &self,
+        kind: &LeaderboardKind,
+        public_key: &str,
+    ) -> AppResult<Option<LeaderboardEntry>>;
+    async fn get_rank_history(
+        &self,
+        public_key: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AppResult<Vec<LeaderboardSnapshot>>;
+    /// Entries of the open season ranked by how far they moved since their first snapshot
+    /// taken at or after `since`.
+    async fn get_top_movers(
+        &self,
+        since: DateTime<Utc>,
+        direction: MoverDirection,
+        limit: u32,
+    ) -> AppResult<Vec<RankMovement>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct PublicLeaderboard {
+    pub entries: Vec<LeaderboardEntryDto>,
+    pub total: u32,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+struct CachedPublicLeaderboard {
+    leaderboard: Arc<PublicLeaderboard>,
+    expires_at: DateTime<Utc>,
 }
 
 #[derive(Clone, Debug)]
 pub struct LeaderboardUseCases {
     persistence: Arc<dyn LeaderboardPersistence>,
+    public_cache: Arc<RwLock<Option<CachedPublicLeaderboard>>>,
+    public_cache_ttl: Duration,
+    event_publisher: Arc<dyn EventPublisher>,
 }
 
 impl LeaderboardUseCases {
-    pub fn new(persistence: Arc<dyn LeaderboardPersistence>) -> Self {
-        Self { persistence }
+    pub fn new(
+        persistence: Arc<dyn LeaderboardPersistence>,
+        public_cache_ttl: Duration,
+        event_publisher: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            persistence,
+            public_cache: Arc::new(RwLock::new(None)),
+            public_cache_ttl,
+            event_publisher,
+        }
+    }
+
+    /// Top of the total leaderboard for unauthenticated clients, served from memory until the
+    /// next refresh or for at most the cache TTL.
+    pub async fn get_public_leaderboard(&self, limit: u32) -> AppResult<PublicLeaderboard> {
+        let limit = limit.min(PUBLIC_LEADERBOARD_MAX_LIMIT) as usize;
+        let cached = self.cached_public_leaderboard();
+        let leaderboard = match cached {
+            Some(leaderboard) => leaderboard,
+            None => self.load_public_leaderboard().await?,
+        };
+
+        Ok(PublicLeaderboard {
+            entries: leaderboard.entries.iter().take(limit).cloned().collect(),
+            total: leaderboard.total,
+            generated_at: leaderboard.generated_at,
+        })
+    }
+
+    fn cached_public_leaderboard(&self) -> Option<Arc<PublicLeaderboard>> {
+        let cache = self.public_cache.read().unwrap_or_else(|e| e.into_inner());
+        cache
+            .as_ref()
+            .filter(|cached| cached.expires_at > Utc::now())
+            .map(|cached| cached.leaderboard.clone())
+    }
+
+    async fn load_public_leaderboard(&self) -> AppResult<Arc<PublicLeaderboard>> {
+        let (entries, total) = self
+            .get_leaderboard(1, PUBLIC_LEADERBOARD_MAX_LIMIT)
+            .await?;
+        let generated_at = Utc::now();
+        let leaderboard = Arc::new(PublicLeaderboard {
+            entries,
+            total,
+            generated_at,
+        });
+
+        let mut cache = self.public_cache.write().unwrap_or_else(|e| e.into_inner());
+        *cache = Some(CachedPublicLeaderboard {
+            leaderboard: leaderboard.clone(),
+            expires_at: generated_at + self.public_cache_ttl,
+        });
+        Ok(leaderboard)
+    }
+
+    fn invalidate_public_cache(&self) {
+        let mut cache = self.public_cache.write().unwrap_or_else(|e| e.into_inner());
+        *cache = None;
+    }
+
+    /// Drops the public cache whenever any replica changes the leaderboard.
+    pub fn spawn_cache_invalidation(self: &Arc<Self>, event_bus: &EventBus) -> JoinHandle<()> {
+        let use_cases = self.clone();
+        let mut changes = event_bus.subscribe_leaderboard_changes();
+        tokio::spawn(async move {
+            while let Ok(()) | Err(RecvError::Lagged(_)) = changes.recv().await {
+                use_cases.invalidate_public_cache();
+            }
+        })
     }
 
     pub async fn get_leaderboard(
@@ -54,26 +240,134 @@ impl LeaderboardUseCases {
         Ok((dto_entries, total))
     }
 
-    pub async fn get_user_rank(&self, public_key: &str) -> AppResult<u32> {
-        self.persistence.get_user_rank(public_key).await
+    /// Keyset page of the total board. Ranks are reassigned on refresh, so a cursor kept across
+    /// a refresh continues from the same rank, not the same entry.
+    pub async fn get_leaderboard_by_cursor(
+        &self,
+        cursor: Option<LeaderboardCursor>,
+        limit: u32,
+    ) -> AppResult<LeaderboardSlice> {
+        let cursor = cursor.unwrap_or(LeaderboardCursor::After(0));
+        let (entries, has_previous, has_next) = match cursor {
+            LeaderboardCursor::After(rank) => {
+                let mut entries = self
+                    .persistence
+                    .get_leaderboard_entries_after(rank, limit + 1)
+                    .await?;
+                let has_next = entries.len() > limit as usize;
+                entries.truncate(limit as usize);
+                (entries, rank > 0, has_next)
+            }
+            LeaderboardCursor::Before(rank) => {
+                let mut entries = self
+                    .persistence
+                    .get_leaderboard_entries_before(rank, limit + 1)
+                    .await?;
+                let has_previous = entries.len() > limit as usize;
+                if has_previous {
+                    entries.remove(0);
+                }
+                (entries, has_previous, true)
+            }
+        };
+        let total = self.persistence.get_total_users_with_badges().await?;
+
+        Ok(LeaderboardSlice {
+            entries: entries.into_iter().map(LeaderboardEntryDto::from).collect(),
+            total,
+            has_previous,
+            has_next,
+        })
+    }
+
+    /// The user's entry with up to `radius` entries above and below it. Empty if the user is
+    /// not on the leaderboard.
+    pub async fn get_leaderboard_around(
+        &self,
+        public_key: &str,
+        radius: u32,
+    ) -> AppResult<LeaderboardSlice> {
+        let total = self.persistence.get_total_users_with_badges().await?;
+        let Some(user_entry) = self
+            .persistence
+            .get_user_leaderboard_entry(public_key)
+            .await?
+        else {
+            return Ok(LeaderboardSlice {
+                entries: Vec::new(),
+                total,
+                has_previous: false,
+                has_next: false,
+            });
+        };
+
+        let from_rank = user_entry.rank.saturating_sub(radius).max(1);
+        let to_rank = user_entry.rank.saturating_add(radius);
+        let entries = self
+            .persistence
+            .get_leaderboard_entries_between(from_rank, to_rank)
+            .await?;
+        let has_next = entries.last().is_some_and(|entry| entry.rank < total);
+
+        Ok(LeaderboardSlice {
+            entries: entries.into_iter().map(LeaderboardEntryDto::from).collect(),
+            total,
+            has_previous: from_rank > 1,
+            has_next,
+        })
     }
 
-    pub async fn get_user_leaderboard_entry(
+    /// Same ranking rules as the total board: score descending, earlier applicants first.
+    pub async fn get_leaderboard_of_kind(
+        &self,
+        kind: LeaderboardKind,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<(Vec<LeaderboardEntryDto>, u32)> {
+        if kind == LeaderboardKind::Total {
+            return self.get_leaderboard(page, limit).await;
+        }
+
+        let offset = (page - 1) * limit;
+        let entries = self
+            .persistence
+            .get_kind_entries(&kind, limit, offset)
+            .await?;
+        let total = self.persistence.get_kind_total(&kind).await?;
+
+        let dto_entries = entries.into_iter().map(LeaderboardEntryDto::from).collect();
+        Ok((dto_entries, total))
+    }
+
+    pub async fn get_user_entry_of_kind(
         &self,
+        kind: LeaderboardKind,
         public_key: &str,
     ) -> AppResult<Option<LeaderboardEntryDto>> {
+        if kind == LeaderboardKind::Total {
+            return self.get_user_leaderboard_entry(public_key).await;
+        }
+
         let entry = self
             .persistence
-            .get_user_leaderboard_entry(public_key)
+            .get_user_kind_entry(&kind, public_key)
             .await?;
         Ok(entry.map(LeaderboardEntryDto::from))
     }
 
-    pub async fn get_user_realtime_leaderboard_entry(
+    /// `None` if the user is not a beta applicant.
+    pub async fn get_user_standing(&self, public_key: &str) -> AppResult<Option<UserStanding>> {
+        self.persistence.get_user_standing(public_key).await
+    }
+
+    pub async fn get_user_leaderboard_entry(
         &self,
         public_key: &str,
     ) -> AppResult<Option<LeaderboardEntryDto>> {
-        let entry = self.persistence.get_user_realtime_entry(public_key).await?;
+        let entry = self
+            .persistence
+            .get_user_leaderboard_entry(public_key)
+            .await?;
         Ok(entry.map(LeaderboardEntryDto::from))
     }
 
@@ -86,4 +380,192 @@ impl LeaderboardUseCases {
             .add_new_user_to_leaderboard(beta_applicant_id, public_key)
             .await
     }
+
+    /// With `credit`, adds today's badge totals to every score, which only the badge sync does
+    /// once it has synced everyone. Otherwise only re-ranks on the stored scores. `audit` is set
+    /// when an admin triggered the refresh rather than the scheduler.
+    pub async fn refresh(
+        &self,
+        credit: bool,
+        audit: Option<&NewAdminAuditEntry>,
+    ) -> AppResult<RefreshOutcome> {
+        let today = Utc::now().date_naive();
+        let credited_on = credit.then_some(today);
+        let Some(ranked) = self.persistence.refresh_daily(credited_on, audit).await? else {
+            info!("Leaderboard scores for {} were already credited", today);
+            return Ok(RefreshOutcome::AlreadyCredited);
+        };
+        self.invalidate_public_cache();
+        publish_leaderboard_change(self.event_publisher.as_ref()).await;
+        let stats = refresh_stats(&ranked);
+        info!(
+            "Leaderboard {}: {} updated, {} created, {} movers",
+            if credit { "credited" } else { "re-ranked" },
+            stats.entries_updated,
+            stats.entries_created,
+            stats.movers
+        );
+
+        let events: Vec<UserEvent> = ranked
+            .into_iter()
+            .filter(|entry| entry.previous_rank != Some(entry.rank))
+            .map(|entry| {
+                UserEvent::new(
+                    entry.public_key,
+                    UserEventKind::RankChanged {
+                        rank: entry.rank,
+                        previous_rank: entry.previous_rank,
+                        total_score: entry.total_score,
+                    },
+                )
+            })
+            .collect();
+        publish_events(self.event_publisher.as_ref(), &events).await;
+
+        Ok(if credit {
+            RefreshOutcome::Credited(stats)
+        } else {
+            RefreshOutcome::Reranked(stats)
+        })
+    }
+
+    pub async fn read_seasons(&self) -> AppResult<Vec<Season>> {
+        self.persistence.read_seasons().await
+    }
+
+    /// Standings of a season; the open season is served from the live leaderboard.
+    pub async fn get_season_leaderboard(
+        &self,
+        season_id: i32,
+        page: u32,
+        limit: u32,
+    ) -> AppResult<(Vec<LeaderboardEntryDto>, u32)> {
+        let season = self.persistence.read_season(season_id).await?;
+        if season.is_open() {
+            return self.get_leaderboard(page, limit).await;
+        }
+
+        let offset = (page - 1) * limit;
+        let entries = self
+            .persistence
+            .get_season_entries(season_id, limit, offset)
+            .await?;
+        let total = self.persistence.get_season_total(season_id).await?;
+
+        let dto_entries = entries.into_iter().map(LeaderboardEntryDto::from).collect();
+        Ok((dto_entries, total))
+    }
+
+    pub async fn get_user_season_entry(
+        &self,
+        season_id: i32,
+        public_key: &str,
+    ) -> AppResult<Option<LeaderboardEntryDto>> {
+        let season = self.persistence.read_season(season_id).await?;
+        if season.is_open() {
+            return self.get_user_leaderboard_entry(public_key).await;
+        }
+
+        let entry = self
+            .persistence
+            .get_user_season_entry(season_id, public_key)
+            .await?;
+        Ok(entry.map(LeaderboardEntryDto::from))
+    }
+
+    pub async fn update_open_season(
+        &self,
+        update: SeasonUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<Season> {
+        if let Some(name) = &update.name {
+            validate_text("name", name)?;
+        }
+        if update.ends_at.is_some_and(|ends_at| ends_at <= Utc::now()) {
+            return Err(AppError::BadRequest(
+                "endsAt must be in the future".to_string(),
+            ));
+        }
+        self.persistence.update_open_season(&update, audit).await
+    }
+
+    pub async fn finalize_season(
+        &self,
+        next: NewSeason,
+        audit: Option<&NewAdminAuditEntry>,
+    ) -> AppResult<SeasonRollover> {
+        validate_text("name", &next.name)?;
+        if next.ends_at.is_some_and(|ends_at| ends_at <= Utc::now()) {
+            return Err(AppError::BadRequest(
+                "endsAt must be in the future".to_string(),
+            ));
+        }
+
+        let rollover = self.persistence.finalize_open_season(&next, audit).await?;
+        self.invalidate_public_cache();
+        publish_leaderboard_change(self.event_publisher.as_ref()).await;
+        info!(
+            "Finalized {} with {} entries, started {}",
+            rollover.finalized.name, rollover.archived_entries, rollover.next.name
+        );
+        Ok(rollover)
+    }
+
+    /// Finalizes the open season once its planned end has passed. The next season is named
+    /// after its position and has no planned end.
+    pub async fn finalize_due_season(&self) -> AppResult<Option<SeasonRollover>> {
+        let Some(season) = self.persistence.read_open_season().await? else {
+            return Ok(None);
+        };
+        if !season.is_due(Utc::now()) {
+            return Ok(None);
+        }
+
+        let seasons = self.persistence.read_seasons().await?;
+        let next = NewSeason {
+            name: format!("Season {}", seasons.len()),
+            ends_at: None,
+        };
+        self.finalize_season(next, None).await.map(Some)
+    }
+
+    pub async fn get_rank_history(
+        &self,
+        public_key: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> AppResult<Vec<LeaderboardSnapshot>> {
+        if from > to {
+            return Err(AppError::BadRequest(
+                "from must not be after to".to_string(),
+            ));
+        }
+        if to - from > Duration::days(MAX_HISTORY_DAYS) {
+            return Err(AppError::BadRequest(format!(
+                "History is limited to {} days",
+                MAX_HISTORY_DAYS
+            )));
+        }
+        self.persistence
+            .get_rank_history(public_key, from, to)
+            .await
+    }
+
+    pub async fn get_top_movers(
+        &self,
+        direction: MoverDirection,
+        days: u32,
+        limit: u32,
+    ) -> AppResult<Vec<RankMovement>> {
+        if days == 0 || days > MAX_MOVER_DAYS {
+            return Err(AppError::BadRequest(format!(
+                "days must be between 1 and {}",
+                MAX_MOVER_DAYS
+            )));
+        }
+        let since = Utc::now() - Duration::days(i64::from(days));
+        self.persistence
+            .get_top_movers(since, direction, limit)
+            .await
+    }
 }
@@ -0,0 +1,70 @@
+use crate::app_error::AppResult;
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::use_cases::badge_sync::BADGE_SYNC_JOB;
+use crate::use_cases::job::Job;
+use crate::use_cases::leaderboard::LeaderboardUseCases;
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::sync::Arc;
+
+/// Re-ranks the leaderboard on an admin's request without crediting a day, which is left to
+/// the badge sync. Runs under the badge sync's lock so the two never overlap.
+#[derive(Clone, Debug)]
+pub struct LeaderboardRefreshJob {
+    leaderboard_use_cases: Arc<LeaderboardUseCases>,
+    audit: Option<NewAdminAuditEntry>,
+}
+
+impl LeaderboardRefreshJob {
+    pub fn new(
+        leaderboard_use_cases: Arc<LeaderboardUseCases>,
+        audit: Option<NewAdminAuditEntry>,
+    ) -> Self {
+        Self {
+            leaderboard_use_cases,
+            audit,
+        }
+    }
+}
+
+#[async_trait]
+impl Job for LeaderboardRefreshJob {
+    fn name(&self) -> &'static str {
+        "leaderboard_refresh"
+    }
+
+    fn lock_name(&self) -> &'static str {
+        BADGE_SYNC_JOB
+    }
+
+    async fn run(&self) -> AppResult<Value> {
+        let outcome = self
+            .leaderboard_use_cases
+            .refresh(false, self.audit.as_ref())
+            .await?;
+        let Some(stats) = outcome.stats() else {
+            return Ok(json!({}));
+        };
+
+        let top_movers: Vec<Value> = stats
+            .top_movers
+            .iter()
+            .map(|entry| {
+                json!({
+                    "publicKey": entry.public_key,
+                    "rank": entry.rank,
+                    "previousRank": entry.previous_rank,
+                    "totalScore": entry.total_score,
+                })
+            })
+            .collect();
+        Ok(json!({
+            "entriesUpdated": stats.entries_updated,
+            "entriesCreated": stats.entries_created,
+            "maxScore": stats.max_score,
+            "minScore": stats.min_score,
+            "movers": stats.movers,
+            "topMovers": top_movers,
+        }))
+    }
+}
@@ -1,7 +1,16 @@
+pub mod admin;
+pub mod auth;
+pub mod auth_purge;
 pub mod badge;
+pub mod badge_evaluation;
 pub mod badge_group;
+pub mod badge_sync;
 pub mod beta_applicant;
 pub mod beta_applicant_progression;
 pub mod cat;
+pub mod event;
+pub mod job;
 pub mod leaderboard;
+pub mod leaderboard_refresh;
+pub mod tracked_token;
 pub mod wallet_holdings;
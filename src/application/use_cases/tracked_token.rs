@@ -0,0 +1,121 @@
+use crate::app_error::{AppError, AppResult};
+use crate::entities::admin_audit_entry::NewAdminAuditEntry;
+use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::tracked_token::{
+    NewTrackedToken, TokenKind, TrackedToken, TrackedTokenUpdate,
+};
+use crate::use_cases::auth::validate_address;
+use crate::use_cases::badge_group::validate_text;
+use async_trait::async_trait;
+use std::fmt::Debug;
+use std::sync::Arc;
+use tracing::info;
+
+const MAX_DECIMALS: i32 = 18;
+
+#[async_trait]
+pub trait TrackedTokenPersistence: Send + Sync + Debug {
+    async fn read_tracked_tokens(&self) -> AppResult<Vec<TrackedToken>>;
+    /// Creates a new progression event type for the token and links it. Writes the admin
+    /// audit entry in the same transaction.
+    async fn insert_tracked_token(
+        &self,
+        token: &NewTrackedToken,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<TrackedToken>;
+    /// Links the token to an existing signal unless that signal already has a token. Returns
+    /// whether the token was inserted.
+    async fn seed_tracked_token(
+        &self,
+        token: &NewTrackedToken,
+        signal: ProgressionEventType,
+    ) -> AppResult<bool>;
+    async fn update_tracked_token(
+        &self,
+        id: i32,
+        update: &TrackedTokenUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<TrackedToken>;
+}
+
+#[derive(Clone, Debug)]
+pub struct TrackedTokenUseCases {
+    persistence: Arc<dyn TrackedTokenPersistence>,
+    /// The only mint whose staked balance the wallet holdings providers report.
+    staked_mint: String,
+}
+
+impl TrackedTokenUseCases {
+    pub fn new(persistence: Arc<dyn TrackedTokenPersistence>, staked_mint: String) -> Self {
+        Self {
+            persistence,
+            staked_mint,
+        }
+    }
+
+    pub async fn read_all(&self) -> AppResult<Vec<TrackedToken>> {
+        self.persistence.read_tracked_tokens().await
+    }
+
+    pub async fn create(
+        &self,
+        token: NewTrackedToken,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<TrackedToken> {
+        validate_address("mint", &token.mint)?;
+        validate_text("symbol", &token.symbol)?;
+        validate_decimals(token.decimals)?;
+        if token.kind == TokenKind::Staked && token.mint != self.staked_mint {
+            return Err(AppError::BadRequest(format!(
+                "Only {} can be tracked as staked",
+                self.staked_mint
+            )));
+        }
+        self.persistence.insert_tracked_token(&token, audit).await
+    }
+
+    pub async fn update(
+        &self,
+        id: i32,
+        update: TrackedTokenUpdate,
+        audit: &NewAdminAuditEntry,
+    ) -> AppResult<TrackedToken> {
+        if let Some(symbol) = &update.symbol {
+            validate_text("symbol", symbol)?;
+        }
+        if let Some(decimals) = update.decimals {
+            validate_decimals(decimals)?;
+        }
+        self.persistence
+            .update_tracked_token(id, &update, audit)
+            .await
+    }
+
+    /// Registers the tokens of built-in signals on first start. Tokens already linked to
+    /// the signal are left alone, so later changes through the admin API stick.
+    pub async fn seed(
+        &self,
+        tokens: Vec<(NewTrackedToken, ProgressionEventType)>,
+    ) -> AppResult<()> {
+        for (token, signal) in tokens {
+            if self.persistence.seed_tracked_token(&token, signal).await? {
+                info!(
+                    "Seeded tracked token {} for {}",
+                    token.symbol,
+                    signal.name()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_decimals(decimals: i32) -> AppResult<()> {
+    if !(0..=MAX_DECIMALS).contains(&decimals) {
+        return Err(AppError::BadRequest(format!(
+            "decimals must be between 0 and {}",
+            MAX_DECIMALS
+        )));
+    }
+    Ok(())
+}
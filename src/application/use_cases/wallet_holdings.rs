@@ -1,5 +1,6 @@
 use crate::app_error::AppResult;
-use crate::entities::wallet_holdings::WalletHoldings;
+use crate::entities::token_amount::TokenAmount;
+use crate::entities::wallet_holdings::{WalletHoldings, WalletHoldingsCacheStats};
 use async_trait::async_trait;
 use std::fmt::Debug;
 use std::sync::Arc;
@@ -7,12 +8,20 @@ use std::sync::Arc;
 #[async_trait]
 pub trait WalletHoldingsClient: Send + Sync + Debug {
     async fn get_wallet_holdings(&self, public_key: &str) -> AppResult<WalletHoldings>;
-    async fn get_token_balance(&self, public_key: &str, token_address: &str) -> AppResult<f64>;
+    async fn get_token_balance(
+        &self,
+        public_key: &str,
+        token_address: &str,
+    ) -> AppResult<TokenAmount>;
     async fn get_staked_token_balance(
         &self,
         public_key: &str,
         token_address: &str,
-    ) -> AppResult<f64>;
+    ) -> AppResult<TokenAmount>;
+    /// Only clients that cache holdings report stats.
+    fn cache_stats(&self) -> Option<WalletHoldingsCacheStats> {
+        None
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -29,7 +38,7 @@ impl WalletHoldingsUseCases {
         &self,
         public_key: &str,
         token_address: &str,
-    ) -> AppResult<f64> {
+    ) -> AppResult<TokenAmount> {
         let balance = self
             .client
             .get_token_balance(public_key, token_address)
@@ -41,11 +50,15 @@ impl WalletHoldingsUseCases {
         &self,
         public_key: &str,
         token_address: &str,
-    ) -> AppResult<f64> {
+    ) -> AppResult<TokenAmount> {
         let balance = self
             .client
             .get_staked_token_balance(public_key, token_address)
             .await?;
         Ok(balance)
     }
+
+    pub fn read_cache_stats(&self) -> Option<WalletHoldingsCacheStats> {
+        self.client.cache_stats()
+    }
 }
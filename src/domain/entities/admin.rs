@@ -0,0 +1,8 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct Admin {
+    pub id: i32,
+    pub public_key: String,
+    pub created_at: DateTime<Utc>,
+}
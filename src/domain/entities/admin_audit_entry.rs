@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+#[derive(Debug, Clone)]
+pub struct AdminAuditEntry {
+    pub id: i32,
+    pub admin_public_key: String,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub payload: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewAdminAuditEntry {
+    pub admin_public_key: String,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Option<String>,
+    pub payload: Value,
+}
+
+impl NewAdminAuditEntry {
+    /// For entities whose id is only known once the mutation ran, such as created ones.
+    pub fn with_entity_id(&self, entity_id: impl ToString) -> Self {
+        Self {
+            entity_id: Some(entity_id.to_string()),
+            ..self.clone()
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct AuthNonce {
+    pub nonce: String,
+    pub public_key: String,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Result of asking for a nonce, which is refused while the public key already has the
+/// maximum of outstanding ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonceClaim {
+    Claimed,
+    /// `retry_at` is when the oldest outstanding nonce expires.
+    TooMany {
+        retry_at: DateTime<Utc>,
+    },
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct AuthSession {
+    pub id: i32,
+    pub public_key: String,
+    pub access_expires_at: DateTime<Utc>,
+    pub refresh_expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Freshly minted token pair. The raw tokens are only ever held here; persistence stores hashes.
+#[derive(Debug, Clone)]
+pub struct IssuedSession {
+    pub public_key: String,
+    pub access_token: String,
+    pub refresh_token: String,
+    pub access_expires_at: DateTime<Utc>,
+    pub refresh_expires_at: DateTime<Utc>,
+}
+
+/// Rows deleted by a purge of ended sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AuthSessionPurge {
+    pub sessions: u64,
+    pub rotated_tokens: u64,
+}
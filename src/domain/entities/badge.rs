@@ -1,3 +1,4 @@
+use crate::entities::badge_rule::BadgeRule;
 use chrono::{DateTime, Utc};
 
 #[derive(Debug)]
@@ -38,3 +39,36 @@ impl From<Badge> for BadgeDto {
         }
     }
 }
+
+/// A badge as configured by admins, independent of any user.
+#[derive(Debug, Clone)]
+pub struct BadgeDefinition {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+    pub score: i32,
+    pub badge_group_id: i32,
+    pub sort_order: i32,
+    pub rule: Option<BadgeRule>,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewBadge {
+    pub title: String,
+    pub description: String,
+    pub score: i32,
+    pub badge_group_id: i32,
+    pub sort_order: i32,
+    pub rule: Option<BadgeRule>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BadgeUpdate {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub score: Option<i32>,
+    pub badge_group_id: Option<i32>,
+    pub sort_order: Option<i32>,
+}
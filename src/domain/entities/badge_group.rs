@@ -5,5 +5,21 @@ pub struct BadgeGroup {
     pub id: i32,
     pub title: String,
     pub description: String,
+    pub sort_order: i32,
+    pub archived_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone)]
+pub struct NewBadgeGroup {
+    pub title: String,
+    pub description: String,
+    pub sort_order: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BadgeGroupUpdate {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub sort_order: Option<i32>,
+}
@@ -1,26 +1,22 @@
+use crate::entities::badge_rule::BadgeRule;
+
 #[derive(Debug)]
 pub struct BadgeRequirement {
     pub badge_id: i32,
-    pub progression_event_type: String,
-    pub operation: String,
-    pub required_count: i32,
+    pub rule: BadgeRule,
 }
 
 #[derive(Debug, Clone)]
 pub struct BadgeRequirementDto {
     pub badge_id: i32,
-    pub progression_event_type: String,
-    pub operation: String,
-    pub required_count: i32,
+    pub rule: BadgeRule,
 }
 
 impl From<BadgeRequirement> for BadgeRequirementDto {
     fn from(requirement: BadgeRequirement) -> Self {
         Self {
             badge_id: requirement.badge_id,
-            progression_event_type: requirement.progression_event_type,
-            operation: requirement.operation,
-            required_count: requirement.required_count,
+            rule: requirement.rule,
         }
     }
 }
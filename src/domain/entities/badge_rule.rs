@@ -0,0 +1,568 @@
+use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::token_amount::serialize_decimal;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+const MAX_RULE_DEPTH: usize = 16;
+/// A hundred years, far beyond any streak but well within what a `Duration` can hold.
+const MAX_HELD_DAYS: i64 = 36_500;
+
+/// Unlock condition of a badge, stored as JSON in `badges.rule`. Thresholds are exact decimals
+/// and may also be given as strings, e.g.
+///
+/// ```json
+/// {"op": "and", "rules": [
+///     {"op": "gte", "signal": "catics_balance_check", "value": 1000},
+///     {"op": "held_for_days", "signal": "catics_balance_check", "days": 30}
+/// ]}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case", deny_unknown_fields)]
+pub enum BadgeRule {
+    Eq {
+        signal: ProgressionEventType,
+        #[serde(serialize_with = "serialize_decimal")]
+        value: Decimal,
+    },
+    Gte {
+        signal: ProgressionEventType,
+        #[serde(serialize_with = "serialize_decimal")]
+        value: Decimal,
+    },
+    Lte {
+        signal: ProgressionEventType,
+        #[serde(serialize_with = "serialize_decimal")]
+        value: Decimal,
+    },
+    Between {
+        signal: ProgressionEventType,
+        #[serde(serialize_with = "serialize_decimal")]
+        min: Decimal,
+        #[serde(serialize_with = "serialize_decimal")]
+        max: Decimal,
+    },
+    And {
+        rules: Vec<BadgeRule>,
+    },
+    Or {
+        rules: Vec<BadgeRule>,
+    },
+    Not {
+        rule: Box<BadgeRule>,
+    },
+    HasBadge {
+        badge_id: i32,
+    },
+    /// The signal has been continuously positive for at least `days` days.
+    HeldForDays {
+        signal: ProgressionEventType,
+        days: i64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SignalProgress {
+    pub count: Decimal,
+    pub held_since: Option<DateTime<Utc>>,
+}
+
+/// Everything a rule may look at for a single applicant.
+#[derive(Debug, Clone)]
+pub struct ProgressionSnapshot {
+    pub beta_applicant_id: i32,
+    pub public_key: String,
+    pub signals: HashMap<ProgressionEventType, SignalProgress>,
+    pub badge_ids: HashSet<i32>,
+    pub now: DateTime<Utc>,
+}
+
+impl ProgressionSnapshot {
+    fn signal(&self, signal: ProgressionEventType) -> Option<SignalProgress> {
+        self.signals.get(&signal).copied()
+    }
+}
+
+impl BadgeRule {
+    /// Whether the rule is known to hold. A signal without stored progress has never been
+    /// synced for the applicant, so rules depending on it are not met until it is.
+    pub fn evaluate(&self, snapshot: &ProgressionSnapshot) -> bool {
+        self.check(snapshot) == Some(true)
+    }
+
+    /// `None` when the outcome depends on a signal without stored progress. `and` and `or`
+    /// still decide when a known part settles them, e.g. `or` with one met rule.
+    fn check(&self, snapshot: &ProgressionSnapshot) -> Option<bool> {
+        match self {
+            BadgeRule::Eq { signal, value } => Some(snapshot.signal(*signal)?.count == *value),
+            BadgeRule::Gte { signal, value } => Some(snapshot.signal(*signal)?.count >= *value),
+            BadgeRule::Lte { signal, value } => Some(snapshot.signal(*signal)?.count <= *value),
+            BadgeRule::Between { signal, min, max } => {
+                Some((*min..=*max).contains(&snapshot.signal(*signal)?.count))
+            }
+            BadgeRule::And { rules } => {
+                let results: Vec<Option<bool>> =
+                    rules.iter().map(|rule| rule.check(snapshot)).collect();
+                if results.contains(&Some(false)) {
+                    Some(false)
+                } else if results.contains(&None) {
+                    None
+                } else {
+                    Some(true)
+                }
+            }
+            BadgeRule::Or { rules } => {
+                let results: Vec<Option<bool>> =
+                    rules.iter().map(|rule| rule.check(snapshot)).collect();
+                if results.contains(&Some(true)) {
+                    Some(true)
+                } else if results.contains(&None) {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            BadgeRule::Not { rule } => rule.check(snapshot).map(|met| !met),
+            BadgeRule::HasBadge { badge_id } => Some(snapshot.badge_ids.contains(badge_id)),
+            BadgeRule::HeldForDays { signal, days } => {
+                let progress = snapshot.signal(*signal)?;
+                Some(
+                    progress.count > Decimal::ZERO
+                        && progress.held_since.is_some_and(|since| {
+                            Duration::try_days(*days)
+                                .is_some_and(|days| snapshot.now - since >= days)
+                        }),
+                )
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.validate_at(0, false)
+    }
+
+    /// `negated` is set below a `not`. Badges unlocked during an evaluation count towards
+    /// `has_badge` in later rules, so a negated `has_badge` could hold early in an evaluation
+    /// and fail once the badge is unlocked, with the result depending on rule order.
+    fn validate_at(&self, depth: usize, negated: bool) -> Result<(), String> {
+        if depth > MAX_RULE_DEPTH {
+            return Err(format!("rule is nested deeper than {}", MAX_RULE_DEPTH));
+        }
+
+        match self {
+            BadgeRule::Between { min, max, .. } if min > max => {
+                Err("between requires min <= max".to_string())
+            }
+            BadgeRule::And { rules } | BadgeRule::Or { rules } => {
+                if rules.is_empty() {
+                    return Err("and/or require at least one rule".to_string());
+                }
+                rules
+                    .iter()
+                    .try_for_each(|rule| rule.validate_at(depth + 1, negated))
+            }
+            BadgeRule::Not { rule } => rule.validate_at(depth + 1, true),
+            BadgeRule::HasBadge { .. } if negated => {
+                Err("has_badge must not be used inside not".to_string())
+            }
+            BadgeRule::HeldForDays { days, .. } if !(0..=MAX_HELD_DAYS).contains(days) => {
+                Err(format!(
+                    "held_for_days requires between 0 and {} days",
+                    MAX_HELD_DAYS
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn referenced_badge_ids(&self) -> Vec<i32> {
+        match self {
+            BadgeRule::HasBadge { badge_id } => vec![*badge_id],
+            BadgeRule::And { rules } | BadgeRule::Or { rules } => rules
+                .iter()
+                .flat_map(BadgeRule::referenced_badge_ids)
+                .collect(),
+            BadgeRule::Not { rule } => rule.referenced_badge_ids(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn referenced_signals(&self) -> Vec<ProgressionEventType> {
+        match self {
+            BadgeRule::Eq { signal, .. }
+            | BadgeRule::Gte { signal, .. }
+            | BadgeRule::Lte { signal, .. }
+            | BadgeRule::Between { signal, .. }
+            | BadgeRule::HeldForDays { signal, .. } => vec![*signal],
+            BadgeRule::And { rules } | BadgeRule::Or { rules } => rules
+                .iter()
+                .flat_map(BadgeRule::referenced_signals)
+                .collect(),
+            BadgeRule::Not { rule } => rule.referenced_signals(),
+            BadgeRule::HasBadge { .. } => Vec::new(),
+        }
+    }
+
+    /// First simple comparison in the rule, used to fill the flat requirement fields clients
+    /// showed before rules became composable.
+    pub fn primary_comparison(&self) -> Option<(ProgressionEventType, &'static str, Decimal)> {
+        match self {
+            BadgeRule::Eq { signal, value } => Some((*signal, "eq", *value)),
+            BadgeRule::Gte { signal, value } => Some((*signal, "gte", *value)),
+            BadgeRule::Lte { signal, value } => Some((*signal, "lte", *value)),
+            BadgeRule::Between { signal, min, .. } => Some((*signal, "between", *min)),
+            BadgeRule::And { rules } | BadgeRule::Or { rules } => {
+                rules.iter().find_map(BadgeRule::primary_comparison)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Evaluates every rule against the snapshot and returns the ids of badges that are not held
+/// yet but are now satisfied. Badges unlocked here count towards `has_badge` in later rules.
+pub fn unlocked_badge_ids(rules: &[(i32, BadgeRule)], snapshot: &ProgressionSnapshot) -> Vec<i32> {
+    let mut held = snapshot.badge_ids.clone();
+    let mut unlocked = Vec::new();
+
+    loop {
+        let evaluation = ProgressionSnapshot {
+            badge_ids: held.clone(),
+            ..snapshot.clone()
+        };
+        let newly: Vec<i32> = rules
+            .iter()
+            .filter(|(badge_id, rule)| !held.contains(badge_id) && rule.evaluate(&evaluation))
+            .map(|(badge_id, _)| *badge_id)
+            .collect();
+
+        if newly.is_empty() {
+            return unlocked;
+        }
+
+        held.extend(newly.iter().copied());
+        unlocked.extend(newly);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+    use std::str::FromStr;
+
+    const CATICS: ProgressionEventType = ProgressionEventType::CaticsBalanceCheck;
+    const JUP: ProgressionEventType = ProgressionEventType::JupStaked;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-02-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn snapshot(signals: &[(ProgressionEventType, i64)], badge_ids: &[i32]) -> ProgressionSnapshot {
+        ProgressionSnapshot {
+            beta_applicant_id: 1,
+            public_key: "7YttLkHDoNj9wyDur5pM1ejNaAvT9X4eqaYcHQqtj2G5".to_string(),
+            signals: signals
+                .iter()
+                .map(|(signal, count)| {
+                    (
+                        *signal,
+                        SignalProgress {
+                            count: Decimal::from(*count),
+                            held_since: Some(now() - Duration::days(10)),
+                        },
+                    )
+                })
+                .collect(),
+            badge_ids: badge_ids.iter().copied().collect(),
+            now: now(),
+        }
+    }
+
+    fn gte(signal: ProgressionEventType, value: i64) -> BadgeRule {
+        BadgeRule::Gte {
+            signal,
+            value: Decimal::from(value),
+        }
+    }
+
+    fn has_badge(badge_id: i32) -> BadgeRule {
+        BadgeRule::HasBadge { badge_id }
+    }
+
+    #[test]
+    fn compares_signals() {
+        let user = snapshot(&[(CATICS, 100)], &[]);
+        let value = |value: i64| Decimal::from(value);
+
+        assert!(
+            BadgeRule::Eq {
+                signal: CATICS,
+                value: value(100)
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            !BadgeRule::Eq {
+                signal: CATICS,
+                value: value(99)
+            }
+            .evaluate(&user)
+        );
+        assert!(gte(CATICS, 100).evaluate(&user));
+        assert!(!gte(CATICS, 101).evaluate(&user));
+        assert!(
+            BadgeRule::Lte {
+                signal: CATICS,
+                value: value(100)
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            !BadgeRule::Lte {
+                signal: CATICS,
+                value: value(99)
+            }
+            .evaluate(&user)
+        );
+        let between = |min: i64, max: i64| BadgeRule::Between {
+            signal: CATICS,
+            min: value(min),
+            max: value(max),
+        };
+        assert!(between(100, 100).evaluate(&user));
+        assert!(between(50, 150).evaluate(&user));
+        assert!(!between(101, 150).evaluate(&user));
+    }
+
+    #[test]
+    fn signals_without_progress_are_not_evaluable() {
+        let user = snapshot(&[(CATICS, 100)], &[]);
+        let zero = BadgeRule::Eq {
+            signal: JUP,
+            value: Decimal::ZERO,
+        };
+        let not = |rule: BadgeRule| BadgeRule::Not {
+            rule: Box::new(rule),
+        };
+
+        assert!(!zero.evaluate(&user));
+        assert!(!not(zero.clone()).evaluate(&user));
+        assert!(!not(gte(JUP, 1)).evaluate(&user));
+        assert!(
+            !BadgeRule::HeldForDays {
+                signal: JUP,
+                days: 0
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            !BadgeRule::And {
+                rules: vec![gte(CATICS, 1), zero.clone()]
+            }
+            .evaluate(&user)
+        );
+        // A known part can still settle the outcome
+        assert!(
+            BadgeRule::Or {
+                rules: vec![zero.clone(), gte(CATICS, 1)]
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            not(BadgeRule::And {
+                rules: vec![zero, gte(CATICS, 101)]
+            })
+            .evaluate(&user)
+        );
+    }
+
+    #[test]
+    fn combines_rules() {
+        let user = snapshot(&[(CATICS, 100)], &[7]);
+        let met = gte(CATICS, 100);
+        let unmet = gte(JUP, 1);
+        let user = ProgressionSnapshot {
+            signals: snapshot(&[(CATICS, 100), (JUP, 0)], &[]).signals,
+            ..user
+        };
+
+        assert!(
+            BadgeRule::And {
+                rules: vec![met.clone(), has_badge(7)]
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            !BadgeRule::And {
+                rules: vec![met.clone(), unmet.clone()]
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            BadgeRule::Or {
+                rules: vec![unmet.clone(), met.clone()]
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            !BadgeRule::Or {
+                rules: vec![unmet.clone(), has_badge(8)]
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            BadgeRule::Not {
+                rule: Box::new(unmet)
+            }
+            .evaluate(&user)
+        );
+        assert!(
+            !BadgeRule::Not {
+                rule: Box::new(met)
+            }
+            .evaluate(&user)
+        );
+        assert!(has_badge(7).evaluate(&user));
+        assert!(!has_badge(8).evaluate(&user));
+    }
+
+    #[test]
+    fn held_for_days_needs_a_long_enough_positive_streak() {
+        let held = |days: i64| BadgeRule::HeldForDays {
+            signal: CATICS,
+            days,
+        };
+        let user = snapshot(&[(CATICS, 100)], &[]);
+
+        assert!(held(10).evaluate(&user));
+        assert!(!held(11).evaluate(&user));
+        assert!(!held(0).evaluate(&snapshot(&[(CATICS, 0)], &[])));
+        // Too large to be a `Duration`, which must not panic
+        assert!(!held(i64::MAX).evaluate(&user));
+
+        let mut without_streak = user.clone();
+        without_streak.signals.get_mut(&CATICS).unwrap().held_since = None;
+        assert!(!held(0).evaluate(&without_streak));
+    }
+
+    #[test]
+    fn validates_structure() {
+        let mut deep = gte(CATICS, 1);
+        for _ in 0..=MAX_RULE_DEPTH {
+            deep = BadgeRule::And { rules: vec![deep] };
+        }
+        assert!(deep.validate().is_err());
+
+        assert!(BadgeRule::And { rules: vec![] }.validate().is_err());
+        assert!(BadgeRule::Or { rules: vec![] }.validate().is_err());
+        let between = |min: i64, max: i64| BadgeRule::Between {
+            signal: CATICS,
+            min: Decimal::from(min),
+            max: Decimal::from(max),
+        };
+        assert!(between(2, 1).validate().is_err());
+        assert!(between(1, 1).validate().is_ok());
+
+        let held = |days: i64| BadgeRule::HeldForDays {
+            signal: CATICS,
+            days,
+        };
+        assert!(held(0).validate().is_ok());
+        assert!(held(MAX_HELD_DAYS).validate().is_ok());
+        assert!(held(-1).validate().is_err());
+        assert!(held(MAX_HELD_DAYS + 1).validate().is_err());
+    }
+
+    #[test]
+    fn rejects_negated_has_badge() {
+        let negated = |rule: BadgeRule| BadgeRule::Not {
+            rule: Box::new(rule),
+        };
+
+        assert!(has_badge(1).validate().is_ok());
+        assert!(negated(gte(CATICS, 1)).validate().is_ok());
+        assert!(negated(has_badge(1)).validate().is_err());
+        assert!(
+            negated(BadgeRule::Or {
+                rules: vec![gte(CATICS, 1), has_badge(1)]
+            })
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn unlocks_chained_badges_in_one_evaluation() {
+        // Listed in reverse, so each badge needs one more round than the one before
+        let rules = vec![
+            (3, has_badge(2)),
+            (2, has_badge(1)),
+            (1, gte(CATICS, 100)),
+            (4, gte(JUP, 1)),
+        ];
+
+        let mut unlocked = unlocked_badge_ids(&rules, &snapshot(&[(CATICS, 100)], &[]));
+        unlocked.sort_unstable();
+        assert_eq!(unlocked, vec![1, 2, 3]);
+
+        // Badges already held are not unlocked again but still count
+        let unlocked = unlocked_badge_ids(&rules, &snapshot(&[], &[1, 2]));
+        assert_eq!(unlocked, vec![3]);
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let json = json!({"op": "and", "rules": [
+            {"op": "gte", "signal": "catics_balance_check", "value": 1000},
+            {"op": "between", "signal": "signal_12", "min": "0.5", "max": "12345678901234567.89"},
+            {"op": "not", "rule": {"op": "lte", "signal": "jup_staked", "value": 2}},
+            {"op": "has_badge", "badge_id": 3},
+            {"op": "held_for_days", "signal": "catics_balance_check", "days": 30}
+        ]});
+
+        let rule: BadgeRule = serde_json::from_value(json).unwrap();
+        let BadgeRule::And { rules } = &rule else {
+            panic!("expected and, got {:?}", rule);
+        };
+        assert_eq!(
+            rules[1],
+            BadgeRule::Between {
+                signal: ProgressionEventType::Other(12),
+                min: Decimal::from_str("0.5").unwrap(),
+                max: Decimal::from_str("12345678901234567.89").unwrap(),
+            }
+        );
+
+        let serialized = serde_json::to_value(&rule).unwrap();
+        // Thresholds are written as strings, however they were given
+        assert_eq!(serialized["rules"][1]["min"], Value::from("0.5"));
+        assert_eq!(
+            serialized["rules"][1]["max"],
+            Value::from("12345678901234567.89")
+        );
+        assert_eq!(
+            serde_json::from_value::<BadgeRule>(serialized).unwrap(),
+            rule
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_operators_and_fields() {
+        for json in [
+            json!({"op": "gt", "signal": "catics_balance_check", "value": 1}),
+            json!({"op": "gte", "signal": "catics_balance_check", "value": 1, "extra": 1}),
+            json!({"op": "gte", "signal": "unknown_signal", "value": 1}),
+            json!({"op": "gte", "signal": "catics_balance_check", "value": "lots"}),
+        ] {
+            assert!(
+                serde_json::from_value::<BadgeRule>(json.clone()).is_err(),
+                "{}",
+                json
+            );
+        }
+    }
+}
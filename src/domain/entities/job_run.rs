@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+    /// The process died while the run was in progress; detected by the next run.
+    Abandoned,
+}
+
+impl JobRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobRunStatus::Running => "running",
+            JobRunStatus::Succeeded => "succeeded",
+            JobRunStatus::Failed => "failed",
+            JobRunStatus::Abandoned => "abandoned",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct JobRun {
+    pub id: i32,
+    pub job_name: String,
+    pub status: String,
+    pub summary: Value,
+    pub error: Option<String>,
+    /// Cron slot of a scheduled run, `None` for runs started on demand.
+    pub scheduled_for: Option<DateTime<Utc>>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
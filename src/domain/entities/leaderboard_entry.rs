@@ -1,9 +1,12 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 
 #[derive(Debug)]
 pub struct LeaderboardEntry {
     pub public_key: String,
     pub total_score: i32,
+    /// Exact score of a signal board, which ranks by it; `total_score` is its capped whole part.
+    pub score: Option<Decimal>,
     pub rank: u32,
     pub previous_rank: Option<u32>,
     pub created_at: DateTime<Utc>,
@@ -13,6 +16,7 @@ pub struct LeaderboardEntry {
 pub struct LeaderboardEntryDto {
     pub public_key: String,
     pub total_score: i32,
+    pub score: Option<Decimal>,
     pub rank: u32,
     pub previous_rank: Option<u32>,
 }
@@ -22,8 +26,36 @@ impl From<LeaderboardEntry> for LeaderboardEntryDto {
         Self {
             public_key: entry.public_key,
             total_score: entry.total_score,
+            score: entry.score,
             rank: entry.rank,
             previous_rank: entry.previous_rank,
         }
     }
 }
+
+/// Keyset position in the total leaderboard; the given rank itself is excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardCursor {
+    After(u32),
+    Before(u32),
+}
+
+#[derive(Debug, Clone)]
+pub struct LeaderboardSlice {
+    pub entries: Vec<LeaderboardEntryDto>,
+    pub total: u32,
+    pub has_previous: bool,
+    pub has_next: bool,
+}
+
+/// A user's place on the total leaderboard. The official values are fixed by the last daily
+/// refresh; the projected values add the user's current badge total, as the next refresh will.
+#[derive(Debug, Clone)]
+pub struct UserStanding {
+    /// `None` until the user is on the leaderboard.
+    pub official_rank: Option<u32>,
+    pub official_score: i32,
+    pub previous_rank: Option<u32>,
+    pub projected_rank: u32,
+    pub projected_score: i32,
+}
@@ -0,0 +1,43 @@
+use crate::entities::progression_event_type::ProgressionEventType;
+use std::fmt;
+use std::str::FromStr;
+
+/// What a leaderboard ranks by. Written as `total`, `badge_group:<id>` or `signal:<name>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeaderboardKind {
+    /// Accumulated daily score, see `leaderboard_entries`.
+    #[default]
+    Total,
+    /// Score of the badges in one badge group.
+    BadgeGroup(i32),
+    /// Current progress count of one progression signal.
+    Signal(ProgressionEventType),
+}
+
+impl FromStr for LeaderboardKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            None if value == "total" => Ok(LeaderboardKind::Total),
+            Some(("badge_group", id)) => id
+                .parse()
+                .map(LeaderboardKind::BadgeGroup)
+                .map_err(|_| format!("invalid badge group id '{}'", id)),
+            Some(("signal", name)) => ProgressionEventType::from_name(name)
+                .map(LeaderboardKind::Signal)
+                .ok_or_else(|| format!("unknown signal '{}'", name)),
+            _ => Err(format!("unknown leaderboard kind '{}'", value)),
+        }
+    }
+}
+
+impl fmt::Display for LeaderboardKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeaderboardKind::Total => write!(f, "total"),
+            LeaderboardKind::BadgeGroup(id) => write!(f, "badge_group:{}", id),
+            LeaderboardKind::Signal(signal) => write!(f, "signal:{}", signal.name()),
+        }
+    }
+}
@@ -0,0 +1,271 @@
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+
+const TOP_MOVERS: usize = 10;
+
+/// An applicant as seen by the daily refresh, before scores are accumulated.
+#[derive(Debug, Clone)]
+pub struct LeaderboardStanding {
+    pub beta_applicant_id: i32,
+    pub public_key: String,
+    pub applicant_created_at: DateTime<Utc>,
+    /// `None` if the applicant has no leaderboard entry yet.
+    pub total_score: Option<i32>,
+    pub rank: Option<i32>,
+    pub badge_total: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankedEntry {
+    pub beta_applicant_id: i32,
+    pub public_key: String,
+    pub total_score: i32,
+    pub rank: i32,
+    pub previous_rank: Option<i32>,
+}
+
+impl RankedEntry {
+    /// Positive when the entry climbed.
+    pub fn rank_change(&self) -> Option<i32> {
+        self.previous_rank.map(|previous| previous - self.rank)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardRefreshStats {
+    pub entries_updated: usize,
+    pub entries_created: usize,
+    pub max_score: Option<i32>,
+    pub min_score: Option<i32>,
+    /// Number of existing entries whose rank changed.
+    pub movers: usize,
+    /// Largest rank changes in either direction, biggest first.
+    pub top_movers: Vec<RankedEntry>,
+}
+
+/// What a leaderboard refresh did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefreshOutcome {
+    /// The day's badge totals were added to every score and the table re-ranked.
+    Credited(LeaderboardRefreshStats),
+    /// The table was re-ranked on the stored scores without crediting a day.
+    Reranked(LeaderboardRefreshStats),
+    /// The day had already been credited, so nothing changed.
+    AlreadyCredited,
+}
+
+impl RefreshOutcome {
+    pub fn stats(&self) -> Option<&LeaderboardRefreshStats> {
+        match self {
+            Self::Credited(stats) | Self::Reranked(stats) => Some(stats),
+            Self::AlreadyCredited => None,
+        }
+    }
+}
+
+/// Adds each applicant's badge total to their score and ranks everyone by score, earlier
+/// applicants first on ties. Ranks are always `1..=n` without gaps.
+pub fn rank_standings(standings: Vec<LeaderboardStanding>) -> Vec<RankedEntry> {
+    let mut scored: Vec<(LeaderboardStanding, i32)> = standings
+        .into_iter()
+        .map(|standing| {
+            let score = standing.total_score.unwrap_or(0) + standing.badge_total;
+            (standing, score)
+        })
+        .collect();
+
+    scored.sort_by_key(|(standing, score)| {
+        (
+            Reverse(*score),
+            standing.applicant_created_at,
+            standing.beta_applicant_id,
+        )
+    });
+
+    scored
+        .into_iter()
+        .zip(1..)
+        .map(|((standing, total_score), rank)| RankedEntry {
+            beta_applicant_id: standing.beta_applicant_id,
+            public_key: standing.public_key,
+            total_score,
+            rank,
+            previous_rank: standing.rank,
+        })
+        .collect()
+}
+
+/// Rank an entry holds between the two write phases of a refresh.
+///
+/// `rank` is unique and Postgres checks that on every row, so writing the final ranks directly
+/// fails as soon as two entries swap places. Every entry is first moved to a negative rank
+/// derived from its (unique) applicant id, which cannot collide with any real rank.
+pub fn temporary_rank(beta_applicant_id: i32) -> i32 {
+    -beta_applicant_id
+}
+
+pub fn refresh_stats(entries: &[RankedEntry]) -> LeaderboardRefreshStats {
+    let mut movers: Vec<&RankedEntry> = entries
+        .iter()
+        .filter(|entry| entry.rank_change().is_some_and(|change| change != 0))
+        .collect();
+    movers.sort_by_key(|entry| (Reverse(entry.rank_change().unwrap_or(0).abs()), entry.rank));
+
+    LeaderboardRefreshStats {
+        entries_updated: entries
+            .iter()
+            .filter(|entry| entry.previous_rank.is_some())
+            .count(),
+        entries_created: entries
+            .iter()
+            .filter(|entry| entry.previous_rank.is_none())
+            .count(),
+        max_score: entries.iter().map(|entry| entry.total_score).max(),
+        min_score: entries.iter().map(|entry| entry.total_score).min(),
+        movers: movers.len(),
+        top_movers: movers.into_iter().take(TOP_MOVERS).cloned().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    fn standing(
+        id: i32,
+        score: Option<i32>,
+        rank: Option<i32>,
+        badges: i32,
+    ) -> LeaderboardStanding {
+        LeaderboardStanding {
+            beta_applicant_id: id,
+            public_key: format!("key-{}", id),
+            applicant_created_at: Utc.timestamp_opt(1_700_000_000 + i64::from(id), 0).unwrap(),
+            total_score: score,
+            rank,
+            badge_total: badges,
+        }
+    }
+
+    /// Applies rank updates one row at a time against a unique index, like Postgres does for
+    /// a non-deferrable unique constraint.
+    fn apply(table: &mut HashMap<i32, i32>, updates: &[(i32, i32)]) -> Result<(), i32> {
+        for (id, rank) in updates {
+            if table
+                .iter()
+                .any(|(other, held)| other != id && held == rank)
+            {
+                return Err(*rank);
+            }
+            table.insert(*id, *rank);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn swapped_entries_get_unique_ranks() {
+        let ranked = rank_standings(vec![
+            standing(1, Some(100), Some(1), 0),
+            standing(2, Some(90), Some(2), 50),
+        ]);
+
+        assert_eq!(ranked[0].beta_applicant_id, 2);
+        assert_eq!((ranked[0].rank, ranked[0].previous_rank), (1, Some(2)));
+        assert_eq!((ranked[1].rank, ranked[1].previous_rank), (2, Some(1)));
+    }
+
+    #[test]
+    fn writing_final_ranks_directly_collides_on_swap() {
+        let mut table = HashMap::from([(1, 1), (2, 2)]);
+        let ranked = rank_standings(vec![
+            standing(1, Some(100), Some(1), 0),
+            standing(2, Some(90), Some(2), 50),
+        ]);
+        let updates: Vec<(i32, i32)> = ranked
+            .iter()
+            .map(|entry| (entry.beta_applicant_id, entry.rank))
+            .collect();
+
+        assert_eq!(apply(&mut table, &updates), Err(1));
+    }
+
+    #[test]
+    fn temporary_ranks_avoid_collisions() {
+        let standings = vec![
+            standing(1, Some(10), Some(1), 0),
+            standing(2, Some(9), Some(2), 5),
+            standing(3, Some(8), Some(3), 10),
+            standing(4, None, None, 30),
+        ];
+        let mut table: HashMap<i32, i32> = standings
+            .iter()
+            .filter_map(|s| s.rank.map(|rank| (s.beta_applicant_id, rank)))
+            .collect();
+        let ranked = rank_standings(standings);
+
+        let temporary: Vec<(i32, i32)> = ranked
+            .iter()
+            .map(|entry| {
+                (
+                    entry.beta_applicant_id,
+                    temporary_rank(entry.beta_applicant_id),
+                )
+            })
+            .collect();
+        let final_ranks: Vec<(i32, i32)> = ranked
+            .iter()
+            .map(|entry| (entry.beta_applicant_id, entry.rank))
+            .collect();
+
+        assert_eq!(apply(&mut table, &temporary), Ok(()));
+        assert_eq!(apply(&mut table, &final_ranks), Ok(()));
+
+        let mut ranks: Vec<i32> = table.values().copied().collect();
+        ranks.sort();
+        assert_eq!(ranks, vec![1, 2, 3, 4]);
+        assert_eq!(table[&4], 1);
+    }
+
+    #[test]
+    fn ties_go_to_earlier_applicants() {
+        let ranked = rank_standings(vec![
+            standing(3, Some(5), Some(1), 0),
+            standing(1, Some(5), Some(2), 0),
+            standing(2, None, None, 5),
+        ]);
+
+        let order: Vec<i32> = ranked.iter().map(|entry| entry.beta_applicant_id).collect();
+        assert_eq!(order, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn stats_report_scores_and_movers() {
+        let ranked = rank_standings(vec![
+            standing(1, Some(100), Some(1), 0),
+            standing(2, Some(90), Some(2), 0),
+            standing(3, Some(10), Some(3), 200),
+            standing(4, None, None, 0),
+        ]);
+
+        let stats = refresh_stats(&ranked);
+
+        assert_eq!(stats.entries_updated, 3);
+        assert_eq!(stats.entries_created, 1);
+        assert_eq!(stats.max_score, Some(210));
+        assert_eq!(stats.min_score, Some(0));
+        assert_eq!(stats.movers, 3);
+        assert_eq!(stats.top_movers[0].beta_applicant_id, 3);
+        assert_eq!(stats.top_movers[0].rank_change(), Some(2));
+    }
+
+    #[test]
+    fn stats_of_empty_leaderboard() {
+        let stats = refresh_stats(&[]);
+
+        assert_eq!(stats.entries_updated, 0);
+        assert_eq!(stats.max_score, None);
+        assert!(stats.top_movers.is_empty());
+    }
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct LeaderboardSnapshot {
+    pub rank: u32,
+    pub total_score: i32,
+    pub taken_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoverDirection {
+    Climbers,
+    Fallers,
+}
+
+/// Rank change of an entry since its first snapshot in a time window.
+#[derive(Debug, Clone)]
+pub struct RankMovement {
+    pub public_key: String,
+    pub rank: u32,
+    pub total_score: i32,
+    pub baseline_rank: u32,
+    /// Positive when the entry climbed.
+    pub change: i32,
+}
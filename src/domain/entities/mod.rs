@@ -1,9 +1,24 @@
+pub mod admin;
+pub mod admin_audit_entry;
+pub mod auth_nonce;
+pub mod auth_session;
 pub mod badge;
 pub mod badge_group;
 pub mod badge_requirement;
+pub mod badge_rule;
 pub mod beta_applicant;
 pub mod cat;
+pub mod job_run;
 pub mod leaderboard_entry;
+pub mod leaderboard_kind;
+pub mod leaderboard_refresh;
+pub mod leaderboard_snapshot;
+pub mod progression_event;
 pub mod progression_event_type;
+pub mod season;
+pub mod sync_report;
+pub mod token_amount;
+pub mod tracked_token;
+pub mod user_event;
 pub mod user_progression;
 pub mod wallet_holdings;
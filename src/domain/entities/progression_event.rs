@@ -0,0 +1,47 @@
+use crate::entities::progression_event_type::ProgressionEventType;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
+/// Where a recorded progression value came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressionSource {
+    Signup,
+    WalletHoldings,
+    Referrals,
+}
+
+impl ProgressionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProgressionSource::Signup => "signup",
+            ProgressionSource::WalletHoldings => "wallet_holdings",
+            ProgressionSource::Referrals => "referrals",
+        }
+    }
+}
+
+/// One entry of the append-only `progression_events` log.
+#[derive(Debug, Clone)]
+pub struct ProgressionEvent {
+    pub event_type: ProgressionEventType,
+    pub value: Decimal,
+    pub source: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Window of `progression_events` to read: the latest `limit` events recorded in
+/// `[from, to)`.
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressionHistoryQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: u32,
+}
+
+/// Events of a window, oldest first. `has_more` is set when older events in the window were
+/// left out; they are read by passing the first `recorded_at` as the next `to`.
+#[derive(Debug, Clone)]
+pub struct ProgressionHistory {
+    pub events: Vec<ProgressionEvent>,
+    pub has_more: bool,
+}
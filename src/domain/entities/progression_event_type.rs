@@ -1,15 +1,123 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+
+const OTHER_PREFIX: &str = "signal_";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProgressionEventType {
-    BetaApplicantCreated = 1,
-    CaticsBalanceCheck = 2,
-    MineSeason0 = 3,
-    CatLevelUp = 4,
-    JupStaked = 5,
-    ReferralCreated = 6,
+    BetaApplicantCreated,
+    CaticsBalanceCheck,
+    MineSeason0,
+    CatLevelUp,
+    JupStaked,
+    ReferralCreated,
+    /// A signal that only exists in `progression_event_types`, such as the balance of a token
+    /// added to `tracked_tokens` at runtime. Named `signal_<id>`.
+    Other(i32),
 }
 
 impl ProgressionEventType {
+    pub const ALL: [ProgressionEventType; 6] = [
+        ProgressionEventType::BetaApplicantCreated,
+        ProgressionEventType::CaticsBalanceCheck,
+        ProgressionEventType::MineSeason0,
+        ProgressionEventType::CatLevelUp,
+        ProgressionEventType::JupStaked,
+        ProgressionEventType::ReferralCreated,
+    ];
+
     pub fn id(&self) -> i32 {
-        *self as i32
+        match self {
+            ProgressionEventType::BetaApplicantCreated => 1,
+            ProgressionEventType::CaticsBalanceCheck => 2,
+            ProgressionEventType::MineSeason0 => 3,
+            ProgressionEventType::CatLevelUp => 4,
+            ProgressionEventType::JupStaked => 5,
+            ProgressionEventType::ReferralCreated => 6,
+            ProgressionEventType::Other(id) => *id,
+        }
+    }
+
+    /// Matches `progression_event_types.event_type`.
+    pub fn name(&self) -> Cow<'static, str> {
+        match self {
+            ProgressionEventType::BetaApplicantCreated => "beta_applicant_created".into(),
+            ProgressionEventType::CaticsBalanceCheck => "catics_balance_check".into(),
+            ProgressionEventType::MineSeason0 => "mine_season_0".into(),
+            ProgressionEventType::CatLevelUp => "cat_level_up".into(),
+            ProgressionEventType::JupStaked => "jup_staked".into(),
+            ProgressionEventType::ReferralCreated => "referral_created".into(),
+            ProgressionEventType::Other(id) => format!("{}{}", OTHER_PREFIX, id).into(),
+        }
+    }
+
+    pub fn from_id(id: i32) -> Option<Self> {
+        if id <= 0 {
+            return None;
+        }
+        Some(
+            Self::ALL
+                .into_iter()
+                .find(|event_type| event_type.id() == id)
+                .unwrap_or(ProgressionEventType::Other(id)),
+        )
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(id) = name.strip_prefix(OTHER_PREFIX) {
+            return id.parse().ok().and_then(Self::from_id);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.name() == name)
+    }
+}
+
+impl Serialize for ProgressionEventType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for ProgressionEventType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Self::from_name(&name)
+            .ok_or_else(|| D::Error::custom(format!("unknown progression event type '{}'", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for event_type in ProgressionEventType::ALL
+            .into_iter()
+            .chain([ProgressionEventType::Other(42)])
+        {
+            assert_eq!(
+                ProgressionEventType::from_name(&event_type.name()),
+                Some(event_type)
+            );
+            assert_eq!(
+                ProgressionEventType::from_id(event_type.id()),
+                Some(event_type)
+            );
+        }
+    }
+
+    #[test]
+    fn database_only_signals_are_named_by_id() {
+        assert_eq!(ProgressionEventType::Other(7).name(), "signal_7");
+        assert_eq!(
+            ProgressionEventType::from_name("signal_5"),
+            Some(ProgressionEventType::JupStaked)
+        );
+        assert_eq!(ProgressionEventType::from_name("signal_0"), None);
+        assert_eq!(ProgressionEventType::from_name("signal_x"), None);
+        assert_eq!(ProgressionEventType::from_name("unknown"), None);
     }
 }
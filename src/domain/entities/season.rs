@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone)]
+pub struct Season {
+    pub id: i32,
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    /// Planned end; the season stays open until it is finalized.
+    pub ends_at: Option<DateTime<Utc>>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
+impl Season {
+    pub fn is_open(&self) -> bool {
+        self.finalized_at.is_none()
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.is_open() && self.ends_at.is_some_and(|ends_at| ends_at <= now)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SeasonUpdate {
+    pub name: Option<String>,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewSeason {
+    pub name: String,
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+/// Result of freezing the open season and starting the next one.
+#[derive(Debug, Clone)]
+pub struct SeasonRollover {
+    pub finalized: Season,
+    pub next: Season,
+    pub archived_entries: u64,
+}
@@ -0,0 +1,113 @@
+use crate::entities::progression_event_type::ProgressionEventType;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// What syncing a single signal produced.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignalOutcome {
+    /// The signal was read and its progress recorded with this value.
+    Value(Decimal),
+    /// The source could not be read, for a reason users may see; the full error is only
+    /// logged. Stored progress is kept and badges depending on the signal are not evaluated.
+    Unavailable(String),
+    /// The signal was not synced on purpose, e.g. because its token is disabled.
+    Skipped(String),
+}
+
+impl SignalOutcome {
+    pub fn status(&self) -> SignalSyncStatus {
+        match self {
+            SignalOutcome::Value(_) => SignalSyncStatus::Synced,
+            SignalOutcome::Unavailable(_) => SignalSyncStatus::Unavailable,
+            SignalOutcome::Skipped(_) => SignalSyncStatus::Skipped,
+        }
+    }
+
+    pub fn value(&self) -> Option<Decimal> {
+        match self {
+            SignalOutcome::Value(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Why no value was recorded.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            SignalOutcome::Value(_) => None,
+            SignalOutcome::Unavailable(reason) | SignalOutcome::Skipped(reason) => Some(reason),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalSyncStatus {
+    Synced,
+    Unavailable,
+    Skipped,
+}
+
+impl SignalSyncStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignalSyncStatus::Synced => "synced",
+            SignalSyncStatus::Unavailable => "unavailable",
+            SignalSyncStatus::Skipped => "skipped",
+        }
+    }
+}
+
+impl fmt::Display for SignalSyncStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SignalSyncStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "synced" => Ok(SignalSyncStatus::Synced),
+            "unavailable" => Ok(SignalSyncStatus::Unavailable),
+            "skipped" => Ok(SignalSyncStatus::Skipped),
+            _ => Err(format!("Unknown signal sync status '{}'", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SignalSyncResult {
+    pub signal: ProgressionEventType,
+    pub outcome: SignalOutcome,
+}
+
+/// What a single user's progression sync recorded and unlocked.
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    pub signals: Vec<SignalSyncResult>,
+    pub unlocked_badge_ids: Vec<i32>,
+}
+
+impl SyncReport {
+    pub fn failed_signals(&self) -> usize {
+        self.unavailable_signals().len()
+    }
+
+    pub fn unavailable_signals(&self) -> HashSet<ProgressionEventType> {
+        self.signals
+            .iter()
+            .filter(|result| result.outcome.status() == SignalSyncStatus::Unavailable)
+            .map(|result| result.signal)
+            .collect()
+    }
+}
+
+/// Result of asking to start a sync that is subject to a cooldown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncClaim {
+    Claimed,
+    CoolingDown { retry_at: DateTime<Utc> },
+}
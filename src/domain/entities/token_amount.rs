@@ -0,0 +1,191 @@
+use rust_decimal::Decimal;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::fmt;
+
+/// Token balance as an integer number of base units and the decimals of its mint, so that
+/// sums and conversions are exact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TokenAmount {
+    pub raw: u128,
+    pub decimals: u8,
+}
+
+impl TokenAmount {
+    pub fn new(raw: u128, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    /// Parses an amount in base units, such as the `amount` string of a token account.
+    pub fn from_raw_str(raw: &str, decimals: u8) -> Result<Self, String> {
+        let raw = raw
+            .parse()
+            .map_err(|e| format!("Invalid token amount '{}': {}", raw, e))?;
+        Ok(Self { raw, decimals })
+    }
+
+    /// Parses an amount in whole tokens such as "1234.5". Digits beyond the mint's decimals
+    /// cannot be held and are dropped.
+    pub fn from_ui_str(value: &str, decimals: u8) -> Result<Self, String> {
+        let invalid = || format!("Invalid token amount '{}'", value);
+        let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+        if whole.is_empty() && fraction.is_empty()
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let mut digits = whole.to_string();
+        digits.extend(
+            fraction
+                .chars()
+                .chain(std::iter::repeat('0'))
+                .take(decimals as usize),
+        );
+        let raw = if digits.is_empty() {
+            0
+        } else {
+            digits.parse().map_err(|_| invalid())?
+        };
+        Ok(Self { raw, decimals })
+    }
+
+    /// Adds two amounts, at the larger of their decimals. `None` on overflow.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let decimals = self.decimals.max(other.decimals);
+        let raw = self
+            .rescaled(decimals)?
+            .checked_add(other.rescaled(decimals)?)?;
+        Some(Self { raw, decimals })
+    }
+
+    /// The same amount in base units of `decimals`. `None` when those cannot hold it exactly.
+    pub fn with_decimals(self, decimals: u8) -> Option<Self> {
+        let raw = if decimals >= self.decimals {
+            self.rescaled(decimals)?
+        } else {
+            let factor = 10u128.checked_pow(u32::from(self.decimals - decimals))?;
+            self.raw
+                .is_multiple_of(factor)
+                .then_some(self.raw / factor)?
+        };
+        Some(Self { raw, decimals })
+    }
+
+    fn rescaled(self, decimals: u8) -> Option<u128> {
+        10u128
+            .checked_pow(u32::from(decimals - self.decimals))
+            .and_then(|factor| self.raw.checked_mul(factor))
+    }
+
+    pub fn to_decimal(self) -> Result<Decimal, String> {
+        i128::try_from(self.raw)
+            .ok()
+            .and_then(|raw| Decimal::try_from_i128_with_scale(raw, u32::from(self.decimals)).ok())
+            .ok_or_else(|| format!("Token amount {} is too large to store", self))
+    }
+}
+
+impl fmt::Display for TokenAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = format!("{:0>width$}", self.raw, width = self.decimals as usize + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - self.decimals as usize);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            write!(f, "{}", whole)
+        } else {
+            write!(f, "{}.{}", whole, fraction)
+        }
+    }
+}
+
+/// Decimal string, so that a value has the same JSON type however large or precise it is.
+pub fn decimal_to_json(value: Decimal) -> Value {
+    Value::String(value.normalize().to_string())
+}
+
+pub fn serialize_decimal<S: Serializer>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error> {
+    decimal_to_json(*value).serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn sums_base_units_exactly() {
+        let a = TokenAmount::from_raw_str("1500000", 6).unwrap();
+        let b = TokenAmount::from_raw_str("2500001", 6).unwrap();
+
+        let sum = a.checked_add(b).unwrap();
+
+        assert_eq!(sum, TokenAmount::new(4_000_001, 6));
+        assert_eq!(sum.to_string(), "4.000001");
+        assert_eq!(
+            sum.to_decimal().unwrap(),
+            Decimal::from_str("4.000001").unwrap()
+        );
+    }
+
+    #[test]
+    fn keeps_amounts_beyond_the_i32_range() {
+        let amount = TokenAmount::from_raw_str("5000000000123456789", 9).unwrap();
+
+        assert_eq!(
+            amount.to_decimal().unwrap(),
+            Decimal::from_str("5000000000.123456789").unwrap()
+        );
+        assert!(TokenAmount::new(u128::MAX, 0).to_decimal().is_err());
+        assert!(
+            TokenAmount::new(u128::MAX, 0)
+                .checked_add(TokenAmount::new(1, 0))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parses_ui_amounts_at_the_mint_decimals() {
+        assert_eq!(
+            TokenAmount::from_ui_str("42.5", 6),
+            Ok(TokenAmount::new(42_500_000, 6))
+        );
+        assert_eq!(
+            TokenAmount::from_ui_str("0.1234567", 6),
+            Ok(TokenAmount::new(123_456, 6))
+        );
+        assert_eq!(TokenAmount::from_ui_str("7", 0), Ok(TokenAmount::new(7, 0)));
+        assert!(TokenAmount::from_ui_str("-1", 6).is_err());
+        assert!(TokenAmount::from_ui_str("1e9", 6).is_err());
+        assert!(TokenAmount::from_ui_str(".", 6).is_err());
+    }
+
+    #[test]
+    fn changes_decimals_only_when_exact() {
+        let amount = TokenAmount::new(1_500_000, 6);
+
+        assert_eq!(
+            amount.with_decimals(9),
+            Some(TokenAmount::new(1_500_000_000, 9))
+        );
+        assert_eq!(amount.with_decimals(1), Some(TokenAmount::new(15, 1)));
+        assert_eq!(amount.with_decimals(0), None);
+        assert_eq!(TokenAmount::new(1, 0).with_decimals(40), None);
+    }
+
+    #[test]
+    fn decimals_are_always_json_strings() {
+        assert_eq!(decimal_to_json(Decimal::from(1000)), Value::from("1000"));
+        assert_eq!(
+            decimal_to_json(Decimal::from_str("4.000100").unwrap()),
+            Value::from("4.0001")
+        );
+        assert_eq!(
+            decimal_to_json(Decimal::from_str("12345678901234567.89").unwrap()),
+            Value::from("12345678901234567.89")
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use crate::entities::progression_event_type::ProgressionEventType;
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Balance held in the wallet's token accounts.
+    Held,
+    /// Balance staked through a provider that reports it per wallet, currently JUP governance.
+    Staked,
+}
+
+impl TokenKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenKind::Held => "held",
+            TokenKind::Staked => "staked",
+        }
+    }
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TokenKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "held" => Ok(TokenKind::Held),
+            "staked" => Ok(TokenKind::Staked),
+            other => Err(format!("unknown token kind '{}'", other)),
+        }
+    }
+}
+
+/// A token whose balance is synced into the progression `signal`.
+#[derive(Debug, Clone)]
+pub struct TrackedToken {
+    pub id: i32,
+    pub mint: String,
+    pub symbol: String,
+    pub decimals: i32,
+    pub kind: TokenKind,
+    pub signal: ProgressionEventType,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTrackedToken {
+    pub mint: String,
+    pub symbol: String,
+    pub decimals: i32,
+    pub kind: TokenKind,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TrackedTokenUpdate {
+    pub symbol: Option<String>,
+    pub decimals: Option<i32>,
+    pub enabled: Option<bool>,
+}
@@ -0,0 +1,54 @@
+use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::token_amount::serialize_decimal;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Something that happened to a single user, pushed to the event streams they have open.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserEvent {
+    pub public_key: String,
+    #[serde(flatten)]
+    pub kind: UserEventKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    tag = "type",
+    rename_all = "snake_case",
+    rename_all_fields = "camelCase"
+)]
+pub enum UserEventKind {
+    BadgeUnlocked {
+        badge_id: i32,
+    },
+    ProgressionUpdated {
+        signal: ProgressionEventType,
+        #[serde(serialize_with = "serialize_decimal")]
+        count: Decimal,
+    },
+    RankChanged {
+        rank: i32,
+        previous_rank: Option<i32>,
+        total_score: i32,
+    },
+}
+
+impl UserEventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UserEventKind::BadgeUnlocked { .. } => "badge_unlocked",
+            UserEventKind::ProgressionUpdated { .. } => "progression_updated",
+            UserEventKind::RankChanged { .. } => "rank_changed",
+        }
+    }
+}
+
+impl UserEvent {
+    pub fn new(public_key: impl Into<String>, kind: UserEventKind) -> Self {
+        Self {
+            public_key: public_key.into(),
+            kind,
+        }
+    }
+}
@@ -1,13 +1,22 @@
+use crate::entities::sync_report::SignalSyncStatus;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+
 #[derive(Debug)]
 pub struct UserProgression {
     pub progression_event_type: String,
-    pub current_progress: i32,
+    pub current_progress: Decimal,
+    /// Outcome of the latest sync of the signal; `None` if it was never synced.
+    pub sync_status: Option<SignalSyncStatus>,
+    pub last_synced_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct UserProgressionDto {
     pub progression_event_type: String,
-    pub current_progress: i32,
+    pub current_progress: Decimal,
+    pub sync_status: Option<SignalSyncStatus>,
+    pub last_synced_at: Option<DateTime<Utc>>,
 }
 
 impl From<UserProgression> for UserProgressionDto {
@@ -15,6 +24,8 @@ impl From<UserProgression> for UserProgressionDto {
         Self {
             progression_event_type: progression.progression_event_type,
             current_progress: progression.current_progress,
+            sync_status: progression.sync_status,
+            last_synced_at: progression.last_synced_at,
         }
     }
 }
@@ -1,8 +1,21 @@
+use crate::entities::token_amount::TokenAmount;
 use std::collections::HashMap;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WalletHoldings {
     pub public_key: String,
-    pub token_holdings: HashMap<String, f64>,
-    pub staked_token_holdings: HashMap<String, f64>,
+    pub token_holdings: HashMap<String, TokenAmount>,
+    /// Staked balances come from a separate source, so when it fails the reason is kept here
+    /// and the held balances stay usable.
+    pub staked_token_holdings: Result<HashMap<String, TokenAmount>, String>,
+}
+
+/// Counters of a caching wallet holdings client since the process started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalletHoldingsCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Requests that waited for an identical in-flight upstream fetch instead of starting one.
+    pub coalesced: u64,
+    pub entries: usize,
 }
@@ -13,11 +13,16 @@ pub fn create_app(app_state: AppState) -> Router {
             "https://staging.app.catalytics.pro".parse().unwrap(),
             "http://localhost:4200".parse().unwrap(),
         ])
-        .allow_methods([http::Method::GET, http::Method::POST, http::Method::PATCH])
+        .allow_methods([
+            http::Method::GET,
+            http::Method::POST,
+            http::Method::PATCH,
+            http::Method::DELETE,
+        ])
         .allow_headers(Any);
 
     Router::new()
-        .nest("/api", adapters::http::routes::router())
+        .nest("/api", adapters::http::routes::router(app_state.clone()))
         .with_state(app_state)
         .layer(cors)
 }
@@ -0,0 +1,80 @@
+use std::env;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonceStore {
+    Postgres,
+    Memory,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    /// When enabled, every signed message must also carry a server-issued nonce, which is
+    /// consumed. Signed messages are always checked as Sign-In-With-Solana messages, and the
+    /// session exchange always requires a nonce.
+    pub require_nonce: bool,
+    /// Deprecation window for clients that sign free text: while enabled, a correctly signed
+    /// message that is not a Sign-In-With-Solana message still authenticates, for the message
+    /// max age. Ignored whenever a nonce is required.
+    pub allow_legacy_messages: bool,
+    pub allowed_domains: Vec<String>,
+    pub nonce_ttl_seconds: i64,
+    pub max_message_age_seconds: i64,
+    pub nonce_store: NonceStore,
+    pub access_token_ttl_seconds: i64,
+    pub refresh_token_ttl_seconds: i64,
+    /// Wallets that are always admins, on top of the `admins` table.
+    pub admin_public_keys: Vec<String>,
+    /// Bearer token for internal callers such as batch jobs. Internal routes reject every
+    /// request while it is unset.
+    pub internal_api_token: Option<String>,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            require_nonce: env::var("AUTH_REQUIRE_NONCE")
+                .map(|value| value == "true")
+                .unwrap_or(false),
+            allow_legacy_messages: env::var("AUTH_ALLOW_LEGACY_MESSAGES")
+                .map(|value| value == "true")
+                .unwrap_or(true),
+            allowed_domains: env::var("AUTH_ALLOWED_DOMAINS")
+                .unwrap_or_else(|_| {
+                    "app.catalytics.pro,staging.app.catalytics.pro,localhost:4200".to_string()
+                })
+                .split(',')
+                .map(|domain| domain.trim().to_string())
+                .filter(|domain| !domain.is_empty())
+                .collect(),
+            nonce_ttl_seconds: env::var("AUTH_NONCE_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(300),
+            max_message_age_seconds: env::var("AUTH_MESSAGE_MAX_AGE_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(300),
+            nonce_store: match env::var("AUTH_NONCE_STORE").as_deref() {
+                Ok("memory") => NonceStore::Memory,
+                _ => NonceStore::Postgres,
+            },
+            access_token_ttl_seconds: env::var("AUTH_ACCESS_TOKEN_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(900),
+            refresh_token_ttl_seconds: env::var("AUTH_REFRESH_TOKEN_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(30 * 24 * 60 * 60),
+            admin_public_keys: env::var("ADMIN_PUBLIC_KEYS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|public_key| public_key.trim().to_string())
+                .filter(|public_key| !public_key.is_empty())
+                .collect(),
+            internal_api_token: env::var("INTERNAL_API_TOKEN")
+                .ok()
+                .filter(|token| !token.is_empty()),
+        }
+    }
+}
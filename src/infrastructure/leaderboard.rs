@@ -0,0 +1,19 @@
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct LeaderboardConfig {
+    /// How long the public leaderboard is served from memory, and how long clients and CDNs
+    /// may cache it. Refreshes in this process invalidate it earlier.
+    pub public_cache_ttl_seconds: i64,
+}
+
+impl Default for LeaderboardConfig {
+    fn default() -> Self {
+        Self {
+            public_cache_ttl_seconds: env::var("LEADERBOARD_PUBLIC_CACHE_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(60),
+        }
+    }
+}
@@ -1,11 +1,29 @@
 use crate::adapters::persistence::PostgresPersistence;
 use crate::infrastructure::database::init_db;
-use crate::infrastructure::wallet_holdings::HttpWalletHoldingClient;
+use crate::infrastructure::solana_rpc::SolanaRpcWalletHoldingsClient;
+use crate::infrastructure::upstream::UpstreamConfig;
+use crate::infrastructure::wallet_holdings::{HttpWalletHoldingClient, catalytics_client};
+use crate::infrastructure::wallet_holdings_cache::{
+    CachedWalletHoldingsClient, WalletHoldingsCacheConfig,
+};
+use crate::infrastructure::wallet_holdings_fallback::{
+    FallbackWalletHoldingsClient, WalletHoldingsProvidersConfig,
+};
+use crate::use_cases::wallet_holdings::WalletHoldingsClient;
+use std::sync::Arc;
 
 pub mod app;
+pub mod auth;
 pub mod database;
+pub mod leaderboard;
+pub mod scheduler;
 pub mod setup;
+pub mod solana_rpc;
+pub mod sync;
+pub mod upstream;
 pub mod wallet_holdings;
+pub mod wallet_holdings_cache;
+pub mod wallet_holdings_fallback;
 
 pub async fn postgres_persistence() -> anyhow::Result<PostgresPersistence> {
     let pool = init_db().await?;
@@ -13,7 +31,30 @@ pub async fn postgres_persistence() -> anyhow::Result<PostgresPersistence> {
     Ok(persistence)
 }
 
-pub async fn wallet_holding_client() -> anyhow::Result<HttpWalletHoldingClient> {
-    let client = HttpWalletHoldingClient::with_defaults()?;
-    Ok(client)
+pub async fn wallet_holding_client() -> anyhow::Result<CachedWalletHoldingsClient> {
+    let config = WalletHoldingsProvidersConfig::default();
+    let catalytics_client = Arc::new(catalytics_client(UpstreamConfig::default())?);
+    let mut providers: Vec<(String, Arc<dyn WalletHoldingsClient>)> = Vec::new();
+    for name in config.providers {
+        let provider: Arc<dyn WalletHoldingsClient> = match name.as_str() {
+            "jupiter" => Arc::new(HttpWalletHoldingClient::with_defaults(
+                catalytics_client.clone(),
+            )?),
+            "rpc" => Arc::new(SolanaRpcWalletHoldingsClient::with_defaults(
+                catalytics_client.clone(),
+            )?),
+            _ => anyhow::bail!("Unknown wallet holdings provider '{}'", name),
+        };
+        providers.push((name, provider));
+    }
+
+    let client: Arc<dyn WalletHoldingsClient> = match providers.len() {
+        0 => anyhow::bail!("WALLET_HOLDINGS_PROVIDERS names no provider"),
+        1 => providers.remove(0).1,
+        _ => Arc::new(FallbackWalletHoldingsClient::new(providers)),
+    };
+    Ok(CachedWalletHoldingsClient::new(
+        client,
+        WalletHoldingsCacheConfig::default(),
+    ))
 }
@@ -0,0 +1,114 @@
+use crate::adapters::http::app_state::AppState;
+use crate::use_cases::auth_purge::AuthPurgeJob;
+use crate::use_cases::badge_sync::BadgeSyncJob;
+use crate::use_cases::job::{Job, JobUseCases};
+use chrono::Utc;
+use cron::Schedule;
+use std::env;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    /// Runs the scheduler inside the API process. Replicas coordinate through advisory locks
+    /// and claim each scheduled time once, so enabling it everywhere is safe.
+    pub enabled: bool,
+    /// Cron expression with seconds, e.g. `0 0 1 * * *` for daily at 01:00 UTC.
+    pub badge_sync_schedule: String,
+    pub badge_sync_concurrency: usize,
+    /// Cron expression with seconds for deleting expired sign-in nonces and ended sessions.
+    pub auth_purge_schedule: String,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: env::var("SCHEDULER_ENABLED")
+                .map(|value| value == "true")
+                .unwrap_or(true),
+            badge_sync_schedule: env::var("BADGE_SYNC_SCHEDULE")
+                .unwrap_or_else(|_| "0 0 1 * * *".to_string()),
+            badge_sync_concurrency: env::var("BADGE_SYNC_CONCURRENCY")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(8),
+            auth_purge_schedule: env::var("AUTH_PURGE_SCHEDULE")
+                .unwrap_or_else(|_| "0 0 * * * *".to_string()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ScheduledJob {
+    schedule: Schedule,
+    job: Arc<dyn Job>,
+}
+
+#[derive(Debug)]
+pub struct Scheduler {
+    job_use_cases: Arc<JobUseCases>,
+    jobs: Vec<ScheduledJob>,
+}
+
+impl Scheduler {
+    pub fn new(job_use_cases: Arc<JobUseCases>) -> Self {
+        Self {
+            job_use_cases,
+            jobs: Vec::new(),
+        }
+    }
+
+    pub fn with_job(mut self, schedule: &str, job: Arc<dyn Job>) -> anyhow::Result<Self> {
+        let schedule = Schedule::from_str(schedule)
+            .map_err(|e| anyhow::anyhow!("Invalid schedule for {}: {}", job.name(), e))?;
+        self.jobs.push(ScheduledJob { schedule, job });
+        Ok(self)
+    }
+
+    /// Starts one task per job that sleeps until the next scheduled time and then runs it.
+    pub fn spawn(self) -> Vec<JoinHandle<()>> {
+        self.jobs
+            .into_iter()
+            .map(|scheduled| {
+                let job_use_cases = self.job_use_cases.clone();
+                tokio::spawn(async move {
+                    loop {
+                        let Some(next) = scheduled.schedule.upcoming(Utc).next() else {
+                            info!("Job {} has no upcoming runs", scheduled.job.name());
+                            return;
+                        };
+                        info!("Next run of job {} at {}", scheduled.job.name(), next);
+
+                        let wait = (next - Utc::now()).to_std().unwrap_or_default();
+                        tokio::time::sleep(wait).await;
+
+                        if let Err(e) = job_use_cases
+                            .run_scheduled(scheduled.job.as_ref(), next)
+                            .await
+                        {
+                            error!("Job {} could not run: {}", scheduled.job.name(), e);
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn init_scheduler(app_state: &AppState, config: &SchedulerConfig) -> anyhow::Result<Scheduler> {
+    let badge_sync = BadgeSyncJob::new(
+        app_state.beta_applicant_use_cases.clone(),
+        app_state.beta_applicant_progression_use_cases.clone(),
+        app_state.badge_use_cases.clone(),
+        app_state.leaderboard_use_cases.clone(),
+        config.badge_sync_concurrency,
+    );
+
+    let auth_purge = AuthPurgeJob::new(app_state.auth_use_cases.clone());
+
+    Scheduler::new(app_state.job_use_cases.clone())
+        .with_job(&config.badge_sync_schedule, Arc::new(badge_sync))?
+        .with_job(&config.auth_purge_schedule, Arc::new(auth_purge))
+}
@@ -1,28 +1,79 @@
 use crate::adapters::http::app_state::AppState;
+use crate::adapters::persistence::in_memory::InMemoryPersistence;
+use crate::entities::progression_event_type::ProgressionEventType;
+use crate::entities::tracked_token::{NewTrackedToken, TokenKind};
+use crate::infrastructure::auth::{AuthConfig, NonceStore};
+use crate::infrastructure::leaderboard::LeaderboardConfig;
+use crate::infrastructure::sync::SyncConfig;
+use crate::infrastructure::wallet_holdings::jup_mint;
 use crate::infrastructure::{postgres_persistence, wallet_holding_client};
+use crate::use_cases::admin::AdminUseCases;
+use crate::use_cases::auth::{AuthLifetimes, AuthNoncePersistence, AuthUseCases};
 use crate::use_cases::badge::BadgeUseCases;
 use crate::use_cases::badge_group::BadgeGroupUseCases;
 use crate::use_cases::beta_applicant::BetaApplicantUseCases;
 use crate::use_cases::beta_applicant_progression::BetaApplicantProgressionUseCases;
 use crate::use_cases::cat::CatUseCases;
+use crate::use_cases::event::EventBus;
+use crate::use_cases::job::JobUseCases;
 use crate::use_cases::leaderboard::LeaderboardUseCases;
+use crate::use_cases::tracked_token::TrackedTokenUseCases;
+use crate::use_cases::wallet_holdings::WalletHoldingsUseCases;
+use chrono::Duration;
+use std::env;
 use std::sync::Arc;
 use tracing_subscriber::EnvFilter;
 
+/// Streams that fall this many events behind skip the oldest ones.
+const EVENT_BUS_CAPACITY: usize = 1024;
+
 pub async fn init_app_state() -> anyhow::Result<AppState> {
     let postgres_arc = Arc::new(postgres_persistence().await?);
     let wallet_holding_arc = Arc::new(wallet_holding_client().await?);
+    let auth_config = AuthConfig::default();
+    let leaderboard_config = LeaderboardConfig::default();
+    let sync_config = SyncConfig::default();
+    let event_bus = EventBus::new(EVENT_BUS_CAPACITY);
+    postgres_arc.spawn_event_listener(event_bus.clone());
+
+    let nonce_persistence: Arc<dyn AuthNoncePersistence> = match auth_config.nonce_store {
+        NonceStore::Postgres => postgres_arc.clone(),
+        NonceStore::Memory => Arc::new(InMemoryPersistence::new()),
+    };
 
     let beta_applicant_use_cases = BetaApplicantUseCases::new(postgres_arc.clone());
-    let badge_use_case = BadgeUseCases::new(postgres_arc.clone());
+    let badge_use_case = BadgeUseCases::new(postgres_arc.clone(), postgres_arc.clone());
     let badge_group_use_case = BadgeGroupUseCases::new(postgres_arc.clone());
     let beta_applicant_progression_use_cases = BetaApplicantProgressionUseCases::new(
         postgres_arc.clone(),
         postgres_arc.clone(),
         wallet_holding_arc.clone(),
+        postgres_arc.clone(),
+        postgres_arc.clone(),
+        Duration::seconds(sync_config.self_sync_cooldown_seconds),
     );
     let cat_use_cases = CatUseCases::new(postgres_arc.clone());
-    let leaderboard_use_cases = LeaderboardUseCases::new(postgres_arc.clone());
+    let wallet_holdings_use_cases = WalletHoldingsUseCases::new(wallet_holding_arc.clone());
+    let tracked_token_use_cases = TrackedTokenUseCases::new(postgres_arc.clone(), jup_mint());
+    tracked_token_use_cases.seed(legacy_token_seeds()).await?;
+    let leaderboard_use_cases = Arc::new(LeaderboardUseCases::new(
+        postgres_arc.clone(),
+        Duration::seconds(leaderboard_config.public_cache_ttl_seconds),
+        postgres_arc.clone(),
+    ));
+    leaderboard_use_cases.spawn_cache_invalidation(&event_bus);
+    let admin_use_cases =
+        AdminUseCases::new(postgres_arc.clone(), auth_config.admin_public_keys.clone());
+    let job_use_cases = JobUseCases::new(postgres_arc.clone());
+    let auth_use_cases = AuthUseCases::new(
+        nonce_persistence,
+        postgres_arc.clone(),
+        AuthLifetimes {
+            nonce: Duration::seconds(auth_config.nonce_ttl_seconds),
+            access_token: Duration::seconds(auth_config.access_token_ttl_seconds),
+            refresh_token: Duration::seconds(auth_config.refresh_token_ttl_seconds),
+        },
+    );
 
     Ok(AppState {
         beta_applicant_use_cases: Arc::new(beta_applicant_use_cases),
@@ -30,10 +81,48 @@ pub async fn init_app_state() -> anyhow::Result<AppState> {
         badge_group_use_cases: Arc::new(badge_group_use_case),
         beta_applicant_progression_use_cases: Arc::new(beta_applicant_progression_use_cases),
         cat_use_cases: Arc::new(cat_use_cases),
-        leaderboard_use_cases: Arc::new(leaderboard_use_cases),
+        leaderboard_use_cases,
+        auth_use_cases: Arc::new(auth_use_cases),
+        admin_use_cases: Arc::new(admin_use_cases),
+        job_use_cases: Arc::new(job_use_cases),
+        auth_config: Arc::new(auth_config),
+        leaderboard_config: Arc::new(leaderboard_config),
+        tracked_token_use_cases: Arc::new(tracked_token_use_cases),
+        sync_config: Arc::new(sync_config),
+        wallet_holdings_use_cases: Arc::new(wallet_holdings_use_cases),
+        event_bus: Arc::new(event_bus),
     })
 }
 
+/// Tokens of the built-in signals, which used to be configured with `CATICS_TOKEN_ADDRESS` and
+/// `JUP_TOKEN_ADDRESS`. They are only read to fill an empty registry.
+fn legacy_token_seeds() -> Vec<(NewTrackedToken, ProgressionEventType)> {
+    let mut seeds = vec![(
+        NewTrackedToken {
+            mint: jup_mint(),
+            symbol: "JUP".to_string(),
+            decimals: 6,
+            kind: TokenKind::Staked,
+        },
+        ProgressionEventType::JupStaked,
+    )];
+    if let Ok(mint) = env::var("CATICS_TOKEN_ADDRESS") {
+        seeds.push((
+            NewTrackedToken {
+                mint,
+                symbol: "CATICS".to_string(),
+                decimals: env::var("CATICS_TOKEN_DECIMALS")
+                    .ok()
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(6),
+                kind: TokenKind::Held,
+            },
+            ProgressionEventType::CaticsBalanceCheck,
+        ));
+    }
+    seeds
+}
+
 pub fn init_tracing() {
     let env_filter = EnvFilter::try_from_default_env()
         .unwrap_or_else(|_| EnvFilter::new("catalytics_core=info,sqlx=warn"));
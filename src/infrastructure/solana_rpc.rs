@@ -0,0 +1,145 @@
+use crate::adapters::client::solana_rpc::{
+    JsonRpcRequest, JsonRpcResponse, TokenAccount, TokenAccountsResult,
+};
+use crate::adapters::client::wallet_holdings::StakedJupResponse;
+use crate::app_error::{AppError, AppResult};
+use crate::entities::token_amount::TokenAmount;
+use crate::entities::wallet_holdings::WalletHoldings;
+use crate::infrastructure::upstream::{UpstreamClient, UpstreamConfig};
+use crate::infrastructure::wallet_holdings::jup_mint;
+use axum::http::HeaderMap;
+use serde_json::json;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use tracing::warn;
+
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+const PROVIDER: &str = "solana_rpc";
+
+#[derive(Debug, Clone)]
+pub struct SolanaRpcConfig {
+    pub rpc_url: String,
+    /// Staked balances are not visible on-chain as token accounts, so they still come from
+    /// the Catalytics governance endpoint.
+    pub catalytics_base_url: String,
+    pub jup_mint: String,
+    pub upstream: UpstreamConfig,
+}
+
+impl Default for SolanaRpcConfig {
+    fn default() -> Self {
+        Self {
+            rpc_url: env::var("SOLANA_RPC_URL")
+                .unwrap_or_else(|_| "https://api.mainnet-beta.solana.com".to_string()),
+            catalytics_base_url: env::var("CATALYTICS_API_BASE_URL").unwrap(),
+            jup_mint: jup_mint(),
+            upstream: UpstreamConfig::default(),
+        }
+    }
+}
+
+/// Reads SPL balances straight from a Solana RPC node, for both the Token and the
+/// Token-2022 program.
+#[derive(Debug)]
+pub struct SolanaRpcWalletHoldingsClient {
+    pub rpc_client: UpstreamClient,
+    pub catalytics_client: Arc<UpstreamClient>,
+    pub config: SolanaRpcConfig,
+}
+
+impl SolanaRpcWalletHoldingsClient {
+    pub fn new(config: SolanaRpcConfig, catalytics_client: Arc<UpstreamClient>) -> AppResult<Self> {
+        let rpc_client = UpstreamClient::new(PROVIDER, HeaderMap::new(), config.upstream.clone())?;
+
+        Ok(Self {
+            rpc_client,
+            catalytics_client,
+            config,
+        })
+    }
+
+    pub fn with_defaults(catalytics_client: Arc<UpstreamClient>) -> AppResult<Self> {
+        Self::new(SolanaRpcConfig::default(), catalytics_client)
+    }
+
+    pub async fn get_token_accounts(
+        &self,
+        owner: &str,
+        program_id: &str,
+    ) -> AppResult<Vec<TokenAccount>> {
+        let request = JsonRpcRequest::new(
+            "getTokenAccountsByOwner",
+            json!([owner, { "programId": program_id }, { "encoding": "jsonParsed" }]),
+        );
+        let response: JsonRpcResponse<TokenAccountsResult> = self
+            .rpc_client
+            .post_json(&self.config.rpc_url, &request)
+            .await?;
+
+        match (response.result, response.error) {
+            (Some(result), _) => Ok(result.value),
+            (None, Some(error)) => Err(rpc_error(format!(
+                "RPC error {}: {}",
+                error.code, error.message
+            ))),
+            (None, None) => Err(rpc_error(
+                "RPC response has neither result nor error".to_string(),
+            )),
+        }
+    }
+
+    pub async fn get_staked_jup(&self, public_key: &str) -> AppResult<StakedJupResponse> {
+        let url = format!(
+            "{}/v2/solana/jup/governance/staked/{}",
+            self.config.catalytics_base_url, public_key
+        );
+        self.catalytics_client.get_json(&url).await
+    }
+
+    pub fn convert_to_wallet_holdings(
+        &self,
+        public_key: String,
+        token_accounts: Vec<TokenAccount>,
+        staked_jup: AppResult<StakedJupResponse>,
+    ) -> AppResult<WalletHoldings> {
+        let mut token_holdings: HashMap<String, TokenAmount> = HashMap::new();
+        for account in token_accounts {
+            let info = account.account.data.parsed.info;
+            let amount =
+                TokenAmount::from_raw_str(&info.token_amount.amount, info.token_amount.decimals)
+                    .map_err(rpc_error)?;
+            let total = match token_holdings.get(&info.mint) {
+                Some(total) => total.checked_add(amount).ok_or_else(|| {
+                    rpc_error(format!("Token amount overflow for mint {}", info.mint))
+                })?,
+                None => amount,
+            };
+            token_holdings.insert(info.mint, total);
+        }
+        token_holdings.retain(|_, amount| amount.raw > 0);
+
+        let staked_token_holdings = staked_jup
+            .and_then(|staked_jup| staked_jup.amount())
+            .map(|amount| HashMap::from([(self.config.jup_mint.clone(), amount)]))
+            .map_err(|e| {
+                warn!("Staked JUP is unavailable for {}: {}", public_key, e);
+                e.to_string()
+            });
+
+        Ok(WalletHoldings {
+            public_key,
+            token_holdings,
+            staked_token_holdings,
+        })
+    }
+}
+
+fn rpc_error(message: String) -> AppError {
+    AppError::Upstream {
+        provider: PROVIDER.to_string(),
+        message,
+    }
+}
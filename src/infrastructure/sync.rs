@@ -0,0 +1,24 @@
+use std::env;
+
+#[derive(Debug, Clone)]
+pub struct SyncConfig {
+    /// Minimum time between two syncs a user triggers for their own wallet.
+    pub self_sync_cooldown_seconds: i64,
+    /// Keeps the deprecated `GET /api/badges/sync?publicKey=` route available. When disabled
+    /// it answers `410 Gone`.
+    pub legacy_sync_enabled: bool,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            self_sync_cooldown_seconds: env::var("SELF_SYNC_COOLDOWN_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(300),
+            legacy_sync_enabled: env::var("LEGACY_BADGE_SYNC_ENABLED")
+                .map(|value| value == "true")
+                .unwrap_or(true),
+        }
+    }
+}
@@ -0,0 +1,416 @@
+use crate::app_error::{AppError, AppResult};
+use axum::http::HeaderMap;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::{Client, RequestBuilder, Response, StatusCode, header};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+#[derive(Debug, Clone)]
+pub struct UpstreamConfig {
+    pub connect_timeout_ms: u64,
+    /// Longest wait for the next chunk of a response.
+    pub read_timeout_ms: u64,
+    /// Retries after the first attempt, for timeouts, connection errors, 429 and 5xx.
+    pub max_retries: u32,
+    pub retry_base_delay_ms: u64,
+    /// Upper bound of a single backoff. A longer `Retry-After` fails the request instead.
+    pub retry_max_delay_ms: u64,
+    /// Consecutive failed requests that open the circuit of a provider.
+    pub breaker_failure_threshold: u32,
+    /// How long an open circuit fails requests before letting one through again.
+    pub breaker_open_seconds: u64,
+}
+
+impl Default for UpstreamConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_ms: env::var("UPSTREAM_CONNECT_TIMEOUT_MS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(2_000),
+            read_timeout_ms: env::var("UPSTREAM_READ_TIMEOUT_MS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(10_000),
+            max_retries: env::var("UPSTREAM_MAX_RETRIES")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(2),
+            retry_base_delay_ms: env::var("UPSTREAM_RETRY_BASE_DELAY_MS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(250),
+            retry_max_delay_ms: env::var("UPSTREAM_RETRY_MAX_DELAY_MS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(5_000),
+            breaker_failure_threshold: env::var("UPSTREAM_BREAKER_FAILURE_THRESHOLD")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(5),
+            breaker_open_seconds: env::var("UPSTREAM_BREAKER_OPEN_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(30),
+        }
+    }
+}
+
+/// HTTP client for one upstream provider, with timeouts, retries and a circuit breaker.
+/// Every failure is an `AppError::Upstream` carrying the provider name.
+#[derive(Debug)]
+pub struct UpstreamClient {
+    provider: String,
+    client: Client,
+    config: UpstreamConfig,
+    breaker: CircuitBreaker,
+}
+
+/// Outcome of a failed request. Rejections are answers the provider gave on purpose, such as
+/// a 404, and do not count against its circuit.
+enum Failure {
+    Unavailable(String),
+    Rejected(String),
+}
+
+impl UpstreamClient {
+    pub fn new(provider: &str, headers: HeaderMap, config: UpstreamConfig) -> AppResult<Self> {
+        let client = Client::builder()
+            .default_headers(headers)
+            .connect_timeout(Duration::from_millis(config.connect_timeout_ms))
+            .read_timeout(Duration::from_millis(config.read_timeout_ms))
+            .build()
+            .map_err(|e| AppError::Internal(format!("Failed to build client: {}", e)))?;
+
+        Ok(Self {
+            provider: provider.to_string(),
+            client,
+            breaker: CircuitBreaker::new(
+                config.breaker_failure_threshold,
+                Duration::from_secs(config.breaker_open_seconds),
+            ),
+            config,
+        })
+    }
+
+    pub async fn get_json<T: DeserializeOwned>(&self, url: &str) -> AppResult<T> {
+        self.request_json(|| self.client.get(url)).await
+    }
+
+    pub async fn post_json<B: Serialize + Sync, T: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &B,
+    ) -> AppResult<T> {
+        self.request_json(|| self.client.post(url).json(body)).await
+    }
+
+    async fn request_json<T: DeserializeOwned>(
+        &self,
+        request: impl Fn() -> RequestBuilder + Sync,
+    ) -> AppResult<T> {
+        if !self.breaker.allow() {
+            return Err(self.error("circuit open after repeated failures".to_string()));
+        }
+
+        match self.send_with_retries(&request).await {
+            Ok(body) => {
+                self.breaker.record_success();
+                Ok(body)
+            }
+            Err(Failure::Rejected(message)) => {
+                self.breaker.record_success();
+                Err(self.error(message))
+            }
+            Err(Failure::Unavailable(message)) => {
+                if self.breaker.record_failure() {
+                    warn!("Opened circuit of upstream {}: {}", self.provider, message);
+                }
+                Err(self.error(message))
+            }
+        }
+    }
+
+    async fn send_with_retries<T: DeserializeOwned>(
+        &self,
+        request: &(impl Fn() -> RequestBuilder + Sync),
+    ) -> Result<T, Failure> {
+        let mut attempt = 0;
+        loop {
+            let (message, retry_after) = match request().send().await {
+                Ok(response) if response.status().is_success() => {
+                    return response.json::<T>().await.map_err(|e| {
+                        Failure::Unavailable(format!("Failed to parse JSON response: {}", e))
+                    });
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let retry_after = retry_after(&response);
+                    let message = format!(
+                        "Request failed with status {}: {}",
+                        status,
+                        response
+                            .text()
+                            .await
+                            .unwrap_or_else(|_| "Unknown error".to_string())
+                    );
+                    if !is_retryable(status) {
+                        return Err(Failure::Rejected(message));
+                    }
+                    (message, retry_after)
+                }
+                Err(e) if e.is_timeout() || e.is_connect() || e.is_request() => {
+                    (format!("HTTP request failed: {}", e), None)
+                }
+                Err(e) => {
+                    return Err(Failure::Unavailable(format!("HTTP request failed: {}", e)));
+                }
+            };
+
+            if attempt >= self.config.max_retries {
+                return Err(Failure::Unavailable(message));
+            }
+            let delay = match retry_delay(&self.config, attempt, retry_after) {
+                Some(delay) => delay,
+                None => return Err(Failure::Unavailable(message)),
+            };
+            warn!(
+                "Retrying {} request in {:?} after: {}",
+                self.provider, delay, message
+            );
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    fn error(&self, message: String) -> AppError {
+        AppError::Upstream {
+            provider: self.provider.clone(),
+            message,
+        }
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Reads `Retry-After` as either seconds or an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(header::RETRY_AFTER)?.to_str().ok()?;
+    parse_retry_after(value, Utc::now())
+}
+
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = DateTime::parse_from_rfc2822(value.trim()).ok()?;
+    Some((at.with_timezone(&Utc) - now).to_std().unwrap_or_default())
+}
+
+/// Exponential backoff with jitter, never shorter than `Retry-After`. Returns `None` when the
+/// provider asks to wait longer than the configured maximum.
+fn retry_delay(
+    config: &UpstreamConfig,
+    attempt: u32,
+    retry_after: Option<Duration>,
+) -> Option<Duration> {
+    let max_delay = Duration::from_millis(config.retry_max_delay_ms);
+    let backoff_ms = config
+        .retry_base_delay_ms
+        .saturating_mul(1 << attempt.min(16))
+        .min(config.retry_max_delay_ms);
+    let backoff = Duration::from_millis(rand::rng().random_range(backoff_ms / 2..=backoff_ms));
+
+    match retry_after {
+        Some(retry_after) if retry_after > max_delay => None,
+        Some(retry_after) => Some(retry_after.max(backoff)),
+        None => Some(backoff),
+    }
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+/// Opens after `failure_threshold` consecutive failures. Once `open_for` has passed, one
+/// request is let through per period; its success closes the circuit again.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_for: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    pub fn allow(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match state.open_until {
+            Some(until) if Instant::now() < until => false,
+            Some(_) => {
+                state.open_until = Some(Instant::now() + self.open_for);
+                true
+            }
+            None => true,
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        *state = BreakerState::default();
+    }
+
+    /// Returns whether this failure opened the circuit.
+    pub fn record_failure(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        if state.consecutive_failures < self.failure_threshold {
+            return false;
+        }
+        let was_closed = state.open_until.is_none();
+        state.open_until = Some(Instant::now() + self.open_for);
+        was_closed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::response::IntoResponse;
+    use axum::routing::get;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn config() -> UpstreamConfig {
+        UpstreamConfig {
+            connect_timeout_ms: 1_000,
+            read_timeout_ms: 1_000,
+            max_retries: 2,
+            retry_base_delay_ms: 1,
+            retry_max_delay_ms: 50,
+            breaker_failure_threshold: 2,
+            breaker_open_seconds: 60,
+        }
+    }
+
+    /// Serves `status` with `Retry-After: 0` for the first `failures` requests, then `{}`.
+    async fn flaky_server(failures: u32, status: StatusCode) -> (String, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/",
+            get(move || {
+                let counter = counter.clone();
+                async move {
+                    if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                        (status, [(header::RETRY_AFTER, "0")], "down").into_response()
+                    } else {
+                        axum::Json(serde_json::json!({})).into_response()
+                    }
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (url, calls)
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_until_success() {
+        let (url, calls) = flaky_server(2, StatusCode::SERVICE_UNAVAILABLE).await;
+        let client = UpstreamClient::new("test", HeaderMap::new(), config()).unwrap();
+
+        let body: AppResult<serde_json::Value> = client.get_json(&url).await;
+
+        assert!(body.is_ok());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried_and_keep_the_circuit_closed() {
+        let (url, calls) = flaky_server(u32::MAX, StatusCode::NOT_FOUND).await;
+        let client = UpstreamClient::new("test", HeaderMap::new(), config()).unwrap();
+
+        for _ in 0..3 {
+            let body: AppResult<serde_json::Value> = client.get_json(&url).await;
+            assert!(matches!(body, Err(AppError::Upstream { provider, .. }) if provider == "test"));
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn open_circuit_fails_without_calling_the_provider() {
+        let (url, calls) = flaky_server(u32::MAX, StatusCode::BAD_GATEWAY).await;
+        let client = UpstreamClient::new("test", HeaderMap::new(), config()).unwrap();
+
+        for _ in 0..3 {
+            let body: AppResult<serde_json::Value> = client.get_json(&url).await;
+            assert!(body.is_err());
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn breaker_lets_one_probe_through_after_the_open_period() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+
+        assert!(breaker.record_failure());
+        assert!(!breaker.allow());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.allow());
+        assert!(!breaker.allow());
+        breaker.record_success();
+
+        assert!(breaker.allow());
+        assert!(breaker.allow());
+    }
+
+    #[test]
+    fn retry_after_is_honoured_up_to_the_maximum_delay() {
+        let config = config();
+
+        assert_eq!(
+            retry_delay(&config, 0, Some(Duration::from_millis(40))),
+            Some(Duration::from_millis(40))
+        );
+        assert_eq!(retry_delay(&config, 0, Some(Duration::from_secs(1))), None);
+        assert!(retry_delay(&config, 10, None).unwrap() <= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_dates() {
+        let now = DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
@@ -1,16 +1,38 @@
 use crate::adapters::client::wallet_holdings::{JupiterHoldingsResponse, StakedJupResponse};
 use crate::app_error::{AppError, AppResult};
+use crate::entities::token_amount::TokenAmount;
 use crate::entities::wallet_holdings::WalletHoldings;
+use crate::infrastructure::upstream::{UpstreamClient, UpstreamConfig};
 use axum::http::{HeaderMap, HeaderValue};
-use reqwest::Client;
 use std::collections::HashMap;
 use std::env;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Mint of the JUP token on mainnet.
+pub const JUP_MINT: &str = "JUPyiwrYJFskUPiHa7hkeR8VUtAeFoSYbKedZNsDvCN";
+
+/// Mint the Catalytics governance endpoint reports staked balances for, mainnet JUP unless
+/// `JUP_TOKEN_ADDRESS` is set. It is the only mint that can be tracked as `staked`.
+pub fn jup_mint() -> String {
+    env::var("JUP_TOKEN_ADDRESS").unwrap_or_else(|_| JUP_MINT.to_string())
+}
+
+/// Client for the Catalytics API. Every provider reading staked balances shares one, so
+/// they count failures against the same circuit instead of each retrying a provider that is
+/// down.
+pub fn catalytics_client(config: UpstreamConfig) -> AppResult<UpstreamClient> {
+    UpstreamClient::new("catalytics", HeaderMap::new(), config)
+}
 
 #[derive(Debug, Clone)]
 pub struct HttpWalletHoldingsConfig {
     pub jupiter_base_url: String,
     pub jupiter_api_key: String,
     pub catalytics_base_url: String,
+    /// Mint the Catalytics governance endpoint reports staked balances for.
+    pub jup_mint: String,
+    pub upstream: UpstreamConfig,
 }
 
 impl Default for HttpWalletHoldingsConfig {
@@ -19,33 +41,31 @@ impl Default for HttpWalletHoldingsConfig {
             jupiter_base_url: env::var("JUPITER_API_BASE_URL").unwrap(),
             jupiter_api_key: env::var("JUPITER_API_KEY").unwrap(),
             catalytics_base_url: env::var("CATALYTICS_API_BASE_URL").unwrap(),
+            jup_mint: jup_mint(),
+            upstream: UpstreamConfig::default(),
         }
     }
 }
 
 #[derive(Debug)]
 pub struct HttpWalletHoldingClient {
-    pub jupiter_client: Client,
-    pub catalytics_client: Client,
+    pub jupiter_client: UpstreamClient,
+    pub catalytics_client: Arc<UpstreamClient>,
     pub config: HttpWalletHoldingsConfig,
 }
 
 impl HttpWalletHoldingClient {
-    pub fn new(config: HttpWalletHoldingsConfig) -> AppResult<Self> {
+    pub fn new(
+        config: HttpWalletHoldingsConfig,
+        catalytics_client: Arc<UpstreamClient>,
+    ) -> AppResult<Self> {
         let mut headers = HeaderMap::new();
 
         let api_key_header = HeaderValue::from_str(&config.jupiter_api_key)
             .map_err(|e| AppError::Internal(format!("Invalid API key format: {}", e)))?;
         headers.insert("x-api-key", api_key_header);
 
-        let jupiter_client = Client::builder()
-            .default_headers(headers)
-            .build()
-            .map_err(|e| AppError::Internal(format!("Failed to build client: {}", e)))?;
-        let catalytics_client = Client::builder()
-            .default_headers(HeaderMap::new())
-            .build()
-            .map_err(|e| AppError::Internal(format!("Failed to build client: {}", e)))?;
+        let jupiter_client = UpstreamClient::new("jupiter", headers, config.upstream.clone())?;
 
         Ok(Self {
             jupiter_client,
@@ -54,8 +74,8 @@ impl HttpWalletHoldingClient {
         })
     }
 
-    pub fn with_defaults() -> AppResult<Self> {
-        Self::new(HttpWalletHoldingsConfig::default())
+    pub fn with_defaults(catalytics_client: Arc<UpstreamClient>) -> AppResult<Self> {
+        Self::new(HttpWalletHoldingsConfig::default(), catalytics_client)
     }
 
     pub async fn make_jupiter_get_request<T: serde::de::DeserializeOwned>(
@@ -63,28 +83,7 @@ impl HttpWalletHoldingClient {
         endpoint: &str,
     ) -> AppResult<T> {
         let url = format!("{}{}", self.config.jupiter_base_url, endpoint);
-
-        let response = self
-            .jupiter_client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("HTTP request failed: {}", e)))?;
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "Jupiter API request failed with status {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .unwrap_or_else(|_| "Unknown error".to_string())
-            )));
-        }
-        let body = response
-            .json::<T>()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse JSON response: {}", e)))?;
-        Ok(body)
+        self.jupiter_client.get_json(&url).await
     }
 
     pub async fn make_catalytics_get_request<T: serde::de::DeserializeOwned>(
@@ -92,54 +91,47 @@ impl HttpWalletHoldingClient {
         endpoint: &str,
     ) -> AppResult<T> {
         let url = format!("{}{}", self.config.catalytics_base_url, endpoint);
-
-        let response = self
-            .catalytics_client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| AppError::Internal(format!("HTTP request failed: {}", e)))?;
-        if !response.status().is_success() {
-            return Err(AppError::Internal(format!(
-                "Jupiter API request failed with status {}: {}",
-                response.status(),
-                response
-                    .text()
-                    .await
-                    .unwrap_or_else(|_| "Unknown error".to_string())
-            )));
-        }
-        let body = response
-            .json::<T>()
-            .await
-            .map_err(|e| AppError::Internal(format!("Failed to parse JSON response: {}", e)))?;
-        Ok(body)
+        self.catalytics_client.get_json(&url).await
     }
 
     pub async fn convert_to_wallet_holdings(
         &self,
         public_key: String,
         holdings: JupiterHoldingsResponse,
-        staked_jup: StakedJupResponse,
+        staked_jup: AppResult<StakedJupResponse>,
     ) -> AppResult<WalletHoldings> {
         let mut token_holdings = HashMap::new();
 
         if let Some(tokens) = holdings.tokens {
             for (token_mint, token_accounts) in tokens {
-                let total_balance: f64 =
-                    token_accounts.iter().map(|account| account.ui_amount).sum();
+                let mut total_balance: Option<TokenAmount> = None;
+                for account in token_accounts {
+                    let decimals = u8::try_from(account.decimals).map_err(|_| {
+                        jupiter_error(format!("Invalid decimals {}", account.decimals))
+                    })?;
+                    let amount = TokenAmount::from_raw_str(&account.amount, decimals)
+                        .map_err(jupiter_error)?;
+                    total_balance = Some(match total_balance {
+                        Some(total) => total.checked_add(amount).ok_or_else(|| {
+                            jupiter_error(format!("Token amount overflow for mint {}", token_mint))
+                        })?,
+                        None => amount,
+                    });
+                }
 
-                if total_balance > 0.0 {
+                if let Some(total_balance) = total_balance.filter(|total| total.raw > 0) {
                     token_holdings.insert(token_mint, total_balance);
                 }
             }
         }
 
-        let mut staked_token_holdings = HashMap::new();
-        staked_token_holdings.insert(
-            env::var("JUP_TOKEN_ADDRESS").unwrap(),
-            staked_jup.staked_jup,
-        );
+        let staked_token_holdings = staked_jup
+            .and_then(|staked_jup| staked_jup.amount())
+            .map(|amount| HashMap::from([(self.config.jup_mint.clone(), amount)]))
+            .map_err(|e| {
+                warn!("Staked JUP is unavailable for {}: {}", public_key, e);
+                e.to_string()
+            });
 
         Ok(WalletHoldings {
             public_key,
@@ -148,3 +140,10 @@ impl HttpWalletHoldingClient {
         })
     }
 }
+
+fn jupiter_error(message: String) -> AppError {
+    AppError::Upstream {
+        provider: "jupiter".to_string(),
+        message,
+    }
+}
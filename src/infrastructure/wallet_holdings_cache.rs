@@ -0,0 +1,276 @@
+use crate::app_error::AppResult;
+use crate::entities::wallet_holdings::{WalletHoldings, WalletHoldingsCacheStats};
+use crate::use_cases::wallet_holdings::WalletHoldingsClient;
+use std::collections::HashMap;
+use std::env;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+#[derive(Debug, Clone)]
+pub struct WalletHoldingsCacheConfig {
+    /// How long fetched holdings are reused for the same wallet. With 0, only concurrent
+    /// requests for a wallet share a fetch.
+    pub ttl_seconds: u64,
+    /// Expired entries are dropped once the cache holds this many wallets.
+    pub max_entries: usize,
+}
+
+impl Default for WalletHoldingsCacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: env::var("WALLET_HOLDINGS_CACHE_TTL_SECONDS")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(30),
+            max_entries: env::var("WALLET_HOLDINGS_CACHE_MAX_ENTRIES")
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(10_000),
+        }
+    }
+}
+
+type SharedFetch = Arc<OnceCell<AppResult<Arc<WalletHoldings>>>>;
+
+#[derive(Debug)]
+struct CachedHoldings {
+    holdings: Arc<WalletHoldings>,
+    expires_at: Instant,
+}
+
+/// Wraps another client, keeps its holdings per wallet for the configured TTL and lets
+/// concurrent requests for the same wallet wait for a single upstream fetch.
+#[derive(Debug)]
+pub struct CachedWalletHoldingsClient {
+    inner: Arc<dyn WalletHoldingsClient>,
+    config: WalletHoldingsCacheConfig,
+    entries: Mutex<HashMap<String, CachedHoldings>>,
+    in_flight: Mutex<HashMap<String, SharedFetch>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+impl CachedWalletHoldingsClient {
+    pub fn new(inner: Arc<dyn WalletHoldingsClient>, config: WalletHoldingsCacheConfig) -> Self {
+        Self {
+            inner,
+            config,
+            entries: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            coalesced: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn holdings(&self, public_key: &str) -> AppResult<Arc<WalletHoldings>> {
+        if let Some(holdings) = self.cached(public_key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(holdings);
+        }
+
+        let fetch = {
+            let mut in_flight = lock(&self.in_flight);
+            match in_flight.get(public_key) {
+                Some(fetch) => {
+                    self.coalesced.fetch_add(1, Ordering::Relaxed);
+                    fetch.clone()
+                }
+                None => {
+                    let fetch = SharedFetch::default();
+                    in_flight.insert(public_key.to_string(), fetch.clone());
+                    fetch
+                }
+            }
+        };
+        fetch
+            .get_or_init(|| self.fetch(public_key, &fetch))
+            .await
+            .clone()
+    }
+
+    pub fn stats(&self) -> WalletHoldingsCacheStats {
+        WalletHoldingsCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            entries: lock(&self.entries).len(),
+        }
+    }
+
+    async fn fetch(&self, public_key: &str, fetch: &SharedFetch) -> AppResult<Arc<WalletHoldings>> {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let result = self
+            .inner
+            .get_wallet_holdings(public_key)
+            .await
+            .map(Arc::new);
+        // Holdings without staked balances are not kept, so the next request retries them
+        if let Ok(holdings) = &result
+            && holdings.staked_token_holdings.is_ok()
+        {
+            self.store(public_key, holdings.clone());
+        }
+
+        // Failed fetches are shared with the requests already waiting, but the next request
+        // starts a new one.
+        let mut in_flight = lock(&self.in_flight);
+        if in_flight
+            .get(public_key)
+            .is_some_and(|current| Arc::ptr_eq(current, fetch))
+        {
+            in_flight.remove(public_key);
+        }
+        result
+    }
+
+    fn cached(&self, public_key: &str) -> Option<Arc<WalletHoldings>> {
+        let entries = lock(&self.entries);
+        entries
+            .get(public_key)
+            .filter(|cached| cached.expires_at > Instant::now())
+            .map(|cached| cached.holdings.clone())
+    }
+
+    fn store(&self, public_key: &str, holdings: Arc<WalletHoldings>) {
+        if self.config.ttl_seconds == 0 || self.config.max_entries == 0 {
+            return;
+        }
+        let now = Instant::now();
+        let mut entries = lock(&self.entries);
+        if entries.len() >= self.config.max_entries && !entries.contains_key(public_key) {
+            entries.retain(|_, cached| cached.expires_at > now);
+            if entries.len() >= self.config.max_entries {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, cached)| cached.expires_at)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(
+            public_key.to_string(),
+            CachedHoldings {
+                holdings,
+                expires_at: now + Duration::from_secs(self.config.ttl_seconds),
+            },
+        );
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_error::AppError;
+    use crate::entities::token_amount::TokenAmount;
+    use async_trait::async_trait;
+    use std::sync::atomic::AtomicUsize;
+
+    const MINT: &str = "mint";
+
+    #[derive(Debug, Default)]
+    struct CountingClient {
+        calls: AtomicUsize,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl WalletHoldingsClient for CountingClient {
+        async fn get_wallet_holdings(&self, public_key: &str) -> AppResult<WalletHoldings> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            if self.fail {
+                return Err(AppError::Internal("upstream down".to_string()));
+            }
+            Ok(WalletHoldings {
+                public_key: public_key.to_string(),
+                token_holdings: HashMap::from([(MINT.to_string(), TokenAmount::new(5, 0))]),
+                staked_token_holdings: Ok(HashMap::from([(
+                    MINT.to_string(),
+                    TokenAmount::new(7, 0),
+                )])),
+            })
+        }
+
+        async fn get_token_balance(&self, _: &str, _: &str) -> AppResult<TokenAmount> {
+            unreachable!()
+        }
+
+        async fn get_staked_token_balance(&self, _: &str, _: &str) -> AppResult<TokenAmount> {
+            unreachable!()
+        }
+    }
+
+    fn cached_client(inner: &Arc<CountingClient>, ttl_seconds: u64) -> CachedWalletHoldingsClient {
+        CachedWalletHoldingsClient::new(
+            inner.clone(),
+            WalletHoldingsCacheConfig {
+                ttl_seconds,
+                max_entries: 10,
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn concurrent_requests_share_one_fetch_and_later_ones_hit_the_cache() {
+        let inner = Arc::new(CountingClient::default());
+        let client = cached_client(&inner, 60);
+
+        let (held, staked) = tokio::join!(
+            client.get_token_balance("wallet", MINT),
+            client.get_staked_token_balance("wallet", MINT)
+        );
+        client.get_token_balance("wallet", MINT).await.unwrap();
+
+        assert_eq!(
+            (held.unwrap(), staked.unwrap()),
+            (TokenAmount::new(5, 0), TokenAmount::new(7, 0))
+        );
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            client.stats(),
+            WalletHoldingsCacheStats {
+                hits: 1,
+                misses: 1,
+                coalesced: 1,
+                entries: 1,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn failures_reach_every_waiter_and_are_not_cached() {
+        let inner = Arc::new(CountingClient {
+            fail: true,
+            ..Default::default()
+        });
+        let client = cached_client(&inner, 60);
+
+        let (first, second) = tokio::join!(client.holdings("wallet"), client.holdings("wallet"));
+        let third = client.holdings("wallet").await;
+
+        assert!(first.is_err() && second.is_err() && third.is_err());
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(client.stats().entries, 0);
+    }
+
+    #[tokio::test]
+    async fn zero_ttl_only_coalesces() {
+        let inner = Arc::new(CountingClient::default());
+        let client = cached_client(&inner, 0);
+
+        client.holdings("wallet").await.unwrap();
+        client.holdings("wallet").await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
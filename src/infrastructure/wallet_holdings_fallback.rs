@@ -0,0 +1,63 @@
+use crate::app_error::{AppError, AppResult};
+use crate::entities::wallet_holdings::WalletHoldings;
+use crate::use_cases::wallet_holdings::WalletHoldingsClient;
+use std::env;
+use std::sync::Arc;
+use tracing::warn;
+
+#[derive(Debug, Clone)]
+pub struct WalletHoldingsProvidersConfig {
+    /// Provider names, `jupiter` or `rpc`, tried in order until one answers. Defaults to
+    /// Jupiter, followed by the RPC node when `SOLANA_RPC_URL` is set. Both read staked JUP
+    /// from the Catalytics API, so the order only adds redundancy for held balances.
+    pub providers: Vec<String>,
+}
+
+impl Default for WalletHoldingsProvidersConfig {
+    fn default() -> Self {
+        let default_providers = if env::var("SOLANA_RPC_URL").is_ok() {
+            "jupiter,rpc"
+        } else {
+            "jupiter"
+        };
+        Self {
+            providers: env::var("WALLET_HOLDINGS_PROVIDERS")
+                .unwrap_or_else(|_| default_providers.to_string())
+                .split(',')
+                .map(|provider| provider.trim().to_string())
+                .filter(|provider| !provider.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Asks each provider in turn and returns the first holdings one of them could read.
+#[derive(Debug)]
+pub struct FallbackWalletHoldingsClient {
+    providers: Vec<(String, Arc<dyn WalletHoldingsClient>)>,
+}
+
+impl FallbackWalletHoldingsClient {
+    pub fn new(providers: Vec<(String, Arc<dyn WalletHoldingsClient>)>) -> Self {
+        Self { providers }
+    }
+
+    pub async fn holdings(&self, public_key: &str) -> AppResult<WalletHoldings> {
+        let mut last_error = None;
+        for (name, provider) in &self.providers {
+            match provider.get_wallet_holdings(public_key).await {
+                Ok(holdings) => return Ok(holdings),
+                Err(e) => {
+                    warn!(
+                        "Wallet holdings provider {} failed for {}: {}",
+                        name, public_key, e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| {
+            AppError::Internal("No wallet holdings provider is configured".to_string())
+        }))
+    }
+}
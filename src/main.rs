@@ -1,5 +1,6 @@
 use catalytics_core::infrastructure::app::create_app;
-use catalytics_core::infrastructure::setup::init_app_state;
+use catalytics_core::infrastructure::scheduler::{SchedulerConfig, init_scheduler};
+use catalytics_core::infrastructure::setup::{init_app_state, init_tracing};
 use dotenvy::dotenv;
 use tracing::info;
 
@@ -7,8 +8,23 @@ use tracing::info;
 async fn main() -> anyhow::Result<()> {
     dotenv().ok();
 
+    let worker = std::env::args().any(|arg| arg == "--worker");
+    let scheduler_config = SchedulerConfig::default();
     let app_state = init_app_state().await?;
+
+    if worker {
+        init_tracing();
+        init_scheduler(&app_state, &scheduler_config)?.spawn();
+        info!("Catalytics Core running in worker mode");
+        std::future::pending::<()>().await;
+        return Ok(());
+    }
+
+    let scheduler = init_scheduler(&app_state, &scheduler_config)?;
     let app = create_app(app_state);
+    if scheduler_config.enabled {
+        scheduler.spawn();
+    }
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
 